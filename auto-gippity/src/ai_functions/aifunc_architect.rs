@@ -43,3 +43,52 @@ pub fn print_site_urls(_project_description: &str) {
     ///     ["https://api.binance.com/api/v3/exchangeInfo", "https://api.binance.com/api/v3/klines?sysbol=BTCUSDT&interval=1d"]
     println!(OUTPUT)
 }
+
+#[ai_function]
+pub fn print_backend_framework(_project_description: &str) {
+    /// Input: Takes in a project description of a website build and the AVAILABLE_FRAMEWORKS
+    /// Function: Chooses the Rust web framework from AVAILABLE_FRAMEWORKS best suited to build the backend
+    /// Important: Only selects a name listed in AVAILABLE_FRAMEWORKS. When no framework stands out, selects "actix-web"
+    /// Output: Prints the framework name as a JSON string
+    /// Example:
+    ///     project_description = "build a website that tracks gym workouts. AVAILABLE_FRAMEWORKS: actix-web, axum, rocket"
+    ///     prints:
+    ///     "axum"
+    println!(OUTPUT)
+}
+
+#[ai_function]
+pub fn print_requirements_spec(_project_description_and_scope: &str) {
+    /// Input: Takes in a PROJECT_DESCRIPTION of a website build and the PROJECT_SCOPE decided for it
+    /// Function: Writes the requirements specification of the backend: the entities it stores with their fields, user stories, non-functional needs and acceptance criteria
+    /// Important: Field types are "number", "string" or "bool". Every stored entity has an "id" number field
+    /// Important: "persistence" is true when data has to survive a restart. "rate_limit_per_minute" and "page_size" stay null unless PROJECT_DESCRIPTION asks for limits or many items
+    /// Important: Acceptance criteria about one endpoint give its "method", "route" and the successful "expected_status" (200 or 201). Criteria about errors or things no single endpoint shows leave them null
+    /// Output: Prints an object response in the following format:
+    ///     {
+    ///         "entities": [{ "name": "Task", "fields": [{ "name": "id", "type": "number", "required": true }, { "name": "title", "type": "string", "required": true }, { "name": "done", "type": "bool", "required": false }] }],
+    ///         "user_stories": [{ "actor": "user", "action": "add a task with a title", "benefit": "I do not forget it" }],
+    ///         "non_functional": { "persistence": true, "rate_limit_per_minute": null, "page_size": null, "notes": [] },
+    ///         "acceptance_criteria": [
+    ///             { "description": "Creating a task returns it with its new id", "method": "POST", "route": "/task", "expected_status": 201 },
+    ///             { "description": "Unknown task ids answer 404", "method": null, "route": null, "expected_status": null }
+    ///         ]
+    ///     }
+    println!(OUTPUT)
+}
+
+#[ai_function]
+pub fn print_route_plan(_project_description_and_requirements: &str) {
+    /// Input: Takes in a PROJECT_DESCRIPTION of a website build and its REQUIREMENTS with ENTITIES, USER_STORIES and ACCEPTANCE_CRITERIA
+    /// Function: Plans every route the backend serves before any code is written, with the request body it reads and the response it returns, using the fields of ENTITIES
+    /// Important: Dynamic path segments are written with curly braces such as {id}. Every route an acceptance criterion names is in the plan with the same method and path
+    /// Important: Leaves out signup, login, logout and current user routes, a vetted auth module serves them. Plans no routes the project does not need
+    /// Must read: All keys are strings. Types are written as "number", "string" or "bool", "None" when there is no body
+    /// Output: Prints a JSON list in the following format:
+    ///     [
+    ///         { "route": "/task", "is_route_dynamic": "false", "method": "post", "request_body": { "title": "string", "done": "bool" }, "response": { "id": "number", "title": "string", "done": "bool" } },
+    ///         { "route": "/task", "is_route_dynamic": "false", "method": "get", "request_body": "None", "response": [{ "id": "number", "title": "string", "done": "bool" }] },
+    ///         { "route": "/task/{id}", "is_route_dynamic": "true", "method": "delete", "request_body": "None", "response": "None" }
+    ///     ]
+    println!(OUTPUT)
+}
@@ -2,48 +2,66 @@ use ai_functions::ai_function;
 
 #[ai_function]
 pub fn print_backend_webserver_code(_project_description_and_template: &str) {
-    /// Input: Takes in a PROJECT_DESCRIPTION and a CODE_TEMPLATE for a website backend build
+    /// Input: Takes in a PROJECT_DESCRIPTION, a FRAMEWORK with its ALLOWED_LIBRARIES and FRAMEWORK_NOTES, and a CODE_TEMPLATE for a website backend build
     /// Function: Takes an existing set of code marked as CODE_TEMPLATE and updates or re-writes it to work for the purpose in PROJECT_DESCRIPTION
-    /// Important: The following libraries are already installed
-    ///    reqwest, serde, serde_json, tokio, actix-web, async-trait, actix_cors
-    /// Therefore, this function can only work with code from the standard Rust library or the above as per shown in the CODE_TEMPLATE
+    /// Important: Only the libraries listed in ALLOWED_LIBRARIES are installed
+    /// Therefore, this function can only work with code from the standard Rust library or ALLOWED_LIBRARIES as per shown in the CODE_TEMPLATE, following the FRAMEWORK_NOTES
+    /// Important: When REQUIREMENTS are given, the code stores every listed entity with its fields, supports every user story, meets the non-functional needs and answers each acceptance criterion's route with its expected_status
+    /// Important: When ENTITY_MODELS and a ROUTE_PLAN are given, uses those structs and serves exactly the routes in ROUTE_PLAN with their methods, paths and request bodies, no more and no fewer
+    /// Important: When REVIEW_FEEDBACK is given, the previous attempt fell short of PROJECT_DESCRIPTION and every point in it has to be addressed
     /// Output: Print ONLY the code, nothing else. This function ONLY prints code.
     println!(OUTPUT)
 }
 
 #[ai_function]
 pub fn print_improved_webserrver_code(_project_description_and_template: &str) {
-    /// Input: Takes in a PROJECT_DESCRIPTION and a CODE_TEMPLATE for a website backend build
+    /// Input: Takes in a PROJECT_DESCRIPTION, a FRAMEWORK with its ALLOWED_LIBRARIES and FRAMEWORK_NOTES, a CODE_TEMPLATE for a website backend build and a REVIEW_CHECKLIST of gaps and defects found in it
     /// Function: Performs the following tasks:
-    ///     1. Removes any bugs in the code and adds minor additional functionality
+    ///     1. Works through every item in REVIEW_CHECKLIST, fixing each defect and implementing each gap
     ///     2. Makes sure everything requested in the spec from a backend standpoint was followed. If not, add the feature. No other features should be added.
     ///     3. ONLY writes the code. No commentary.
-    /// Important: The following libraries are already installed. Does not use ANY libraries other than what was provided in the CODE_TEMPLATE
-    ///     reqwest, serde, serde_json, tokio, actix-web, async-trait, actix_cors
+    /// Important: Only the libraries listed in ALLOWED_LIBRARIES are installed. Does not use ANY libraries other than those and the standard Rust library
     println!(OUTPUT)
 }
 
 #[ai_function]
 pub fn print_fixed_code(_broken_code_with_bugs: &str) {
-    /// Input: Takes in Rust BROKEN_CODE and the ERROR_BUGS found
+    /// Input: Takes in Rust BROKEN_CODE written for FRAMEWORK and the ERROR_BUGS found
     /// Function: Removes bugs from code
+    /// Important: ERROR_BUGS may be compiler errors or runtime evidence from a running server, such as panics, 5xx responses, dropped connections or responses not matching the API schema
     /// Important: Only prints out the new and improved code. No commentary or anything else.
+    /// Important: Only uses the standard Rust library and the libraries listed in ALLOWED_LIBRARIES
+    println!(OUTPUT)
+}
+
+#[ai_function]
+pub fn print_backend_tests(_code_input: &str) {
+    /// Input: Takes in Rust webserver CODE_INPUT written for FRAMEWORK, its ALLOWED_LIBRARIES and TEST_HARNESS notes
+    /// Function: Writes the body of a test module placed inside CODE_INPUT, which has access to every item through `use super::*;`
+    /// Logic: The module contains:
+    ///     1. Plain #[test] unit tests for helper functions and data handling that do not need a running server
+    ///     2. Integration tests for every route handler, written as described in TEST_HARNESS, checking status codes and response bodies
+    /// Important: Tests must not depend on data left behind by other tests or on the order they run in. Create what a test needs inside that test.
+    /// Important: Only uses the standard Rust library and the libraries listed in ALLOWED_LIBRARIES
+    /// Output: Print ONLY the module body, starting with `use super::*;`. No `mod tests` wrapper, no #[cfg(test)] attribute and no commentary.
     println!(OUTPUT)
 }
 
 #[ai_function]
 pub fn print_rest_api_endpoints(_code_input: &str) {
-    /// Input: Takes in Rust webserver CODE_INPUT based on actix-web
+    /// Input: Takes in Rust webserver CODE_INPUT based on FRAMEWORK and a ROUTE_EXAMPLE of how that framework declares routes
     /// Function: Prints out the JSON schema for url endpoints and their respective types
+    /// Important: Dynamic path segments are always printed with curly braces such as {id}, whatever syntax FRAMEWORK uses
     /// Logic: Script analyses all code and can categorize into the following object keys:
     ///     "route": This represents the url path of the endpoint
     ///     "is_route_dynamic": if a route has curly braces in it such as {symbol} or {id} as an example, then this will be set to true
     ///     "method": This represents the method being called
     ///     "request_body": This represents the body of a post method request
     ///     "response": This represents the output based upon the structs in the code and understanding the functions
+    ///     "handler": This represents the name of the function handling the route
     /// Important: Only prints out the JSON schema. No commentary or anything else.
     /// Must read: All keys are strings. Even bool should be wrapped in double quotes as "bool"
-    /// Example:
+    /// Example (actix-web):
     /// input_code:
     /// ...
     /// pub struct Item {
@@ -108,3 +126,33 @@ pub fn print_rest_api_endpoints(_code_input: &str) {
     /// ]
     println!(OUTPUT)
 }
+
+#[ai_function]
+pub fn print_route_responses(_code_input_and_routes: &str) {
+    /// Input: Takes in Rust webserver CODE_INPUT and the ROUTES it declares as a JSON list of objects with "method", "route" and "handler" keys
+    /// Function: Works out the JSON body each route handler responds with, based upon the structs in the code and understanding the functions
+    /// Important: Only prints out the JSON list. No commentary or anything else.
+    /// Must read: Types are written as strings such as "number", "string" or "bool". A route that responds without a body prints "None", a route whose response cannot be worked out prints "not_provided"
+    /// Output: Prints one object per route in ROUTES, in the same order, with the following keys:
+    ///     "method", "route", "response"
+    /// Example:
+    /// ROUTES: [{"method": "get", "route": "/item/{id}", "handler": "read_item"}, {"method": "delete", "route": "/item/{id}", "handler": "delete_item"}]
+    /// prints:
+    /// [
+    ///     {
+    ///         "method": "get",
+    ///         "route": "/item/{id}",
+    ///         "response": {
+    ///             "id": "number",
+    ///             "name": "string",
+    ///             "completed": "bool"
+    ///         }
+    ///     },
+    ///     {
+    ///         "method": "delete",
+    ///         "route": "/item/{id}",
+    ///         "response": "None"
+    ///     }
+    /// ]
+    println!(OUTPUT)
+}
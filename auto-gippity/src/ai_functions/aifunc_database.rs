@@ -0,0 +1,47 @@
+use ai_functions::ai_function;
+
+#[ai_function]
+pub fn print_database_design(_project_description: &str) {
+    /// Input: Takes in a PROJECT_DESCRIPTION of a website that needs to create, read, update and delete data
+    /// Function: Designs a relational SQLite schema for the data in PROJECT_DESCRIPTION and the SQL migrations creating it
+    /// Important: Every table has an `id INTEGER PRIMARY KEY` column. Relations use foreign keys to the `id` of the other table.
+    /// Important: Migrations only contain SQLite statements, run in the listed order on an empty database, and together create every table and column listed in "tables"
+    /// Important: When REVIEW_FEEDBACK is given, the previous attempt fell short of PROJECT_DESCRIPTION and every point in it has to be addressed
+    /// Output: Prints an object response in the following format:
+    ///     {
+    ///         "tables": [
+    ///             {
+    ///                 "name": "tasks",
+    ///                 "columns": [
+    ///                     { "name": "id", "sql_type": "INTEGER", "primary_key": true, "nullable": false },
+    ///                     { "name": "name", "sql_type": "TEXT", "primary_key": false, "nullable": false },
+    ///                     { "name": "user_id", "sql_type": "INTEGER", "primary_key": false, "nullable": true, "references": "users.id" }
+    ///                 ]
+    ///             }
+    ///         ],
+    ///         "migrations": [
+    ///             { "name": "create_tasks", "sql": "CREATE TABLE tasks (id INTEGER PRIMARY KEY, name TEXT NOT NULL, user_id INTEGER REFERENCES users(id));" }
+    ///         ]
+    ///     }
+    println!(OUTPUT)
+}
+
+#[ai_function]
+pub fn print_fixed_database_design(_broken_design_with_bugs: &str) {
+    /// Input: Takes in a PROJECT_DESCRIPTION, a BROKEN_DESIGN of SQLite tables and migrations, and the ERROR_BUGS found applying the migrations to an empty database
+    /// Function: Fixes the tables and migrations so the migrations apply cleanly and create every table and column listed in "tables"
+    /// Output: Prints the fixed design in the same JSON format as BROKEN_DESIGN, nothing else
+    println!(OUTPUT)
+}
+
+#[ai_function]
+pub fn print_data_access_code(_database_design: &str) {
+    /// Input: Takes in the SQLite TABLES of a website backend and the MIGRATION_RUNNER module it can call
+    /// Function: Writes a Rust module named `db` with the data access code for every table:
+    ///     1. A serde Serialize and Deserialize struct per table
+    ///     2. `pub fn open_database(path: &str) -> rusqlite::Result<rusqlite::Connection>` which opens the file and calls `crate::migrations::run_migrations`
+    ///     3. Create, read one, read all, update and delete functions per table taking `&rusqlite::Connection`
+    /// Important: Only uses the standard Rust library, rusqlite and serde. Queries always use parameters, never string formatting.
+    /// Output: Print ONLY the code of the module, nothing else. This function ONLY prints code.
+    println!(OUTPUT)
+}
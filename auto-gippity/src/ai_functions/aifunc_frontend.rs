@@ -0,0 +1,22 @@
+use ai_functions::ai_function;
+
+#[ai_function]
+pub fn print_frontend_code(_project_description_and_api_schema: &str) {
+    /// Input: Takes in a PROJECT_DESCRIPTION and the API_ENDPOINT_SCHEMA of the backend built for it
+    /// Function: Writes a single page static website in plain HTML, CSS and JavaScript that lets a user do everything in PROJECT_DESCRIPTION through the endpoints in API_ENDPOINT_SCHEMA
+    /// Important: The script declares `const API_BASE_URL = window.API_BASE_URL || "http://127.0.0.1:8080";` and every request is written as fetch(`${API_BASE_URL}/path`, { method: "..." })
+    /// Important: Only calls routes listed in API_ENDPOINT_SCHEMA with their listed method, sending and reading bodies in the listed shapes
+    /// Important: Does not use any external scripts, CDNs or build tools. Everything lives in one index.html file.
+    /// Important: When REVIEW_FEEDBACK is given, the previous attempt fell short of PROJECT_DESCRIPTION and every point in it has to be addressed
+    /// Output: Print ONLY the contents of index.html, nothing else.
+    println!(OUTPUT)
+}
+
+#[ai_function]
+pub fn print_fixed_frontend_code(_broken_page_with_bugs: &str) {
+    /// Input: Takes in a static BROKEN_PAGE, the API_ENDPOINT_SCHEMA it talks to and the ERROR_BUGS found when loading it
+    /// Function: Removes the bugs from the page, keeping every request within the routes and methods in API_ENDPOINT_SCHEMA
+    /// Important: Keeps the `API_BASE_URL` constant and the single index.html layout
+    /// Output: Print ONLY the fixed contents of index.html. No commentary or anything else.
+    println!(OUTPUT)
+}
@@ -14,3 +14,48 @@ pub fn convert_user_input_to_goal(_user_request: &str) {
     ///     OUTPUT: "build a website that fetches and stores crypto price data within a supabase setup including a fronted UI"
     println!(OUTPUT)
 }
+
+#[ai_function]
+pub fn print_agent_roster(_project_description: &str) {
+    /// Input: Takes in a PROJECT_DESCRIPTION, the PROJECT_SCOPE found by the architect and the AVAILABLE_AGENTS with what each one does
+    /// Function: Chooses which of the AVAILABLE_AGENTS the project needs, in the order they should work
+    /// Important: Only uses names listed in AVAILABLE_AGENTS. Leaves out agents the project does not need, i.e. no database_designer when nothing is stored and no frontend_developer when only an API is asked for
+    /// Output: Prints a JSON list of agent names
+    /// Example:
+    ///     PROJECT_DESCRIPTION = "build a website API that stores notes" PROJECT_SCOPE = {"is_curd_required": true, "is_user_login_and_logout": false, "is_external_urls_required": false}
+    ///     prints:
+    ///     ["database_designer", "backend_developer", "documentation_writer"]
+    println!(OUTPUT)
+}
+
+#[ai_function]
+pub fn print_project_review(_project_description_and_factsheet: &str) {
+    /// Input: Takes in a PROJECT_DESCRIPTION, a PROJECT_SUMMARY of everything the agents built and test results, and the TEAM of agents that can act on feedback
+    /// Function: Checks whether the PROJECT_SUMMARY delivers every requirement in PROJECT_DESCRIPTION and, for each unmet requirement, tells the responsible agent in TEAM what to change
+    /// Important: Only reports requirements PROJECT_DESCRIPTION asks for. Failing tests and missing endpoints count as unmet. Feedback only names agents listed in TEAM and is specific enough to act on
+    /// Important: "requirements_met" is true and "feedback" is empty when nothing is missing
+    /// Output: Prints an object response in the following format:
+    ///     {
+    ///         "requirements_met": false,
+    ///         "unmet_requirements": ["Tasks cannot be deleted"],
+    ///         "feedback": [
+    ///             { "agent": "backend_developer", "feedback": "Add DELETE /task/{id} removing the task from the database" },
+    ///             { "agent": "frontend_developer", "feedback": "Add a delete button to every task calling DELETE /task/{id}" }
+    ///         ]
+    ///     }
+    println!(OUTPUT)
+}
+
+#[ai_function]
+pub fn print_clarifying_questions(_user_request_and_goal: &str) {
+    /// Input: Takes in the USER_REQUEST as typed by the user and the PROJECT_GOAL summarized from it
+    /// Function: Finds what the request leaves open that would change how the website is built and writes one question for each
+    /// Important: Only asks about data_sources (where data comes from, which external APIs), auth (whether users log in), persistence (what has to be stored and kept) and scale (how many users or how much data). Does not ask what the request already answers
+    /// Important: Asks at most 4 short questions, each with a sensible "default_answer" used when the user skips it. Prints [] when nothing is unclear
+    /// Output: Prints a JSON list in the following format:
+    ///     [
+    ///         { "topic": "data_sources", "question": "Which forex data provider should the prices come from?", "default_answer": "A free public exchange rate API" },
+    ///         { "topic": "persistence", "question": "Should price history be kept after a restart?", "default_answer": "No, only the latest prices" }
+    ///     ]
+    println!(OUTPUT)
+}
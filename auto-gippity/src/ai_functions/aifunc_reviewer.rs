@@ -0,0 +1,38 @@
+use ai_functions::ai_function;
+
+#[ai_function]
+pub fn print_code_review(_requirements_and_code: &str) {
+    /// Input: Takes in a PROJECT_DESCRIPTION, the FACT_SHEET of decisions made for the project and the backend CODE_INPUT written for it
+    /// Function: Compares the code against every requirement in PROJECT_DESCRIPTION and FACT_SHEET and lists gaps (requirements the code does not implement) and defects (bugs, missing error handling, wrong status codes, data lost on restart, unvalidated input)
+    /// Important: Each item is specific enough to be checked off, naming the handler, struct or function it concerns in "location" when there is one
+    /// Important: Does not list style preferences or features nobody asked for. Prints [] when the code is complete and correct
+    /// Output: Prints a JSON list in the following format:
+    ///     [
+    ///         {
+    ///             "kind": "gap",
+    ///             "description": "Tasks cannot be deleted although the description asks for it",
+    ///             "location": null,
+    ///             "suggestion": "Add DELETE /task/{id} removing the task from the database"
+    ///         },
+    ///         {
+    ///             "kind": "defect",
+    ///             "description": "Unknown ids return 200 with null instead of 404",
+    ///             "location": "read_task",
+    ///             "suggestion": "Return 404 Not Found when no task matches the id"
+    ///         }
+    ///     ]
+    println!(OUTPUT)
+}
+
+#[ai_function]
+pub fn print_review_verification(_checklist_and_code: &str) {
+    /// Input: Takes in a REVIEW_CHECKLIST of numbered items and the IMPROVED_CODE meant to address them
+    /// Function: Checks the code for each item in REVIEW_CHECKLIST and decides whether it was handled
+    /// Important: An item only counts as addressed when the code visibly handles it. Gives one entry per checklist item
+    /// Output: Prints a JSON list in the following format:
+    ///     [
+    ///         { "id": "R1", "addressed": true, "note": "delete_task added and registered" },
+    ///         { "id": "R2", "addressed": false, "note": "read_task still returns null for unknown ids" }
+    ///     ]
+    println!(OUTPUT)
+}
@@ -0,0 +1,20 @@
+use ai_functions::ai_function;
+
+#[ai_function]
+pub fn print_security_review(_code_and_static_findings: &str) {
+    /// Input: Takes in the FILES of a generated Rust web server, the EXTERNAL_URLS it is allowed to call and the STATIC_FINDINGS already reported by pattern checks
+    /// Function: Reviews the code for security problems a pattern check misses, such as SQL built from user input, missing input validation, path traversal through request data, secrets or personal data written to logs, overly permissive CORS or auth that can be bypassed
+    /// Important: Does not repeat anything listed in STATIC_FINDINGS. Only reports problems visible in the code, never generic advice
+    /// Important: severity is one of "low", "medium" or "high". "high" is reserved for problems an attacker can exploit or that harm the machine running the code
+    /// Output: Prints a JSON list of findings in the following format, or [] when there is nothing to report:
+    ///     [
+    ///         {
+    ///             "severity": "medium",
+    ///             "category": "sql",
+    ///             "file": "main.rs",
+    ///             "line": 42,
+    ///             "message": "search handler formats the query string into the SQL statement"
+    ///         }
+    ///     ]
+    println!(OUTPUT)
+}
@@ -1,4 +1,8 @@
 // ai functions
 pub mod aifunc_architect;
 pub mod aifunc_backend;
+pub mod aifunc_database;
+pub mod aifunc_frontend;
 pub mod aifunc_managing;
+pub mod aifunc_reviewer;
+pub mod aifunc_security;
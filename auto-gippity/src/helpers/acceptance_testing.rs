@@ -0,0 +1,156 @@
+use super::route_parser::route_shape;
+use crate::models::{
+    agents::agent_traits::EndpointTestResult,
+    general::requirements::{AcceptanceCriterion, AcceptanceResult, CriterionOutcome},
+};
+
+fn check_criterion(
+    criterion: &AcceptanceCriterion,
+    results: &[EndpointTestResult],
+) -> (CriterionOutcome, String) {
+    let (Some(method), Some(route)) = (criterion.method, criterion.route.as_ref()) else {
+        return (
+            CriterionOutcome::Unchecked,
+            "Not tied to an endpoint".to_string(),
+        );
+    };
+    // The endpoint tests only make valid calls, so error statuses cannot be seen
+    if let Some(status) = criterion.expected_status {
+        if !(200..300).contains(&status) {
+            return (
+                CriterionOutcome::Unchecked,
+                format!("Endpoint tests do not provoke status {}", status),
+            );
+        }
+    }
+
+    let shape = route_shape(route);
+    let Some(result) = results
+        .iter()
+        .find(|result| result.method == method && route_shape(&result.route) == shape)
+    else {
+        return (
+            CriterionOutcome::Unmet,
+            format!("{} {} is not served by the backend", method, route),
+        );
+    };
+
+    match (criterion.expected_status, result.status) {
+        (Some(expected), Some(status)) if expected != status => (
+            CriterionOutcome::Unmet,
+            format!(
+                "{} {} answered {}, expected {}",
+                method, route, status, expected
+            ),
+        ),
+        _ if !result.passed => (
+            CriterionOutcome::Unmet,
+            format!("{} {} failed: {}", method, route, result.message),
+        ),
+        _ => (CriterionOutcome::Met, "OK".to_string()),
+    }
+}
+
+// Check every acceptance criterion naming a route against the endpoint test that called it
+pub fn check_acceptance_criteria(
+    criteria: &[AcceptanceCriterion],
+    results: &[EndpointTestResult],
+) -> Vec<AcceptanceResult> {
+    criteria
+        .iter()
+        .map(|criterion| {
+            let (outcome, message) = check_criterion(criterion, results);
+            AcceptanceResult {
+                id: criterion.id.clone(),
+                description: criterion.description.clone(),
+                outcome,
+                message,
+            }
+        })
+        .collect()
+}
+
+// Unmet criteria for the fix loop, None when every checked one is met
+pub fn acceptance_report(results: &[AcceptanceResult]) -> Option<String> {
+    let unmet = results
+        .iter()
+        .filter(|result| result.outcome == CriterionOutcome::Unmet)
+        .map(|result| format!("{} {}: {}", result.id, result.description, result.message))
+        .collect::<Vec<String>>();
+
+    match unmet.is_empty() {
+        true => None,
+        false => Some(format!(
+            "ACCEPTANCE CRITERIA NOT MET:\n{}",
+            unmet.join("\n")
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::general::api_schema::HttpMethod;
+
+    fn criterion(
+        method: HttpMethod,
+        route: &str,
+        expected_status: Option<u16>,
+    ) -> AcceptanceCriterion {
+        AcceptanceCriterion {
+            id: format!("AC {} {}", method, route),
+            description: String::new(),
+            method: Some(method),
+            route: Some(route.to_string()),
+            expected_status,
+        }
+    }
+
+    fn result(method: HttpMethod, route: &str, status: u16) -> EndpointTestResult {
+        EndpointTestResult {
+            method,
+            route: route.to_string(),
+            url: String::new(),
+            status: Some(status),
+            passed: (200..300).contains(&status),
+            message: format!("status {}", status),
+            contract_violations: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_checks_criteria_against_endpoint_results() {
+        let results = [
+            result(HttpMethod::Post, "/task", 200),
+            result(HttpMethod::Get, "/task/{task_id}", 200),
+            result(HttpMethod::Delete, "/task/{id}", 500),
+        ];
+        let criteria = [
+            criterion(HttpMethod::Post, "/task", Some(201)),
+            criterion(HttpMethod::Get, "/task/{id}", None),
+            criterion(HttpMethod::Delete, "/task/{id}", Some(204)),
+            criterion(HttpMethod::Get, "/task/{id}", Some(404)),
+            criterion(HttpMethod::Put, "/task/{id}", None),
+        ];
+
+        let outcomes = check_acceptance_criteria(&criteria, &results)
+            .iter()
+            .map(|result| result.outcome)
+            .collect::<Vec<CriterionOutcome>>();
+        assert_eq!(
+            outcomes,
+            vec![
+                CriterionOutcome::Unmet,
+                CriterionOutcome::Met,
+                CriterionOutcome::Unmet,
+                CriterionOutcome::Unchecked,
+                CriterionOutcome::Unmet,
+            ]
+        );
+
+        let report =
+            acceptance_report(&check_acceptance_criteria(&criteria, &results)).unwrap_or_default();
+        assert!(report.contains("POST /task answered 200, expected 201"));
+        assert!(report.contains("PUT /task/{id} is not served by the backend"));
+    }
+}
@@ -0,0 +1,379 @@
+use super::{command_line::PrintCommand, endpoint_testing::sample_value};
+use crate::models::{
+    agents::agent_traits::{EndpointTestResult, RouteObject},
+    general::{api_schema::HttpMethod, auth_schema::AuthSetup},
+};
+use reqwest::{
+    header::{COOKIE, SET_COOKIE},
+    Client, Response,
+};
+use serde_json::json;
+use std::{
+    collections::BTreeSet,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use syn::{visit::Visit, FnArg, ItemFn};
+
+struct GuardedHandlerCollector<'a> {
+    guard_type: &'a str,
+    handlers: BTreeSet<String>,
+}
+
+impl<'ast> Visit<'ast> for GuardedHandlerCollector<'_> {
+    fn visit_item_fn(&mut self, item: &'ast ItemFn) {
+        let is_guarded = item.sig.inputs.iter().any(|input| match input {
+            FnArg::Typed(argument) => {
+                let ty = &argument.ty;
+                quote::quote!(#ty)
+                    .into_iter()
+                    .any(|token| token.to_string() == self.guard_type)
+            }
+            FnArg::Receiver(_) => false,
+        });
+
+        if is_guarded {
+            self.handlers.insert(item.sig.ident.to_string());
+        }
+
+        syn::visit::visit_item_fn(self, item);
+    }
+}
+
+// Handlers taking the auth guard as an argument, i.e. fn list(user: auth::AuthenticatedUser)
+pub fn guarded_handlers(code: &str, guard_type: &str) -> BTreeSet<String> {
+    let mut collector = GuardedHandlerCollector {
+        guard_type,
+        handlers: BTreeSet::new(),
+    };
+
+    if let Ok(file) = syn::parse_file(code) {
+        collector.visit_file(&file);
+    }
+
+    collector.handlers
+}
+
+// Routes outside the auth module served by a guarded handler
+pub fn protected_routes<'a>(
+    routes: &'a [RouteObject],
+    guarded: &BTreeSet<String>,
+) -> Vec<&'a RouteObject> {
+    routes
+        .iter()
+        .filter(|route| !AuthSetup::is_auth_route(&route.route))
+        .filter(|route| {
+            route
+                .handler
+                .as_ref()
+                .is_some_and(|handler| guarded.contains(handler))
+        })
+        .collect()
+}
+
+#[derive(Debug, Default)]
+pub struct AuthTestReport {
+    pub results: Vec<EndpointTestResult>,
+    pub issues: Vec<String>,
+    // Cookie header of a logged in test user, for testing the remaining endpoints
+    pub session_cookie: Option<String>,
+}
+
+impl AuthTestReport {
+    // Bug description of every failed auth check, None when authentication works
+    pub fn failure_report(&self) -> Option<String> {
+        let mut lines = self.issues.clone();
+        lines.extend(
+            self.results
+                .iter()
+                .filter(|result| !result.passed)
+                .map(|result| format!("{} {}: {}", result.method, result.url, result.message)),
+        );
+
+        match lines.is_empty() {
+            true => None,
+            false => Some(format!("AUTH CHECK FAILED:\n{}", lines.join("\n"))),
+        }
+    }
+}
+
+fn session_cookie(response: &Response, cookie_name: &str) -> Option<String> {
+    response
+        .headers()
+        .get_all(SET_COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .map(|value| value.split(';').next().unwrap_or_default().trim())
+        .find(|pair| {
+            pair.split_once('=')
+                .is_some_and(|(name, value)| name == cookie_name && !value.is_empty())
+        })
+        .map(|pair| pair.to_string())
+}
+
+struct AuthCheck<'a> {
+    client: &'a Client,
+    base_url: &'a str,
+    results: Vec<EndpointTestResult>,
+}
+
+impl AuthCheck<'_> {
+    // Send one request and record whether its status is one of `expected`
+    async fn expect(
+        &mut self,
+        method: HttpMethod,
+        route: &str,
+        path: &str,
+        body: Option<serde_json::Value>,
+        cookie: Option<&str>,
+        expected: &[u16],
+    ) -> Option<Response> {
+        let url = format!("{}{}", self.base_url, path);
+        let mut request = self.client.request(method.as_reqwest(), &url);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        if let Some(cookie) = cookie {
+            request = request.header(COOKIE, cookie);
+        }
+
+        let (status, response) = match request.send().await {
+            Ok(response) => (Some(response.status().as_u16()), Some(response)),
+            Err(_) => (None, None),
+        };
+
+        let passed = status.is_some_and(|status| expected.contains(&status));
+        let message = match (passed, status) {
+            (true, _) => "OK".to_string(),
+            (false, Some(status)) => format!("Expected status {:?}, got {}", expected, status),
+            (false, None) => "Request failed".to_string(),
+        };
+
+        self.results.push(EndpointTestResult {
+            method,
+            route: route.to_string(),
+            url,
+            status,
+            passed,
+            message,
+            contract_violations: Vec::new(),
+        });
+
+        response
+    }
+}
+
+// Sign up and log in a fresh user, returning its session cookie
+async fn log_in(
+    check: &mut AuthCheck<'_>,
+    setup: &AuthSetup,
+    username: &str,
+    password: &str,
+) -> Option<String> {
+    let credentials = json!({ "username": username, "password": password });
+
+    let response = check
+        .expect(
+            HttpMethod::Post,
+            &setup.login_route,
+            &setup.login_route,
+            Some(credentials),
+            None,
+            &[200],
+        )
+        .await?;
+
+    session_cookie(&response, &setup.session_cookie)
+}
+
+// Protected routes must reject anonymous requests, and the session flow must work end to end
+pub async fn run_auth_tests(
+    client: &Client,
+    base_url: &str,
+    setup: &AuthSetup,
+    routes: &[RouteObject],
+    backend_code: &str,
+    agent_position: &str,
+) -> anyhow::Result<AuthTestReport> {
+    let mut report = AuthTestReport::default();
+    let mut check = AuthCheck {
+        client,
+        base_url,
+        results: Vec::new(),
+    };
+
+    let guarded = guarded_handlers(backend_code, &setup.guard_type);
+    let protected = protected_routes(routes, &guarded);
+    if protected.is_empty() {
+        report.issues.push(format!(
+            "No route requires a login. Handlers reading or changing user data must take an `auth::{}` argument",
+            setup.guard_type
+        ));
+    }
+
+    for route in protected.iter() {
+        let testing_msg = format!(
+            "Testing {} '{}' rejects anonymous requests...",
+            route.method, route.route
+        );
+        PrintCommand::UnitTest.print_agent_message(agent_position, &testing_msg)?;
+
+        let path = route
+            .route
+            .split('/')
+            .map(|segment| match segment.starts_with('{') {
+                true => "1",
+                false => segment,
+            })
+            .collect::<Vec<&str>>()
+            .join("/");
+        let body = (!route.request_body.is_none()).then(|| sample_value(&route.request_body));
+
+        check
+            .expect(route.method, &route.route, &path, body, None, &[401, 403])
+            .await;
+    }
+
+    PrintCommand::UnitTest
+        .print_agent_message(agent_position, "Testing signup, login and logout...")?;
+
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+    let username = format!("test_user_{}", nanos);
+    let password = "correct-horse-battery-staple";
+    let credentials = json!({ "username": username, "password": password });
+
+    check
+        .expect(
+            HttpMethod::Post,
+            &setup.signup_route,
+            &setup.signup_route,
+            Some(credentials),
+            None,
+            &[200, 201],
+        )
+        .await;
+
+    check
+        .expect(
+            HttpMethod::Get,
+            &setup.current_user_route,
+            &setup.current_user_route,
+            None,
+            None,
+            &[401],
+        )
+        .await;
+
+    // One session is logged out again, the other one is handed to the endpoint tests
+    let session = log_in(&mut check, setup, &username, password).await;
+    let discarded_session = log_in(&mut check, setup, &username, password).await;
+
+    match (session.as_deref(), discarded_session.as_deref()) {
+        (Some(session), Some(discarded_session)) => {
+            for cookie in [session, discarded_session] {
+                check
+                    .expect(
+                        HttpMethod::Get,
+                        &setup.current_user_route,
+                        &setup.current_user_route,
+                        None,
+                        Some(cookie),
+                        &[200],
+                    )
+                    .await;
+            }
+
+            check
+                .expect(
+                    HttpMethod::Post,
+                    &setup.logout_route,
+                    &setup.logout_route,
+                    None,
+                    Some(discarded_session),
+                    &[200, 204],
+                )
+                .await;
+
+            check
+                .expect(
+                    HttpMethod::Get,
+                    &setup.current_user_route,
+                    &setup.current_user_route,
+                    None,
+                    Some(discarded_session),
+                    &[401],
+                )
+                .await;
+        }
+        _ => report.issues.push(format!(
+            "Login did not set the {} cookie",
+            setup.session_cookie
+        )),
+    }
+
+    report.results = check.results;
+    report.session_cookie = session;
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finds_protected_routes() {
+        let code = r#"
+            mod auth;
+            async fn list_notes(user: auth::AuthenticatedUser, state: web::Data<AppState>) -> impl Responder {}
+            async fn health() -> impl Responder {}
+            #[get("/notes/<id>")]
+            fn read_note(id: u64, _user: AuthenticatedUser) -> Json<Note> {}
+        "#;
+
+        let guarded = guarded_handlers(code, "AuthenticatedUser");
+        assert_eq!(
+            guarded,
+            BTreeSet::from(["list_notes".to_string(), "read_note".to_string()])
+        );
+
+        let routes = vec![
+            RouteObject::test(HttpMethod::Get, "/notes").with_handler("list_notes"),
+            RouteObject::test(HttpMethod::Get, "/notes/{id}").with_handler("read_note"),
+            RouteObject::test(HttpMethod::Get, "/health").with_handler("health"),
+            RouteObject::test(HttpMethod::Get, "/auth/me").with_handler("list_notes"),
+        ];
+        let protected = protected_routes(&routes, &guarded)
+            .iter()
+            .map(|route| route.route.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(protected, vec!["/notes", "/notes/{id}"]);
+    }
+
+    #[test]
+    fn test_auth_routes_and_report() {
+        let setup = AuthSetup::default();
+        let routes = setup.routes();
+        assert_eq!(routes.len(), 4);
+        assert!(routes
+            .iter()
+            .all(|route| AuthSetup::is_auth_route(&route.route)));
+        assert!(!AuthSetup::is_auth_route("/authors"));
+
+        let mut report = AuthTestReport::default();
+        assert!(report.failure_report().is_none());
+
+        report.results.push(EndpointTestResult {
+            method: HttpMethod::Get,
+            route: "/notes".to_string(),
+            url: "http://127.0.0.1:8080/notes".to_string(),
+            status: Some(200),
+            passed: false,
+            message: "Expected status [401, 403], got 200".to_string(),
+            contract_violations: Vec::new(),
+        });
+        assert!(report
+            .failure_report()
+            .unwrap_or_default()
+            .contains("GET http://127.0.0.1:8080/notes: Expected status [401, 403], got 200"));
+    }
+}
@@ -0,0 +1,260 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::process::{Command, Stdio};
+use strum_macros::Display;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Display)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum TestOutcome {
+    Passed,
+    Failed,
+    Ignored,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TestCaseResult {
+    pub name: String,
+    pub outcome: TestOutcome,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub output: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompilerError {
+    pub file: Option<String>,
+    pub rendered: String,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CargoTestReport {
+    pub compile_errors: Vec<CompilerError>,
+    pub tests: Vec<TestCaseResult>,
+}
+
+impl CargoTestReport {
+    pub fn failed(&self) -> Vec<&TestCaseResult> {
+        self.tests
+            .iter()
+            .filter(|test| test.outcome == TestOutcome::Failed)
+            .collect()
+    }
+
+    pub fn passed_count(&self) -> usize {
+        self.tests
+            .iter()
+            .filter(|test| test.outcome == TestOutcome::Passed)
+            .count()
+    }
+
+    // Compile errors only located in the given file, i.e. a broken generated test module
+    pub fn errors_only_in(&self, file_name: &str) -> bool {
+        !self.compile_errors.is_empty()
+            && self.compile_errors.iter().all(|error| {
+                error
+                    .file
+                    .as_deref()
+                    .is_some_and(|file| file.ends_with(file_name))
+            })
+    }
+
+    // Bug description of every failing test with what it printed, None when all passed
+    pub fn failure_report(&self) -> Option<String> {
+        if !self.compile_errors.is_empty() {
+            let errors = self
+                .compile_errors
+                .iter()
+                .map(|error| error.rendered.trim_end())
+                .collect::<Vec<&str>>();
+
+            return Some(format!("TEST COMPILATION FAILED:\n{}", errors.join("\n")));
+        }
+
+        let failed = self.failed();
+        if failed.is_empty() {
+            return None;
+        }
+
+        let details = failed
+            .iter()
+            .map(|test| format!("---- {} ----\n{}", test.name, test.output.trim_end()))
+            .collect::<Vec<String>>();
+
+        Some(format!(
+            "FAILED TESTS: {} of {} generated tests failed\n{}",
+            failed.len(),
+            self.tests.len(),
+            details.join("\n")
+        ))
+    }
+}
+
+fn parse_test_line(line: &str) -> Option<TestCaseResult> {
+    let rest = line.strip_prefix("test ")?;
+    let (name, status) = rest.rsplit_once(" ... ")?;
+
+    let outcome = match status.trim() {
+        "ok" => TestOutcome::Passed,
+        "FAILED" => TestOutcome::Failed,
+        status if status.starts_with("ignored") => TestOutcome::Ignored,
+        _ => return None,
+    };
+
+    Some(TestCaseResult {
+        name: name.trim().to_string(),
+        outcome,
+        output: String::new(),
+    })
+}
+
+fn parse_compiler_message(message: &Value) -> Option<CompilerError> {
+    if message["reason"] != "compiler-message" || message["message"]["level"] != "error" {
+        return None;
+    }
+
+    let file = message["message"]["spans"]
+        .as_array()
+        .and_then(|spans| {
+            spans
+                .iter()
+                .find(|span| span["is_primary"] == true)
+                .or(spans.first())
+        })
+        .and_then(|span| span["file_name"].as_str())
+        .map(|file| file.to_string());
+
+    Some(CompilerError {
+        file,
+        rendered: message["message"]["rendered"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+    })
+}
+
+// Split the stdout of `cargo test --message-format=json` into compiler errors and test results
+pub fn parse_cargo_test_output(stdout: &str) -> CargoTestReport {
+    let mut report = CargoTestReport::default();
+    let mut captured_test: Option<String> = None;
+
+    for line in stdout.lines() {
+        // Cargo prints its own messages as JSON, libtest output comes through as plain text
+        if let Ok(message) = serde_json::from_str::<Value>(line) {
+            if let Some(error) = parse_compiler_message(&message) {
+                report.compile_errors.push(error);
+            }
+            continue;
+        }
+
+        if let Some(test) = parse_test_line(line) {
+            report.tests.push(test);
+            continue;
+        }
+
+        // Output of failed tests is printed as `---- name stdout ----` sections
+        if let Some(name) = line
+            .strip_prefix("---- ")
+            .and_then(|rest| rest.strip_suffix(" stdout ----"))
+        {
+            captured_test = Some(name.to_string());
+            continue;
+        }
+
+        if line.starts_with("failures:") || line.starts_with("test result:") {
+            captured_test = None;
+            continue;
+        }
+
+        if let Some(name) = captured_test.as_ref() {
+            if let Some(test) = report.tests.iter_mut().find(|test| &test.name == name) {
+                test.output.push_str(line);
+                test.output.push('\n');
+            }
+        }
+    }
+
+    report
+}
+
+// Run the tests of the project in `project_path` and collect per test results
+pub fn run_cargo_tests(project_path: &str) -> anyhow::Result<CargoTestReport> {
+    let output = Command::new("cargo")
+        .args(["test", "--message-format=json"])
+        .current_dir(project_path)
+        // Backtraces would bury the assertion messages the fix loop needs
+        .env("RUST_BACKTRACE", "0")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()?;
+
+    Ok(parse_cargo_test_output(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CARGO_TEST_STDOUT: &str = r#"{"reason":"compiler-artifact","package_id":"web_server 0.1.0","target":{"name":"web_server"}}
+{"reason":"build-finished","success":true}
+
+running 3 tests
+test generated_tests::test_create_task ... ok
+test generated_tests::test_delete_missing_task ... FAILED
+test generated_tests::slow_check ... ignored, needs a database
+
+failures:
+
+---- generated_tests::test_delete_missing_task stdout ----
+
+thread 'generated_tests::test_delete_missing_task' panicked at main_tests.rs:40:5:
+assertion `left == right` failed
+  left: 200
+ right: 404
+
+
+failures:
+    generated_tests::test_delete_missing_task
+
+test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out
+"#;
+
+    #[test]
+    fn test_parses_test_results() {
+        let report = parse_cargo_test_output(CARGO_TEST_STDOUT);
+
+        assert!(report.compile_errors.is_empty());
+        assert_eq!(report.tests.len(), 3);
+        assert_eq!(report.passed_count(), 1);
+        assert_eq!(report.tests[2].outcome, TestOutcome::Ignored);
+
+        let failed = report.failed();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].name, "generated_tests::test_delete_missing_task");
+        assert!(failed[0].output.contains("right: 404"));
+        assert!(!failed[0].output.contains("failures:"));
+
+        let failure_report = report.failure_report().unwrap_or_default();
+        assert!(failure_report.contains("1 of 3 generated tests failed"));
+        assert!(failure_report.contains("left: 200"));
+    }
+
+    #[test]
+    fn test_parses_compile_errors() {
+        let stdout = r#"{"reason":"compiler-message","message":{"level":"warning","rendered":"warning: unused import","spans":[]}}
+{"reason":"compiler-message","message":{"level":"error","rendered":"error[E0425]: cannot find function `create_item`\n","spans":[{"file_name":"main_tests.rs","is_primary":true}]}}
+{"reason":"build-finished","success":false}"#;
+
+        let report = parse_cargo_test_output(stdout);
+
+        assert_eq!(report.compile_errors.len(), 1);
+        assert!(report.errors_only_in("main_tests.rs"));
+        assert!(!report.errors_only_in("main.rs"));
+        assert!(report
+            .failure_report()
+            .unwrap_or_default()
+            .starts_with("TEST COMPILATION FAILED:\nerror[E0425]"));
+        assert_eq!(CargoTestReport::default().failure_report(), None);
+    }
+}
@@ -0,0 +1,822 @@
+use super::{
+    endpoint_testing::plan_crud_sequence,
+    openapi::{openapi_path, operation_ids},
+};
+use crate::models::{
+    agents::agent_traits::RouteObject,
+    general::{
+        api_schema::{FieldSchema, HttpMethod},
+        auth_schema::AuthSetup,
+    },
+};
+use std::collections::BTreeSet;
+
+pub const RUST_CLIENT_CRATE: &str = "web_server_client";
+
+// Environment variable the smoke programs read a logged in session cookie from
+pub const SESSION_COOKIE_ENV: &str = "API_SESSION_COOKIE";
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where", "while",
+    "abstract", "become", "box", "do", "final", "macro", "override", "priv", "typeof", "unsized",
+    "virtual", "yield", "try", "gen",
+];
+
+// Keywords that cannot be written as raw identifiers
+const RESERVED_IDENTS: &[&str] = &["self", "Self", "super", "crate", "_"];
+
+// One route as the generated clients see it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientOperation {
+    // snake_case, taken from the OpenAPI operationId
+    pub name: String,
+    pub method: HttpMethod,
+    pub route: String,
+    // Path template with `{name}` parameters
+    pub path: String,
+    pub path_params: Vec<String>,
+    pub request_body: FieldSchema,
+    pub response: FieldSchema,
+}
+
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+
+    for c in name.chars() {
+        if !c.is_ascii_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            previous_lower = false;
+            continue;
+        }
+
+        // camelCase boundary
+        if c.is_ascii_uppercase() && previous_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        previous_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        current.push(c.to_ascii_lowercase());
+    }
+
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+pub fn snake_case(name: &str) -> String {
+    let snake = words(name).join("_");
+    match snake.chars().next() {
+        None => "value".to_string(),
+        Some(c) if c.is_ascii_digit() => format!("_{}", snake),
+        Some(_) => snake,
+    }
+}
+
+pub fn pascal_case(name: &str) -> String {
+    let pascal = words(name)
+        .iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect::<String>();
+
+    match pascal.chars().next() {
+        None => "Value".to_string(),
+        Some(c) if c.is_ascii_digit() => format!("T{}", pascal),
+        Some(_) => pascal,
+    }
+}
+
+pub fn camel_case(name: &str) -> String {
+    let pascal = pascal_case(name);
+    let mut chars = pascal.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => "value".to_string(),
+    }
+}
+
+// Field or argument name that compiles, i.e. type -> r#type
+fn rust_ident(name: &str) -> String {
+    let ident = snake_case(name);
+    if RESERVED_IDENTS.contains(&ident.as_str()) {
+        format!("{}_", ident)
+    } else if RUST_KEYWORDS.contains(&ident.as_str()) {
+        format!("r#{}", ident)
+    } else {
+        ident
+    }
+}
+
+fn is_js_ident(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+pub fn client_operations(routes: &[RouteObject]) -> Vec<ClientOperation> {
+    routes
+        .iter()
+        .zip(operation_ids(routes))
+        .map(|(route, operation_id)| {
+            let (path, parameters) = openapi_path(&route.route);
+            ClientOperation {
+                name: snake_case(&operation_id),
+                method: route.method,
+                route: route.route.clone(),
+                path,
+                path_params: parameters.into_iter().map(|(name, _)| name).collect(),
+                request_body: route.request_body.clone(),
+                response: route.response.clone(),
+            }
+        })
+        .collect()
+}
+
+// Calls the smoke programs make, in create -> read -> update -> delete order so path parameters
+// can be filled from earlier responses. Auth routes are left out so the session stays valid.
+pub fn smoke_calls(
+    routes: &[RouteObject],
+    operations: &[ClientOperation],
+) -> Vec<(ClientOperation, RouteObject)> {
+    plan_crud_sequence(routes)
+        .into_iter()
+        .filter(|route| !AuthSetup::is_auth_route(&route.route))
+        .filter_map(|route| {
+            let operation = operations.iter().find(|operation| {
+                operation.method == route.method && operation.route == route.route
+            })?;
+            Some((operation.clone(), route))
+        })
+        .collect()
+}
+
+// Named types collected while walking the schemas of every operation
+#[derive(Default)]
+struct TypeDefinitions {
+    names: BTreeSet<String>,
+    definitions: Vec<String>,
+}
+
+impl TypeDefinitions {
+    fn unique_name(&mut self, name: &str) -> String {
+        let mut unique = name.to_string();
+        let mut suffix = 2;
+        while !self.names.insert(unique.clone()) {
+            unique = format!("{}{}", name, suffix);
+            suffix += 1;
+        }
+        unique
+    }
+
+    fn rust_type(&mut self, name: &str, schema: &FieldSchema) -> String {
+        match schema {
+            FieldSchema::None => "()".to_string(),
+            FieldSchema::NotProvided => "serde_json::Value".to_string(),
+            // Not f64, which would turn an id of 7 into 7.0 when it is sent back
+            FieldSchema::Number => "serde_json::Number".to_string(),
+            FieldSchema::String => "String".to_string(),
+            FieldSchema::Bool => "bool".to_string(),
+            FieldSchema::Array(item) => {
+                format!(
+                    "Vec<{}>",
+                    self.rust_field_type(&format!("{}Item", name), item)
+                )
+            }
+            FieldSchema::Object(fields) if fields.is_empty() => {
+                "serde_json::Map<String, serde_json::Value>".to_string()
+            }
+            FieldSchema::Object(fields) => {
+                let name = self.unique_name(name);
+                let mut definition = format!(
+                    "#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]\npub struct {} {{\n",
+                    name
+                );
+                for (field, schema) in fields.iter() {
+                    let ty =
+                        self.rust_field_type(&format!("{}{}", name, pascal_case(field)), schema);
+                    let ident = rust_ident(field);
+                    if ident.trim_start_matches("r#") != field {
+                        definition.push_str(&format!("    #[serde(rename = {:?})]\n", field));
+                    }
+                    definition.push_str(&format!("    pub {}: {},\n", ident, ty));
+                }
+                definition.push('}');
+                self.definitions.push(definition);
+                name
+            }
+        }
+    }
+
+    // Nested fields have no "no body" case, an unknown shape is kept as JSON
+    fn rust_field_type(&mut self, name: &str, schema: &FieldSchema) -> String {
+        match schema {
+            FieldSchema::None => "serde_json::Value".to_string(),
+            _ => self.rust_type(name, schema),
+        }
+    }
+
+    fn ts_type(&mut self, name: &str, schema: &FieldSchema) -> String {
+        match schema {
+            FieldSchema::None => "void".to_string(),
+            FieldSchema::NotProvided => "unknown".to_string(),
+            FieldSchema::Number => "number".to_string(),
+            FieldSchema::String => "string".to_string(),
+            FieldSchema::Bool => "boolean".to_string(),
+            FieldSchema::Array(item) => {
+                format!("{}[]", self.ts_field_type(&format!("{}Item", name), item))
+            }
+            FieldSchema::Object(fields) if fields.is_empty() => {
+                "Record<string, unknown>".to_string()
+            }
+            FieldSchema::Object(fields) => {
+                let name = self.unique_name(name);
+                let mut definition = format!("export interface {} {{\n", name);
+                for (field, schema) in fields.iter() {
+                    let ty = self.ts_field_type(&format!("{}{}", name, pascal_case(field)), schema);
+                    let key = match is_js_ident(field) {
+                        true => field.clone(),
+                        false => format!("{:?}", field),
+                    };
+                    definition.push_str(&format!("  {}: {};\n", key, ty));
+                }
+                definition.push('}');
+                self.definitions.push(definition);
+                name
+            }
+        }
+    }
+
+    fn ts_field_type(&mut self, name: &str, schema: &FieldSchema) -> String {
+        match schema {
+            FieldSchema::None => "unknown".to_string(),
+            _ => self.ts_type(name, schema),
+        }
+    }
+}
+
+fn rust_path_args(operation: &ClientOperation) -> Vec<String> {
+    operation
+        .path_params
+        .iter()
+        .map(|param| match rust_ident(param).as_str() {
+            "body" => "body_".to_string(),
+            ident => ident.to_string(),
+        })
+        .collect()
+}
+
+fn rust_method(operation: &ClientOperation, types: &mut TypeDefinitions) -> String {
+    let type_name = pascal_case(&operation.name);
+    let path_args = rust_path_args(operation);
+
+    let mut args = vec!["&self".to_string()];
+    args.extend(path_args.iter().map(|arg| format!("{}: &str", arg)));
+    if !operation.request_body.is_none() {
+        let body_type = types.rust_type(&format!("{}Request", type_name), &operation.request_body);
+        args.push(format!("body: &{}", body_type));
+    }
+    let response_type = types.rust_type(&format!("{}Response", type_name), &operation.response);
+
+    let (path_format, _) = openapi_path(&operation.path);
+    let mut path_format = path_format;
+    for param in operation.path_params.iter() {
+        path_format = path_format.replace(&format!("{{{}}}", param), "{}");
+    }
+    let format_args = std::iter::once("self.base_url".to_string())
+        .chain(path_args)
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let mut body = format!(
+        "        let url = format!(\"{{}}{}\", {});\n        let request = self.http.request(reqwest::Method::{}, url)",
+        path_format,
+        format_args,
+        operation.method.to_string().to_uppercase()
+    );
+    if !operation.request_body.is_none() {
+        body.push_str(".json(body)");
+    }
+    body.push_str(";\n");
+    body.push_str(match operation.response.is_none() {
+        true => "        self.send_empty(request).await\n",
+        false => "        self.send(request).await\n",
+    });
+
+    format!(
+        "    // {} {}\n    pub async fn {}({}) -> Result<{}> {{\n{}    }}",
+        operation.method,
+        operation.path,
+        rust_ident(&operation.name),
+        args.join(", "),
+        response_type,
+        body
+    )
+}
+
+const RUST_CLIENT_PRELUDE: &str = r#"// Generated from the backend's API schema, do not edit by hand
+use serde::de::DeserializeOwned;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+    Http(reqwest::Error),
+    Status { status: u16, body: String },
+    Decode(serde_json::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Http(e) => write!(f, "request failed: {}", e),
+            Error::Status { status, body } => write!(f, "HTTP {}: {}", status, body),
+            Error::Decode(e) => write!(f, "response does not match the schema: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        Error::Http(e)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+"#;
+
+const RUST_CLIENT_CORE: &str = r#"#[derive(Debug, Clone)]
+pub struct ApiClient {
+    base_url: String,
+    http: reqwest::Client,
+}
+
+impl ApiClient {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self::with_client(reqwest::Client::new(), base_url)
+    }
+
+    // Use a preconfigured client, i.e. one sending a session cookie
+    pub fn with_client(http: reqwest::Client, base_url: impl Into<String>) -> Self {
+        let base_url = base_url.into().trim_end_matches('/').to_string();
+        Self { base_url, http }
+    }
+
+    async fn text(&self, request: reqwest::RequestBuilder) -> Result<String> {
+        let response = request.send().await?;
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            return Err(Error::Status {
+                status: status.as_u16(),
+                body,
+            });
+        }
+        Ok(body)
+    }
+
+    #[allow(dead_code)]
+    async fn send<T: DeserializeOwned>(&self, request: reqwest::RequestBuilder) -> Result<T> {
+        let body = self.text(request).await?;
+        serde_json::from_str(&body).map_err(Error::Decode)
+    }
+
+    #[allow(dead_code)]
+    async fn send_empty(&self, request: reqwest::RequestBuilder) -> Result<()> {
+        self.text(request).await.map(|_| ())
+    }
+"#;
+
+// src/lib.rs of the Rust client crate
+pub fn rust_client_code(operations: &[ClientOperation]) -> String {
+    let mut types = TypeDefinitions::default();
+    let methods = operations
+        .iter()
+        .map(|operation| rust_method(operation, &mut types))
+        .collect::<Vec<String>>();
+
+    let mut code = RUST_CLIENT_PRELUDE.to_string();
+    for definition in types.definitions.iter() {
+        code.push('\n');
+        code.push_str(definition);
+        code.push('\n');
+    }
+    code.push('\n');
+    code.push_str(RUST_CLIENT_CORE);
+    for method in methods.iter() {
+        code.push('\n');
+        code.push_str(method);
+        code.push('\n');
+    }
+    code.push_str("}\n");
+    code
+}
+
+pub fn rust_client_manifest() -> String {
+    format!(
+        r#"[package]
+name = "{}"
+version = "0.1.0"
+edition = "2021"
+
+# Keep the generated client out of any parent workspace
+[workspace]
+
+[dependencies]
+reqwest = {{ version = "0.12", features = ["json"] }}
+serde = {{ version = "1.0", features = ["derive"] }}
+serde_json = "1.0"
+tokio = {{ version = "1", features = ["macros", "rt-multi-thread"] }}
+"#,
+        RUST_CLIENT_CRATE
+    )
+}
+
+// src/bin/smoke.rs, making the one call named on the command line and printing
+// `OK <response json>` or `FAIL <error>`
+pub fn rust_smoke_code(calls: &[(ClientOperation, RouteObject)]) -> String {
+    let mut code = format!(
+        r#"// Generated smoke test: smoke <base_url> <operation> <json body or -> [path parameters...]
+#![allow(unused)]
+use {}::ApiClient;
+
+fn body<T: serde::de::DeserializeOwned>(json: &str) -> T {{
+    serde_json::from_str(json).expect("invalid request body")
+}}
+
+fn report<T: serde::Serialize, E: std::fmt::Display>(result: Result<T, E>) -> i32 {{
+    match result {{
+        Ok(response) => {{
+            let json = serde_json::to_string(&response).unwrap_or_else(|_| "null".to_string());
+            println!("OK {{}}", json);
+            0
+        }}
+        Err(e) => {{
+            println!("FAIL {{}}", e.to_string().replace('\n', " "));
+            1
+        }}
+    }}
+}}
+
+#[tokio::main]
+async fn main() {{
+    let args = std::env::args().collect::<Vec<String>>();
+    if args.len() < 4 {{
+        eprintln!("usage: smoke <base_url> <operation> <json body or -> [path parameters...]");
+        std::process::exit(2);
+    }}
+    let (operation, json, path) = (args[2].as_str(), args[3].as_str(), &args[4..]);
+
+    let mut headers = reqwest::header::HeaderMap::new();
+    if let Ok(cookie) = std::env::var("{}") {{
+        headers.insert(reqwest::header::COOKIE, cookie.parse().expect("invalid cookie"));
+    }}
+    let http = reqwest::Client::builder()
+        .default_headers(headers)
+        .build()
+        .expect("failed to build http client");
+    let client = ApiClient::with_client(http, args[1].clone());
+
+    let code = match operation {{
+"#,
+        RUST_CLIENT_CRATE, SESSION_COOKIE_ENV
+    );
+
+    for (operation, _) in calls.iter() {
+        let mut args = (0..operation.path_params.len())
+            .map(|index| format!("&path[{}]", index))
+            .collect::<Vec<String>>();
+        if !operation.request_body.is_none() {
+            args.push("&body(json)".to_string());
+        }
+        code.push_str(&format!(
+            "        \"{}\" => report(client.{}({}).await),\n",
+            operation.name,
+            rust_ident(&operation.name),
+            args.join(", ")
+        ));
+    }
+
+    code.push_str(
+        r#"        _ => {
+            eprintln!("unknown operation {}", operation);
+            2
+        }
+    };
+    std::process::exit(code);
+}
+"#,
+    );
+    code
+}
+
+// Optional TypeScript annotation, left out for the plain JavaScript build
+fn ts(typed: bool, annotation: &str) -> &str {
+    match typed {
+        true => annotation,
+        false => "",
+    }
+}
+
+fn ts_method(operation: &ClientOperation, types: &mut TypeDefinitions, typed: bool) -> String {
+    let type_name = pascal_case(&operation.name);
+    let path_args = operation
+        .path_params
+        .iter()
+        .map(|param| camel_case(param))
+        .collect::<Vec<String>>();
+
+    let mut args = path_args
+        .iter()
+        .map(|arg| format!("{}{}", arg, ts(typed, ": string")))
+        .collect::<Vec<String>>();
+    if !operation.request_body.is_none() {
+        let body_type = types.ts_type(&format!("{}Request", type_name), &operation.request_body);
+        args.push(format!("body{}", ts(typed, &format!(": {}", body_type))));
+    }
+    let response_type = types.ts_type(&format!("{}Response", type_name), &operation.response);
+
+    let mut path = operation.path.clone();
+    for (param, arg) in operation.path_params.iter().zip(path_args.iter()) {
+        path = path.replace(
+            &format!("{{{}}}", param),
+            &format!("${{encodeURIComponent({})}}", arg),
+        );
+    }
+
+    let mut call_args = vec![
+        format!("\"{}\"", operation.method.to_string().to_uppercase()),
+        format!("`{}`", path),
+    ];
+    if !operation.request_body.is_none() {
+        call_args.push("body".to_string());
+    }
+
+    format!(
+        "  // {} {}\n  async {}({}){} {{\n    return this.request{}({});\n  }}",
+        operation.method,
+        operation.path,
+        camel_case(&operation.name),
+        args.join(", "),
+        ts(typed, &format!(": Promise<{}>", response_type)),
+        ts(typed, &format!("<{}>", response_type)),
+        call_args.join(", ")
+    )
+}
+
+// client.ts, or the equivalent client.js when `typed` is false
+pub fn typescript_client_code(operations: &[ClientOperation], typed: bool) -> String {
+    let mut types = TypeDefinitions::default();
+    let methods = operations
+        .iter()
+        .map(|operation| ts_method(operation, &mut types, typed))
+        .collect::<Vec<String>>();
+
+    let mut code = "// Generated from the backend's API schema, do not edit by hand\n".to_string();
+    if typed {
+        for definition in types.definitions.iter() {
+            code.push('\n');
+            code.push_str(definition);
+            code.push('\n');
+        }
+    }
+
+    code.push_str(&format!(
+        r#"
+export class ApiError extends Error {{
+  status{status};
+  body{body};
+
+  constructor(status{status}, body{body}) {{
+    super(`HTTP ${{status}}: ${{body}}`);
+    this.status = status;
+    this.body = body;
+  }}
+}}
+
+export class ApiClient {{
+  baseUrl{base_url};
+  init{init};
+
+  constructor(baseUrl{base_url}, init{init} = {{}}) {{
+    this.baseUrl = baseUrl.replace(/\/+$/, "");
+    this.init = init;
+  }}
+
+  async request{generic}(method{method}, path{path}, body{body_arg}){returns} {{
+    const headers = new Headers(this.init.headers);
+    if (body !== undefined) {{
+      headers.set("Content-Type", "application/json");
+    }}
+    const response = await fetch(this.baseUrl + path, {{
+      ...this.init,
+      method,
+      headers,
+      body: body === undefined ? undefined : JSON.stringify(body),
+    }});
+    const text = await response.text();
+    if (!response.ok) {{
+      throw new ApiError(response.status, text);
+    }}
+    return (text ? JSON.parse(text) : undefined){cast};
+  }}
+"#,
+        status = ts(typed, ": number"),
+        body = ts(typed, ": string"),
+        base_url = ts(typed, ": string"),
+        init = ts(typed, ": RequestInit"),
+        generic = ts(typed, "<T>"),
+        method = ts(typed, ": string"),
+        path = ts(typed, ": string"),
+        body_arg = ts(typed, "?: unknown"),
+        returns = ts(typed, ": Promise<T>"),
+        cast = ts(typed, " as T"),
+    ));
+
+    for method in methods.iter() {
+        code.push('\n');
+        code.push_str(method);
+        code.push('\n');
+    }
+    code.push_str("}\n");
+    code
+}
+
+// smoke.ts, or smoke.js when `typed` is false, taking the same arguments as the Rust smoke binary
+pub fn typescript_smoke_code(calls: &[(ClientOperation, RouteObject)], typed: bool) -> String {
+    let mut code = String::from(
+        "// Generated smoke test: smoke <base_url> <operation> <json body or -> [path parameters...]\n",
+    );
+    code.push_str("import { ApiClient } from \"./client.js\";\n\n");
+    if typed {
+        // Avoids depending on @types/node for the two globals used
+        code.push_str(
+            "declare const process: {\n  argv: string[];\n  env: Record<string, string | undefined>;\n  exit(code: number): never;\n};\n\n",
+        );
+    }
+
+    code.push_str(&format!(
+        r#"const [baseUrl, operation, json, ...path] = process.argv.slice(2);
+const cookie = process.env.{cookie_env};
+const client = new ApiClient(baseUrl, cookie ? {{ headers: {{ Cookie: cookie }} }} : {{}});
+
+const calls{calls_type} = {{
+"#,
+        cookie_env = SESSION_COOKIE_ENV,
+        calls_type = ts(typed, ": Record<string, () => Promise<unknown>>"),
+    ));
+
+    for (operation, _) in calls.iter() {
+        let mut args = (0..operation.path_params.len())
+            .map(|index| format!("path[{}]", index))
+            .collect::<Vec<String>>();
+        if !operation.request_body.is_none() {
+            args.push("JSON.parse(json)".to_string());
+        }
+        code.push_str(&format!(
+            "  \"{}\": () => client.{}({}),\n",
+            operation.name,
+            camel_case(&operation.name),
+            args.join(", ")
+        ));
+    }
+
+    code.push_str(
+        r#"};
+
+const call = calls[operation];
+if (!call) {
+  console.error(`unknown operation ${operation}`);
+  process.exit(2);
+}
+try {
+  const response = await call();
+  console.log(`OK ${JSON.stringify(response ?? null)}`);
+  process.exit(0);
+} catch (error) {
+  console.log(`FAIL ${String(error).replace(/\n/g, " ")}`);
+  process.exit(1);
+}
+"#,
+    );
+    code
+}
+
+pub const TYPESCRIPT_CONFIG: &str = r#"{
+  "compilerOptions": {
+    "target": "ES2022",
+    "module": "NodeNext",
+    "moduleResolution": "NodeNext",
+    "lib": ["ES2022", "DOM"],
+    "strict": true,
+    "declaration": true,
+    "outDir": "dist",
+    "skipLibCheck": true
+  },
+  "files": ["client.ts", "smoke.ts"]
+}
+"#;
+
+pub const TYPESCRIPT_PACKAGE: &str = r#"{
+  "name": "web-server-client",
+  "version": "0.1.0",
+  "type": "module",
+  "main": "dist/client.js",
+  "types": "dist/client.d.ts",
+  "scripts": {
+    "build": "tsc -p ."
+  }
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn routes() -> Vec<RouteObject> {
+        vec![
+            RouteObject::test(HttpMethod::Get, "/task/{id}")
+                .with_handler("read_task")
+                .with_response(json!({ "id": "number", "type": "string", "dueDate": "string" })),
+            RouteObject::test(HttpMethod::Post, "/task")
+                .with_handler("createTask")
+                .with_body(json!({ "name": "string", "tags": [{ "label": "string" }] }))
+                .with_response(json!({ "id": "number" })),
+            RouteObject::test(HttpMethod::Delete, "/task/{id}")
+                .with_handler("delete_task")
+                .with_response(Value::Null),
+            RouteObject::test(HttpMethod::Post, "/auth/logout")
+                .with_handler("logout")
+                .with_response(Value::Null),
+        ]
+    }
+
+    #[test]
+    fn test_names_identifiers() {
+        assert_eq!(snake_case("createTask"), "create_task");
+        assert_eq!(snake_case("get_/api/v2-items"), "get_api_v2_items");
+        assert_eq!(pascal_case("read_task"), "ReadTask");
+        assert_eq!(camel_case("read_task"), "readTask");
+        assert_eq!(rust_ident("type"), "r#type");
+        assert_eq!(rust_ident("self"), "self_");
+        assert!(!is_js_ident("due-date"));
+    }
+
+    #[test]
+    fn test_generates_typed_clients() {
+        let routes = routes();
+        let operations = client_operations(&routes);
+        let rust = rust_client_code(&operations);
+
+        assert!(rust.contains("pub struct CreateTaskRequestTagsItem {\n    pub label: String,\n}"));
+        assert!(rust.contains("    pub r#type: String,\n"));
+        assert!(rust.contains("    #[serde(rename = \"dueDate\")]\n    pub due_date: String,\n"));
+        assert!(rust.contains(
+            "pub async fn read_task(&self, id: &str) -> Result<ReadTaskResponse> {\n        let url = format!(\"{}/task/{}\", self.base_url, id);"
+        ));
+        assert!(rust.contains(
+            "pub async fn create_task(&self, body: &CreateTaskRequest) -> Result<CreateTaskResponse>"
+        ));
+        assert!(rust.contains("pub async fn delete_task(&self, id: &str) -> Result<()>"));
+
+        let typescript = typescript_client_code(&operations, true);
+        assert!(typescript.contains("export interface ReadTaskResponse {\n  dueDate: string;\n  id: number;\n  type: string;\n}"));
+        assert!(typescript.contains(
+            "async readTask(id: string): Promise<ReadTaskResponse> {\n    return this.request<ReadTaskResponse>(\"GET\", `/task/${encodeURIComponent(id)}`);"
+        ));
+
+        let javascript = typescript_client_code(&operations, false);
+        assert!(!javascript.contains("interface"));
+        assert!(javascript.contains(
+            "async createTask(body) {\n    return this.request(\"POST\", `/task`, body);"
+        ));
+
+        // Every CRUD route is called, the ones with path parameters after the create
+        let calls = smoke_calls(&routes, &operations);
+        let names = calls
+            .iter()
+            .map(|(operation, _)| operation.name.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(names, vec!["create_task", "read_task", "delete_task"]);
+        assert!(rust_smoke_code(&calls)
+            .contains("\"read_task\" => report(client.read_task(&path[0]).await),"));
+        assert!(rust_smoke_code(&calls)
+            .contains("\"create_task\" => report(client.create_task(&body(json)).await),"));
+        assert!(typescript_smoke_code(&calls, true)
+            .contains("  \"delete_task\": () => client.deleteTask(path[0]),\n"));
+    }
+}
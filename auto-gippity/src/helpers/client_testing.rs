@@ -0,0 +1,335 @@
+use super::{
+    client_sdk::{ClientOperation, SESSION_COOKIE_ENV},
+    endpoint_testing::CrudContext,
+};
+use crate::models::agents::agent_traits::RouteObject;
+use serde_json::Value;
+use std::{path::Path, process::Stdio, time::Duration};
+use tokio::process::Command;
+
+// Building the Rust client compiles reqwest, so it gets more time than a smoke run
+const CLIENT_BUILD_TIMEOUT: Duration = Duration::from_secs(600);
+const CLIENT_SMOKE_TIMEOUT: Duration = Duration::from_secs(60);
+
+// Outcome of building one generated client and running its smoke program
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ClientCheck {
+    pub client: String,
+    // The toolchain is missing, nothing was run
+    pub skipped: Option<String>,
+    pub build_error: Option<String>,
+    pub passed: Vec<String>,
+    pub failed: Vec<String>,
+}
+
+impl ClientCheck {
+    fn new(client: &str) -> Self {
+        Self {
+            client: client.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        if let Some(reason) = self.skipped.as_ref() {
+            return format!("{} client skipped: {}", self.client, reason);
+        }
+        if self.build_error.is_some() {
+            return format!("{} client failed to build", self.client);
+        }
+        format!(
+            "{} client: {} of {} calls passed",
+            self.client,
+            self.passed.len(),
+            self.passed.len() + self.failed.len()
+        )
+    }
+}
+
+// What a smoke program printed for its call, None when it printed neither OK nor FAIL
+#[derive(Debug, Clone, PartialEq)]
+pub enum SmokeOutcome {
+    Passed(Option<Value>),
+    Failed(String),
+}
+
+pub fn parse_smoke_output(stdout: &str) -> Option<SmokeOutcome> {
+    stdout.lines().find_map(|line| {
+        if let Some(response) = line.strip_prefix("OK ") {
+            Some(SmokeOutcome::Passed(
+                serde_json::from_str::<Value>(response.trim())
+                    .ok()
+                    .filter(|value| !value.is_null()),
+            ))
+        } else {
+            line.strip_prefix("FAIL ")
+                .map(|error| SmokeOutcome::Failed(error.trim().to_string()))
+        }
+    })
+}
+
+// Calls the server rejected or answered outside its schema, None when every client passed
+pub fn client_failure_report(checks: &[ClientCheck]) -> Option<String> {
+    let lines = checks
+        .iter()
+        .flat_map(|check| {
+            check
+                .failed
+                .iter()
+                .map(move |call| format!("{} client {}", check.client, call))
+        })
+        .collect::<Vec<String>>();
+
+    match lines.is_empty() {
+        true => None,
+        false => Some(format!("CLIENT SDK CHECK FAILED:\n{}", lines.join("\n"))),
+    }
+}
+
+fn is_installed(program: &str) -> bool {
+    std::process::Command::new(program)
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+async fn run(mut command: Command, timeout: Duration) -> anyhow::Result<std::process::Output> {
+    command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    match tokio::time::timeout(timeout, command.output()).await {
+        Ok(output) => Ok(output?),
+        Err(_) => anyhow::bail!("timed out after {}s", timeout.as_secs()),
+    }
+}
+
+// Run the smoke program once per call, filling path parameters from earlier responses the way
+// the endpoint tests do
+async fn run_smoke(
+    check: &mut ClientCheck,
+    smoke: impl Fn() -> Command,
+    calls: &[(ClientOperation, RouteObject)],
+    session_cookie: Option<&str>,
+) {
+    let mut context = CrudContext::default();
+
+    for (operation, route) in calls.iter() {
+        let name = format!("{} {}", operation.method, operation.path);
+        let body = context.request_body(route);
+
+        let mut command = smoke();
+        command
+            .arg(&operation.name)
+            .arg(body.as_ref().map_or("-".to_string(), Value::to_string))
+            .args(context.path_values(route));
+        if let Some(cookie) = session_cookie {
+            command.env(SESSION_COOKIE_ENV, cookie);
+        }
+
+        let output = match run(command, CLIENT_SMOKE_TIMEOUT).await {
+            Ok(output) => output,
+            Err(e) => {
+                check.build_error = Some(format!("smoke run failed: {}", e));
+                return;
+            }
+        };
+
+        match parse_smoke_output(&String::from_utf8_lossy(&output.stdout)) {
+            Some(SmokeOutcome::Passed(response)) => {
+                context.remember_success(route, response.as_ref(), body.as_ref());
+                check.passed.push(name);
+            }
+            Some(SmokeOutcome::Failed(error)) => check.failed.push(format!("{}: {}", name, error)),
+            // A crash before the call is reported is a failure of the client itself
+            None => {
+                check.build_error = Some(String::from_utf8_lossy(&output.stderr).to_string());
+                return;
+            }
+        }
+    }
+}
+
+// Build the Rust client crate and run its smoke binary against `base_url`
+pub async fn check_rust_client(
+    crate_dir: &Path,
+    target_dir: &Path,
+    base_url: &str,
+    calls: &[(ClientOperation, RouteObject)],
+    session_cookie: Option<&str>,
+) -> anyhow::Result<ClientCheck> {
+    let mut check = ClientCheck::new("rust");
+
+    let mut build = Command::new("cargo");
+    build
+        .args(["build", "--quiet", "--bin", "smoke"])
+        .current_dir(crate_dir)
+        .env("CARGO_TARGET_DIR", target_dir);
+
+    let output = match run(build, CLIENT_BUILD_TIMEOUT).await {
+        Ok(output) => output,
+        Err(e) => {
+            check.build_error = Some(format!("cargo build failed: {}", e));
+            return Ok(check);
+        }
+    };
+    if !output.status.success() {
+        check.build_error = Some(String::from_utf8_lossy(&output.stderr).to_string());
+        return Ok(check);
+    }
+
+    let smoke = || {
+        let mut command = Command::new(target_dir.join("debug").join("smoke"));
+        command.arg(base_url);
+        command
+    };
+    run_smoke(&mut check, smoke, calls, session_cookie).await;
+
+    Ok(check)
+}
+
+// Type check the TypeScript client with tsc, then run the JavaScript build with node. Without tsc
+// the client is never type checked, so the check is skipped rather than run on our own dist/.
+pub async fn check_typescript_client(
+    client_dir: &Path,
+    base_url: &str,
+    calls: &[(ClientOperation, RouteObject)],
+    session_cookie: Option<&str>,
+) -> anyhow::Result<ClientCheck> {
+    let mut check = ClientCheck::new("typescript");
+
+    for program in ["node", "tsc"] {
+        if !is_installed(program) {
+            check.skipped = Some(format!("{} is not installed", program));
+            return Ok(check);
+        }
+    }
+
+    let mut build = Command::new("tsc");
+    build.args(["-p", "."]).current_dir(client_dir);
+
+    match run(build, CLIENT_BUILD_TIMEOUT).await {
+        Ok(output) if output.status.success() => {}
+        Ok(output) => {
+            check.build_error = Some(String::from_utf8_lossy(&output.stdout).to_string());
+            return Ok(check);
+        }
+        Err(e) => {
+            check.build_error = Some(format!("tsc failed: {}", e));
+            return Ok(check);
+        }
+    }
+
+    let smoke = || {
+        let mut command = Command::new("node");
+        command
+            .arg("dist/smoke.js")
+            .arg(base_url)
+            .current_dir(client_dir);
+        command
+    };
+    run_smoke(&mut check, smoke, calls, session_cookie).await;
+
+    Ok(check)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        helpers::client_sdk::{client_operations, smoke_calls},
+        models::general::api_schema::HttpMethod,
+    };
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_fills_path_parameters_from_created_resource() {
+        let routes = [
+            RouteObject::test(HttpMethod::Delete, "/task/{id}"),
+            RouteObject::test(HttpMethod::Post, "/task").with_body(json!({ "title": "string" })),
+            RouteObject::test(HttpMethod::Get, "/task/{id}"),
+        ];
+        let calls = smoke_calls(&routes, &client_operations(&routes));
+
+        // Stands in for a smoke program: creating answers id 42, other calls need that id
+        let script = r#"case "$1" in
+            post_task) echo 'OK {"id": 42, "title": "test"}' ;;
+            *) [ "$3" = 42 ] && echo 'OK null' || echo "FAIL HTTP 404: no task $3" ;;
+        esac"#;
+        let smoke = || {
+            let mut command = Command::new("sh");
+            command.args(["-c", script, "smoke"]);
+            command
+        };
+
+        let mut check = ClientCheck::new("shell");
+        run_smoke(&mut check, smoke, &calls, None).await;
+        assert_eq!(
+            check.passed,
+            vec!["POST /task", "GET /task/{id}", "DELETE /task/{id}"]
+        );
+        assert!(check.failed.is_empty() && check.build_error.is_none());
+    }
+
+    #[test]
+    fn test_keeps_integer_ids_of_number_fields() -> anyhow::Result<()> {
+        // Response type of the generated Rust client, which the smoke program prints back
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Task {
+            id: serde_json::Number,
+            score: serde_json::Number,
+        }
+        let task = serde_json::from_str::<Task>(r#"{"id": 7, "score": 0.5}"#)?;
+        let stdout = format!("OK {}\n", serde_json::to_string(&task)?);
+
+        let Some(SmokeOutcome::Passed(response)) = parse_smoke_output(&stdout) else {
+            panic!("Expected a passed call");
+        };
+        assert_eq!(response, Some(json!({ "id": 7, "score": 0.5 })));
+
+        let mut context = CrudContext::default();
+        context.remember_success(
+            &RouteObject::test(HttpMethod::Post, "/task").with_body(json!({ "score": "number" })),
+            response.as_ref(),
+            None,
+        );
+        assert_eq!(
+            context.path_values(&RouteObject::test(HttpMethod::Get, "/task/{id}")),
+            vec!["7"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parses_smoke_output() {
+        assert_eq!(
+            parse_smoke_output("noise\nOK {\"id\":7}\n"),
+            Some(SmokeOutcome::Passed(Some(serde_json::json!({ "id": 7 }))))
+        );
+        assert_eq!(
+            parse_smoke_output("OK null\n"),
+            Some(SmokeOutcome::Passed(None))
+        );
+        assert_eq!(parse_smoke_output("thread 'main' panicked\n"), None);
+
+        let Some(SmokeOutcome::Failed(error)) =
+            parse_smoke_output("FAIL response does not match the schema: missing field `id`\n")
+        else {
+            panic!("Expected a failed call");
+        };
+
+        let mut check = ClientCheck::new("rust");
+        check.passed = vec!["POST /task".to_string()];
+        check.failed = vec![format!("GET /tasks: {}", error)];
+        assert_eq!(check.summary(), "rust client: 1 of 2 calls passed");
+        assert_eq!(
+            client_failure_report(&[check]).unwrap_or_default(),
+            "CLIENT SDK CHECK FAILED:\nrust client GET /tasks: response does not match the schema: missing field `id`"
+        );
+        assert!(client_failure_report(&[ClientCheck::new("typescript")]).is_none());
+    }
+}
@@ -47,7 +47,7 @@ pub fn get_user_response(question: &str) -> anyhow::Result<String> {
 
     // Print the question in a specific color
     stdout.execute(SetForegroundColor(Color::Blue))?;
-    println!("");
+    println!();
     println!("{}", question);
 
     // Reset the color
@@ -62,12 +62,18 @@ pub fn get_user_response(question: &str) -> anyhow::Result<String> {
     Ok(user_response.trim().to_string())
 }
 
-pub fn confirm_safe_code() -> anyhow::Result<bool> {
+// Ask before running generated code, showing the security review findings first
+pub fn confirm_safe_code(security_review: &str) -> anyhow::Result<bool> {
     let mut stdout = io::stdout();
 
     loop {
+        stdout.execute(SetForegroundColor(Color::Yellow))?;
+        println!();
+        println!("SECURITY REVIEW:");
+        println!("{}", security_review);
+
         stdout.execute(SetForegroundColor(Color::Blue))?;
-        println!("");
+        println!();
         print!("WARNING: You are about to run code written entirely by AI.");
         println!("Review your code and confirm you wish to continue.");
 
@@ -105,6 +111,6 @@ mod tests {
         let agent_pos = "Managing Agent";
         let agent_statement = "Testing testing, processing something";
         let result = print_command.print_agent_message(agent_pos, agent_statement);
-        assert_eq!(result.is_ok(), true);
+        assert!(result.is_ok());
     }
 }
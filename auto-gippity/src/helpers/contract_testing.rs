@@ -0,0 +1,202 @@
+use crate::models::{
+    agents::agent_traits::{ContractViolation, EndpointTestResult},
+    general::api_schema::FieldSchema,
+};
+use serde_json::Value;
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "bool",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn violation(path: &str, expected: &str, found: &str) -> ContractViolation {
+    ContractViolation {
+        path: path.to_string(),
+        expected: expected.to_string(),
+        found: found.to_string(),
+    }
+}
+
+fn validate_value(
+    schema: &FieldSchema,
+    value: &Value,
+    path: &str,
+    violations: &mut Vec<ContractViolation>,
+) {
+    // Nested nulls usually come from Option fields, which the schema cannot express
+    if value.is_null() && path != "$" {
+        return;
+    }
+
+    match (schema, value) {
+        (FieldSchema::None | FieldSchema::NotProvided, _) => {}
+        (FieldSchema::Number, Value::Number(_))
+        | (FieldSchema::String, Value::String(_))
+        | (FieldSchema::Bool, Value::Bool(_)) => {}
+        (FieldSchema::Array(item), Value::Array(values)) => {
+            for (index, element) in values.iter().enumerate() {
+                validate_value(item, element, &format!("{}[{}]", path, index), violations);
+            }
+        }
+        (FieldSchema::Object(fields), Value::Object(object)) => {
+            for (name, field) in fields {
+                let field_path = format!("{}.{}", path, name);
+                match object.get(name) {
+                    Some(field_value) => {
+                        validate_value(field, field_value, &field_path, violations)
+                    }
+                    None => violations.push(violation(&field_path, "present", "missing")),
+                }
+            }
+
+            // An untyped "object" has no field list to hold the response to
+            if fields.is_empty() {
+                return;
+            }
+
+            for name in object.keys().filter(|name| !fields.contains_key(*name)) {
+                let field_path = format!("{}.{}", path, name);
+                violations.push(violation(&field_path, "absent", "unexpected field"));
+            }
+        }
+        (expected, found) => {
+            let expected_type = match expected {
+                FieldSchema::Number => "number",
+                FieldSchema::String => "string",
+                FieldSchema::Bool => "bool",
+                FieldSchema::Array(_) => "array",
+                _ => "object",
+            };
+            violations.push(violation(path, expected_type, json_type(found)));
+        }
+    }
+}
+
+// Compare a live response body against the shape recorded for its route
+pub fn validate_response(schema: &FieldSchema, body: &str) -> Vec<ContractViolation> {
+    let mut violations = Vec::new();
+
+    if matches!(schema, FieldSchema::None | FieldSchema::NotProvided) {
+        return violations;
+    }
+
+    match serde_json::from_str::<Value>(body) {
+        Ok(value) => validate_value(schema, &value, "$", &mut violations),
+        // Plain text is a valid way to respond with a single string
+        Err(_) if matches!(schema, FieldSchema::String) && !body.is_empty() => {}
+        Err(_) => violations.push(violation("$", "JSON body", "no JSON body")),
+    }
+
+    violations
+}
+
+// Human readable list of every contract violation, None when all responses matched
+pub fn contract_report(results: &[EndpointTestResult]) -> Option<String> {
+    let lines = results
+        .iter()
+        .flat_map(|result| {
+            result.contract_violations.iter().map(move |violation| {
+                format!(
+                    "{} {}: {} expected {}, found {}",
+                    result.method,
+                    result.route,
+                    violation.path,
+                    violation.expected,
+                    violation.found
+                )
+            })
+        })
+        .collect::<Vec<String>>();
+
+    match lines.is_empty() {
+        true => None,
+        false => Some(format!(
+            "RESPONSE CONTRACT VIOLATIONS: responses do not match the API schema\n{}",
+            lines.join("\n")
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::general::api_schema::HttpMethod;
+    use serde_json::json;
+
+    fn item_schema() -> FieldSchema {
+        FieldSchema::from_value(&json!({
+            "id": "number",
+            "name": "string",
+            "tags": ["string"],
+            "completed": "bool"
+        }))
+    }
+
+    #[test]
+    fn test_accepts_matching_response() {
+        let body = r#"{"id": 1, "name": "task", "tags": ["a", "b"], "completed": null}"#;
+        assert!(validate_response(&item_schema(), body).is_empty());
+
+        let listing = FieldSchema::Array(Box::new(item_schema()));
+        assert!(validate_response(&listing, "[]").is_empty());
+        assert!(validate_response(&FieldSchema::NotProvided, "whatever").is_empty());
+        assert!(validate_response(&FieldSchema::String, "plain text").is_empty());
+
+        let untyped = FieldSchema::from_value(&json!("object"));
+        assert!(validate_response(&untyped, r#"{"id": 1, "name": "task"}"#).is_empty());
+        assert_eq!(
+            validate_response(&untyped, "[]"),
+            vec![violation("$", "object", "array")]
+        );
+    }
+
+    #[test]
+    fn test_reports_mismatched_response() {
+        let body = r#"{"id": "1", "tags": ["a", 2], "completed": true, "extra": 0}"#;
+        let violations = validate_response(&item_schema(), body);
+
+        assert_eq!(
+            violations,
+            vec![
+                violation("$.id", "number", "string"),
+                violation("$.name", "present", "missing"),
+                violation("$.tags[1]", "string", "number"),
+                violation("$.extra", "absent", "unexpected field"),
+            ]
+        );
+
+        let listing = FieldSchema::Array(Box::new(item_schema()));
+        assert_eq!(
+            validate_response(&listing, r#"{"items": []}"#),
+            vec![violation("$", "array", "object")]
+        );
+        assert_eq!(
+            validate_response(&listing, ""),
+            vec![violation("$", "JSON body", "no JSON body")]
+        );
+    }
+
+    #[test]
+    fn test_builds_contract_report() {
+        let mut result = EndpointTestResult {
+            method: HttpMethod::Get,
+            route: "/item/{id}".to_string(),
+            url: "http://localhost:8080/item/1".to_string(),
+            status: Some(200),
+            passed: true,
+            message: "OK".to_string(),
+            contract_violations: Vec::new(),
+        };
+        assert_eq!(contract_report(&[result.clone()]), None);
+
+        result.contract_violations = vec![violation("$.id", "number", "string")];
+        let report = contract_report(&[result]).unwrap_or_default();
+        assert!(report.contains("GET /item/{id}: $.id expected number, found string"));
+    }
+}
@@ -0,0 +1,350 @@
+use std::collections::BTreeSet;
+use syn::{
+    parse::Parser, punctuated::Punctuated, visit::Visit, Expr, ItemExternCrate, ItemMod, ItemUse,
+    Macro, Path, Token, UseTree,
+};
+use toml_edit::{value, Array, DocumentMut, InlineTable, Item, Table};
+
+// Crate published on crates.io that generated code is allowed to depend on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CrateSpec {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub features: &'static [&'static str],
+}
+
+impl CrateSpec {
+    // Name used inside Rust code, i.e. `actix-web` is imported as `actix_web`
+    pub fn lib_name(&self) -> String {
+        self.name.replace('-', "_")
+    }
+}
+
+// Roots of paths that never refer to an external crate
+const BUILTIN_ROOTS: &[&str] = &[
+    "std", "core", "alloc", "crate", "self", "super", "Self", "bool", "char", "str", "f32", "f64",
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+];
+
+// Outcome of reconciling the generated manifest with the generated code
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DependencyReport {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub disallowed: Vec<String>,
+}
+
+impl DependencyReport {
+    pub fn has_disallowed(&self) -> bool {
+        !self.disallowed.is_empty()
+    }
+
+    // Bug description handed back to the fix loop
+    pub fn describe_disallowed(&self, allowed: &[CrateSpec]) -> String {
+        let allowed_names = allowed
+            .iter()
+            .map(|spec| spec.lib_name())
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        format!(
+            "DISALLOWED CRATES: the code uses {} which are not installed. \
+            Remove them and only use the standard library or: {}",
+            self.disallowed.join(", "),
+            allowed_names
+        )
+    }
+}
+
+#[derive(Default)]
+struct CrateCollector {
+    roots: BTreeSet<String>,
+    local_names: BTreeSet<String>,
+}
+
+impl CrateCollector {
+    fn collect_use_tree(&mut self, tree: &UseTree, is_root: bool) {
+        match tree {
+            UseTree::Path(use_path) => {
+                if is_root {
+                    self.roots.insert(use_path.ident.to_string());
+                }
+                self.collect_use_tree(&use_path.tree, false);
+            }
+            UseTree::Name(use_name) => {
+                if is_root {
+                    self.roots.insert(use_name.ident.to_string());
+                }
+                self.local_names.insert(use_name.ident.to_string());
+            }
+            UseTree::Rename(use_rename) => {
+                if is_root {
+                    self.roots.insert(use_rename.ident.to_string());
+                }
+                self.local_names.insert(use_rename.rename.to_string());
+            }
+            UseTree::Group(use_group) => {
+                for item in use_group.items.iter() {
+                    self.collect_use_tree(item, is_root);
+                }
+            }
+            UseTree::Glob(_) => {}
+        }
+    }
+
+    fn into_crates(self) -> BTreeSet<String> {
+        self.roots
+            .into_iter()
+            .filter(|root| !self.local_names.contains(root))
+            .filter(|root| !BUILTIN_ROOTS.contains(&root.as_str()))
+            .filter(|root| root.starts_with(|c: char| c.is_ascii_lowercase()))
+            .collect()
+    }
+}
+
+impl<'ast> Visit<'ast> for CrateCollector {
+    fn visit_item_use(&mut self, node: &'ast ItemUse) {
+        self.collect_use_tree(&node.tree, true);
+    }
+
+    fn visit_item_extern_crate(&mut self, node: &'ast ItemExternCrate) {
+        self.roots.insert(node.ident.to_string());
+        if let Some((_, rename)) = &node.rename {
+            self.local_names.insert(rename.to_string());
+        }
+    }
+
+    fn visit_item_mod(&mut self, node: &'ast ItemMod) {
+        self.local_names.insert(node.ident.to_string());
+        syn::visit::visit_item_mod(self, node);
+    }
+
+    fn visit_path(&mut self, node: &'ast Path) {
+        if node.segments.len() > 1 || node.leading_colon.is_some() {
+            if let Some(first) = node.segments.first() {
+                self.roots.insert(first.ident.to_string());
+            }
+        }
+        syn::visit::visit_path(self, node);
+    }
+
+    fn visit_macro(&mut self, node: &'ast Macro) {
+        // Arguments of format-like macros are plain expressions worth inspecting
+        let parser = Punctuated::<Expr, Token![,]>::parse_terminated;
+        if let Ok(args) = parser.parse2(node.tokens.clone()) {
+            for arg in args.iter() {
+                self.visit_expr(arg);
+            }
+        }
+        syn::visit::visit_macro(self, node);
+    }
+}
+
+// Fallback for code that does not parse: only look at `use` statements
+fn collect_used_crates_from_lines(code: &str) -> BTreeSet<String> {
+    let mut collector = CrateCollector::default();
+
+    for line in code.lines() {
+        let line = line.trim();
+        let Some(path) = line
+            .strip_prefix("pub use ")
+            .or_else(|| line.strip_prefix("use "))
+        else {
+            continue;
+        };
+
+        let root = path
+            .trim_start_matches("::")
+            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .next()
+            .unwrap_or_default();
+
+        if !root.is_empty() {
+            collector.roots.insert(root.to_string());
+        }
+    }
+
+    collector.into_crates()
+}
+
+// Find the external crates referenced by a piece of Rust code
+pub fn collect_used_crates(code: &str) -> BTreeSet<String> {
+    match syn::parse_file(code) {
+        Ok(file) => {
+            let mut collector = CrateCollector::default();
+            collector.visit_file(&file);
+            collector.into_crates()
+        }
+        Err(_) => collect_used_crates_from_lines(code),
+    }
+}
+
+fn dependency_item(spec: &CrateSpec) -> Item {
+    if spec.features.is_empty() {
+        return value(spec.version);
+    }
+
+    let mut features = Array::new();
+    for feature in spec.features {
+        features.push(*feature);
+    }
+
+    let mut table = InlineTable::new();
+    table.insert("version", spec.version.into());
+    table.insert("features", features.into());
+
+    value(table)
+}
+
+// Rewrite the [dependencies] table so it matches exactly what the code uses
+pub fn reconcile_manifest(
+    manifest: &str,
+    used_crates: &BTreeSet<String>,
+    allowed: &[CrateSpec],
+) -> anyhow::Result<(String, DependencyReport)> {
+    let mut document = manifest.parse::<DocumentMut>()?;
+    let mut report = DependencyReport::default();
+
+    let dependencies = document
+        .entry("dependencies")
+        .or_insert(Item::Table(Table::new()))
+        .as_table_mut()
+        .ok_or_else(|| anyhow::anyhow!("[dependencies] is not a table"))?;
+
+    // Drop anything the code no longer needs or is not allowed to have
+    let existing = dependencies
+        .iter()
+        .map(|(name, _)| name.to_string())
+        .collect::<Vec<String>>();
+
+    for name in existing {
+        let lib_name = name.replace('-', "_");
+        let is_allowed = allowed.iter().any(|spec| spec.name == name);
+
+        if !is_allowed || !used_crates.contains(&lib_name) {
+            dependencies.remove(&name);
+            report.removed.push(name);
+        }
+    }
+
+    for used in used_crates {
+        match allowed.iter().find(|spec| &spec.lib_name() == used) {
+            Some(spec) => {
+                let pinned = dependency_item(spec);
+                let is_pinned = dependencies
+                    .get(spec.name)
+                    .map(|current| current.to_string() == pinned.to_string())
+                    .unwrap_or(false);
+
+                if !is_pinned {
+                    if !dependencies.contains_key(spec.name) {
+                        report.added.push(spec.name.to_string());
+                    }
+                    dependencies.insert(spec.name, pinned);
+                }
+            }
+            None => report.disallowed.push(used.clone()),
+        }
+    }
+
+    Ok((document.to_string(), report))
+}
+
+// Manifest used when the generated project does not have one yet
+pub fn default_manifest() -> String {
+    [
+        "[package]",
+        "name = \"web_server\"",
+        "version = \"0.1.0\"",
+        "edition = \"2021\"",
+        "",
+        "# Keep the generated project out of any parent workspace",
+        "[workspace]",
+        "",
+        "[[bin]]",
+        "name = \"web_server\"",
+        "path = \"main.rs\"",
+        "",
+        "[dependencies]",
+        "",
+    ]
+    .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::templates::{
+        template_registry::find_template, template_traits::BackendFramework,
+    };
+
+    const SAMPLE_CODE: &str = r#"
+        use actix_web::{web, App, HttpServer, Responder};
+        use serde::{Deserialize, Serialize};
+        use std::sync::Mutex;
+
+        mod storage {
+            pub fn load() {}
+        }
+
+        #[derive(Serialize, Deserialize)]
+        struct Item {
+            id: u64,
+        }
+
+        async fn list() -> impl Responder {
+            storage::load();
+            web::Json(serde_json::json!({ "ok": u64::MAX }))
+        }
+
+        #[actix_web::main]
+        async fn main() -> std::io::Result<()> {
+            let _ids = rand::random::<u64>();
+            println!("{}", chrono::Utc::now());
+            HttpServer::new(|| App::new().route("/", web::get().to(list)))
+                .bind("127.0.0.1:8080")?
+                .run()
+                .await
+        }
+    "#;
+
+    #[test]
+    fn test_collects_external_crates() {
+        let crates = collect_used_crates(SAMPLE_CODE);
+        let expected = ["actix_web", "chrono", "rand", "serde", "serde_json"]
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<BTreeSet<String>>();
+
+        assert_eq!(crates, expected);
+    }
+
+    #[test]
+    fn test_collects_crates_from_unparsable_code() {
+        let crates = collect_used_crates("use actix_cors::Cors;\nuse std::fs;\nfn main( {");
+        assert_eq!(
+            crates.into_iter().collect::<Vec<String>>(),
+            vec!["actix_cors"]
+        );
+    }
+
+    #[test]
+    fn test_reconciles_manifest() -> anyhow::Result<()> {
+        let manifest = format!("{}actix-web = \"3\"\nrand = \"0.8\"\n", default_manifest());
+        let used_crates = collect_used_crates(SAMPLE_CODE);
+        let allowed = find_template(BackendFramework::ActixWeb).dependencies();
+
+        let (updated, report) = reconcile_manifest(&manifest, &used_crates, &allowed)?;
+
+        assert!(updated.contains("actix-web = \"4.8\""));
+        assert!(updated.contains("serde = { version = \"1.0\", features = [\"derive\"] }"));
+        assert!(updated.contains("serde_json = \"1.0\""));
+        assert!(!updated.contains("rand"));
+        assert!(updated.contains("[workspace]"));
+        assert_eq!(report.added, vec!["serde", "serde_json"]);
+        assert_eq!(report.removed, vec!["rand"]);
+        assert_eq!(report.disallowed, vec!["chrono", "rand"]);
+
+        Ok(())
+    }
+}
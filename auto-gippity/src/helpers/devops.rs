@@ -0,0 +1,431 @@
+use super::{
+    docker_lint::{lint_dockerfile, parse_dockerfile},
+    test_server::SERVER_PORT_ENV,
+};
+use crate::models::general::deployment::DeploymentSetup;
+use serde_json::{json, Map, Value};
+use toml_edit::DocumentMut;
+
+pub const DOCKERFILE_NAME: &str = "Dockerfile";
+pub const DOCKERIGNORE_NAME: &str = ".dockerignore";
+pub const COMPOSE_NAME: &str = "compose.yaml";
+pub const DEPLOYMENT_DOCS_NAME: &str = "DEPLOYMENT.md";
+
+// Where the server runs inside the container, also its working directory for data files
+const CONTAINER_APP_DIR: &str = "/app";
+const DATA_VOLUME: &str = "data";
+
+const BUILDER_IMAGE: &str = "rust:1-slim-bookworm";
+const RUNTIME_IMAGE: &str = "debian:bookworm-slim";
+const FRONTEND_IMAGE: &str = "nginx:1-alpine";
+
+// Name of the binary cargo builds, the first [[bin]] or else the package
+pub fn manifest_binary_name(manifest: &str) -> Option<String> {
+    let document = manifest.parse::<DocumentMut>().ok()?;
+
+    let bin_name = document
+        .get("bin")
+        .and_then(|bins| bins.as_array_of_tables())
+        .and_then(|bins| bins.iter().next())
+        .and_then(|bin| bin.get("name"))
+        .and_then(|name| name.as_str());
+    let package_name = document
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(|name| name.as_str());
+
+    bin_name.or(package_name).map(|name| name.to_string())
+}
+
+// Multi-stage build: compile with the full toolchain, run from a slim image as a non-root user
+pub fn dockerfile(setup: &DeploymentSetup) -> String {
+    let binary = &setup.binary_name;
+    let env = setup
+        .env_vars
+        .iter()
+        .map(|var| format!("{}={}", var.name, var.value))
+        .collect::<Vec<String>>()
+        .join(" \\\n    ");
+
+    format!(
+        r#"# Generated by the DevOps agent
+FROM {builder} AS builder
+RUN apt-get update \
+    && apt-get install -y --no-install-recommends pkg-config libssl-dev \
+    && rm -rf /var/lib/apt/lists/*
+WORKDIR {app}
+COPY . .
+RUN cargo build --release --bin {binary}
+
+FROM {runtime}
+RUN apt-get update \
+    && apt-get install -y --no-install-recommends ca-certificates libssl3 \
+    && rm -rf /var/lib/apt/lists/*
+RUN useradd --system --create-home --home-dir {app} app
+WORKDIR {app}
+COPY --from=builder {app}/target/release/{binary} /usr/local/bin/{binary}
+ENV {env}
+EXPOSE {port}
+USER app
+CMD ["{binary}"]
+"#,
+        builder = BUILDER_IMAGE,
+        runtime = RUNTIME_IMAGE,
+        app = CONTAINER_APP_DIR,
+        binary = binary,
+        env = env,
+        port = setup.port,
+    )
+}
+
+// Build outputs, generated clients and local data stay out of the build context
+pub fn dockerignore() -> String {
+    [
+        "target",
+        "clients",
+        DOCKERFILE_NAME,
+        DOCKERIGNORE_NAME,
+        COMPOSE_NAME,
+        ".env",
+        "*.db",
+        "database.json",
+    ]
+    .join("\n")
+        + "\n"
+}
+
+pub fn compose_file(setup: &DeploymentSetup) -> anyhow::Result<String> {
+    let environment = setup
+        .env_vars
+        .iter()
+        .map(|var| (var.name.clone(), json!(var.value)))
+        .collect::<Map<String, Value>>();
+
+    let mut services = Map::new();
+    services.insert(
+        setup.service_name(),
+        json!({
+            "build": { "context": "." },
+            "environment": environment,
+            "ports": [format!("{}:{}", setup.port, setup.port)],
+            "volumes": [format!("{}:{}", DATA_VOLUME, CONTAINER_APP_DIR)],
+            "restart": "unless-stopped",
+        }),
+    );
+
+    if setup.has_frontend {
+        services.insert(
+            "frontend".to_string(),
+            json!({
+                "image": FRONTEND_IMAGE,
+                "ports": [format!("{}:80", setup.frontend_port())],
+                "volumes": ["./static:/usr/share/nginx/html:ro"],
+                "depends_on": [setup.service_name()],
+                "restart": "unless-stopped",
+            }),
+        );
+    }
+
+    let compose = json!({
+        "services": services,
+        "volumes": { DATA_VOLUME: {} },
+    });
+    Ok(serde_yaml::to_string(&compose)?)
+}
+
+// How to run the container and what it reads from its environment
+pub fn deployment_docs(setup: &DeploymentSetup) -> String {
+    let mut docs = format!(
+        "# Deployment\n\n\
+        `{binary}` is packaged as a container image by `{dockerfile}` and run with `{compose}`.\n\n\
+        ## Run\n\n\
+        ```sh\ndocker compose up --build\n```\n\n\
+        The API is published on http://localhost:{port}.\n",
+        binary = setup.binary_name,
+        dockerfile = DOCKERFILE_NAME,
+        compose = COMPOSE_NAME,
+        port = setup.port,
+    );
+    if setup.has_frontend {
+        docs.push_str(&format!(
+            "The static frontend is served on http://localhost:{}.\n",
+            setup.frontend_port()
+        ));
+    }
+
+    docs.push_str(
+        "\n## Environment\n\n| Variable | Value in the container | Description |\n|---|---|---|\n",
+    );
+    for var in setup.env_vars.iter() {
+        docs.push_str(&format!(
+            "| `{}` | `{}` | {} |\n",
+            var.name, var.value, var.description
+        ));
+    }
+
+    docs.push_str(&format!(
+        "\n## Data\n\nFiles the server writes to its working directory `{}` are kept in the `{}` volume.\n",
+        CONTAINER_APP_DIR, DATA_VOLUME
+    ));
+
+    if !setup.external_urls.is_empty() {
+        docs.push_str(
+            "\n## Outbound access\n\nThe server calls these external APIs, so the container needs outbound network access:\n\n",
+        );
+        for url in setup.external_urls.iter() {
+            docs.push_str(&format!("- {}\n", url));
+        }
+    }
+
+    docs
+}
+
+pub fn deployment_files(setup: &DeploymentSetup) -> anyhow::Result<Vec<(&'static str, String)>> {
+    Ok(vec![
+        (DOCKERFILE_NAME, dockerfile(setup)),
+        (DOCKERIGNORE_NAME, dockerignore()),
+        (COMPOSE_NAME, compose_file(setup)?),
+        (DEPLOYMENT_DOCS_NAME, deployment_docs(setup)),
+    ])
+}
+
+fn check_dockerfile(setup: &DeploymentSetup, content: &str, issues: &mut Vec<String>) {
+    for finding in lint_dockerfile(content) {
+        issues.push(format!("{}: {}", DOCKERFILE_NAME, finding));
+    }
+
+    let instructions = parse_dockerfile(content);
+    let has = |keyword: &str, check: &dyn Fn(&str) -> bool| {
+        instructions
+            .iter()
+            .any(|instruction| instruction.keyword == keyword && check(&instruction.arguments))
+    };
+
+    let binary = &setup.binary_name;
+    let port = setup.port.to_string();
+    if !has("RUN", &|args| args.contains(&format!("--bin {}", binary))) {
+        issues.push(format!(
+            "{}: does not build the {} binary",
+            DOCKERFILE_NAME, binary
+        ));
+    }
+    if !has("COPY", &|args| {
+        args.contains(&format!("target/release/{} ", binary))
+    }) {
+        issues.push(format!(
+            "{}: does not copy the {} binary",
+            DOCKERFILE_NAME, binary
+        ));
+    }
+    if !has("EXPOSE", &|args| {
+        args.split_whitespace().any(|word| word == port)
+    }) {
+        issues.push(format!(
+            "{}: does not expose port {}",
+            DOCKERFILE_NAME, port
+        ));
+    }
+    if !has("ENV", &|args| {
+        args.contains(&format!("{}={}", SERVER_PORT_ENV, port))
+    }) {
+        issues.push(format!(
+            "{}: does not set {}={}",
+            DOCKERFILE_NAME, SERVER_PORT_ENV, port
+        ));
+    }
+
+    let command = instructions
+        .iter()
+        .rev()
+        .find(|instruction| ["CMD", "ENTRYPOINT"].contains(&instruction.keyword.as_str()));
+    if !command.is_some_and(|command| command.arguments.contains(binary.as_str())) {
+        issues.push(format!("{}: does not run {}", DOCKERFILE_NAME, binary));
+    }
+}
+
+fn check_compose(setup: &DeploymentSetup, content: &str, issues: &mut Vec<String>) {
+    let compose = match serde_yaml::from_str::<Value>(content) {
+        Ok(compose) => compose,
+        Err(e) => {
+            issues.push(format!("{}: invalid YAML: {}", COMPOSE_NAME, e));
+            return;
+        }
+    };
+
+    let service_name = setup.service_name();
+    let service = &compose["services"][&service_name];
+    if !service.is_object() {
+        issues.push(format!("{}: no {} service", COMPOSE_NAME, service_name));
+        return;
+    }
+    if service["build"].is_null() {
+        issues.push(format!(
+            "{}: {} is not built from the Dockerfile",
+            COMPOSE_NAME, service_name
+        ));
+    }
+
+    let port = setup.port.to_string();
+    let publishes_port = service["ports"].as_array().is_some_and(|ports| {
+        ports
+            .iter()
+            .filter_map(Value::as_str)
+            .any(|mapping| mapping.rsplit(':').next() == Some(port.as_str()))
+    });
+    if !publishes_port {
+        issues.push(format!(
+            "{}: {} does not publish port {}",
+            COMPOSE_NAME, service_name, port
+        ));
+    }
+    if service["environment"][SERVER_PORT_ENV].as_str() != Some(port.as_str()) {
+        issues.push(format!(
+            "{}: {} does not set {}={}",
+            COMPOSE_NAME, service_name, SERVER_PORT_ENV, port
+        ));
+    }
+
+    for (name, service) in compose["services"].as_object().into_iter().flatten() {
+        if let Some(image) = service["image"].as_str() {
+            let tag = image.rsplit('/').next().unwrap_or_default();
+            if !tag.contains(':') || tag.ends_with(":latest") {
+                issues.push(format!(
+                    "{}: pin a version of the {} image",
+                    COMPOSE_NAME, name
+                ));
+            }
+        }
+    }
+}
+
+// Lint findings and places where the files disagree with the setup, empty when they are fine
+pub fn check_deployment_files(setup: &DeploymentSetup, files: &[(&str, String)]) -> Vec<String> {
+    let mut issues = Vec::new();
+    let file = |name: &str| {
+        files
+            .iter()
+            .find(|(file_name, _)| *file_name == name)
+            .map(|(_, content)| content.as_str())
+    };
+
+    for name in [
+        DOCKERFILE_NAME,
+        DOCKERIGNORE_NAME,
+        COMPOSE_NAME,
+        DEPLOYMENT_DOCS_NAME,
+    ] {
+        if file(name).is_none() {
+            issues.push(format!("{} is missing", name));
+        }
+    }
+
+    if let Some(content) = file(DOCKERFILE_NAME) {
+        check_dockerfile(setup, content, &mut issues);
+    }
+    if let Some(content) = file(COMPOSE_NAME) {
+        check_compose(setup, content, &mut issues);
+    }
+    if let Some(content) = file(DOCKERIGNORE_NAME) {
+        if !content
+            .lines()
+            .any(|line| ["target", "target/", "/target"].contains(&line.trim()))
+        {
+            issues.push(format!(
+                "{}: target is sent to the build context",
+                DOCKERIGNORE_NAME
+            ));
+        }
+    }
+    if let Some(content) = file(DEPLOYMENT_DOCS_NAME) {
+        for var in setup.env_vars.iter() {
+            if !content.contains(&format!("`{}`", var.name)) {
+                issues.push(format!(
+                    "{}: {} is not documented",
+                    DEPLOYMENT_DOCS_NAME, var.name
+                ));
+            }
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{agents::agent_traits::FactSheet, general::deployment::ServerSetup};
+
+    fn setup() -> DeploymentSetup {
+        let manifest = "[package]\nname = \"tasks\"\nversion = \"0.1.0\"\n\n[[bin]]\nname = \"task_api\"\npath = \"main.rs\"\n";
+        let factsheet = FactSheet {
+            external_urls: Some(vec!["https://api.exchangerate.host/latest".to_string()]),
+            frontend_code: Some("<html></html>".to_string()),
+            ..Default::default()
+        };
+
+        let server = ServerSetup {
+            port: 9090,
+            ..ServerSetup::from_manifest(manifest)
+        };
+        DeploymentSetup::new(&server, &factsheet)
+    }
+
+    #[test]
+    fn test_files_use_binary_and_port() -> anyhow::Result<()> {
+        let setup = setup();
+        assert_eq!(setup.binary_name, "task_api");
+
+        let files = deployment_files(&setup)?;
+        assert!(check_deployment_files(&setup, &files).is_empty());
+
+        let dockerfile = &files[0].1;
+        assert!(dockerfile.contains("RUN cargo build --release --bin task_api\n"));
+        assert!(dockerfile.contains(
+            "COPY --from=builder /app/target/release/task_api /usr/local/bin/task_api\n"
+        ));
+        assert!(dockerfile.contains("ENV HOST=0.0.0.0 \\\n    PORT=9090\nEXPOSE 9090\n"));
+        assert!(dockerfile.ends_with("USER app\nCMD [\"task_api\"]\n"));
+
+        let compose = serde_yaml::from_str::<Value>(&files[2].1)?;
+        let service = &compose["services"]["task_api"];
+        assert_eq!(service["ports"], json!(["9090:9090"]));
+        assert_eq!(service["environment"]["PORT"], "9090");
+        assert_eq!(compose["services"]["frontend"]["ports"], json!(["9091:80"]));
+
+        let docs = &files[3].1;
+        assert!(docs.contains("| `PORT` | `9090` |"));
+        assert!(docs.contains("http://localhost:9090"));
+        assert!(docs.contains("- https://api.exchangerate.host/latest\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reports_mismatched_files() -> anyhow::Result<()> {
+        let setup = setup();
+        let mut files = deployment_files(&setup)?;
+        files[0].1 = files[0]
+            .1
+            .replace("9090", "8080")
+            .replace("task_api", "web_server");
+        files[1].1 = "*.db\n".to_string();
+        files[2].1 = files[2].1.replace("9090:9090", "9090:8080");
+        files.pop();
+
+        assert_eq!(
+            check_deployment_files(&setup, &files),
+            vec![
+                "DEPLOYMENT.md is missing",
+                "Dockerfile: does not build the task_api binary",
+                "Dockerfile: does not copy the task_api binary",
+                "Dockerfile: does not expose port 9090",
+                "Dockerfile: does not set PORT=9090",
+                "Dockerfile: does not run task_api",
+                "compose.yaml: task_api does not publish port 9090",
+                ".dockerignore: target is sent to the build context",
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,286 @@
+use std::{collections::BTreeSet, fmt};
+
+// One Dockerfile instruction, with continuation lines joined
+#[derive(Debug, Clone, PartialEq)]
+pub struct Instruction {
+    pub line: usize,
+    pub keyword: String,
+    pub arguments: String,
+}
+
+// Broken rule, numbered like the matching hadolint rule
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintFinding {
+    pub rule: &'static str,
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for LintFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} line {}: {}", self.rule, self.line, self.message)
+    }
+}
+
+pub fn parse_dockerfile(content: &str) -> Vec<Instruction> {
+    let mut instructions = Vec::new();
+    let mut pending: Option<(usize, String)> = None;
+
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('#') || (trimmed.is_empty() && pending.is_none()) {
+            continue;
+        }
+
+        let (start, mut text) = pending.take().unwrap_or((index + 1, String::new()));
+        let (part, continues) = match trimmed.strip_suffix('\\') {
+            Some(part) => (part.trim_end(), true),
+            None => (trimmed, false),
+        };
+        if !text.is_empty() && !part.is_empty() {
+            text.push(' ');
+        }
+        text.push_str(part);
+
+        if continues {
+            pending = Some((start, text));
+            continue;
+        }
+
+        let (keyword, arguments) = text.split_once(char::is_whitespace).unwrap_or((&text, ""));
+        if !keyword.is_empty() {
+            instructions.push(Instruction {
+                line: start,
+                keyword: keyword.to_uppercase(),
+                arguments: arguments.trim().to_string(),
+            });
+        }
+    }
+
+    instructions
+}
+
+fn is_archive(source: &str) -> bool {
+    [".tar", ".tar.gz", ".tgz", ".tar.bz2", ".tar.xz"]
+        .iter()
+        .any(|extension| source.ends_with(extension))
+}
+
+// Image reference of a FROM instruction, with its stage alias
+fn from_parts(arguments: &str) -> (String, Option<String>) {
+    let words = arguments
+        .split_whitespace()
+        .filter(|word| !word.starts_with("--"))
+        .collect::<Vec<&str>>();
+
+    let image = words.first().unwrap_or(&"").to_string();
+    let alias = match words.get(1) {
+        Some(word) if word.eq_ignore_ascii_case("as") => {
+            words.get(2).map(|alias| alias.to_lowercase())
+        }
+        _ => None,
+    };
+    (image, alias)
+}
+
+fn lint_run(instruction: &Instruction, findings: &mut Vec<LintFinding>) {
+    let mut finding = |rule: &'static str, message: &str| {
+        findings.push(LintFinding {
+            rule,
+            line: instruction.line,
+            message: message.to_string(),
+        })
+    };
+
+    let commands = instruction
+        .arguments
+        .split("&&")
+        .flat_map(|command| command.split(';'))
+        .map(str::trim)
+        .collect::<Vec<&str>>();
+
+    if commands.iter().any(|command| command.starts_with("cd ")) {
+        finding("DL3003", "Use WORKDIR to switch to a directory");
+    }
+
+    let installs = commands
+        .iter()
+        .filter(|command| command.starts_with("apt-get") && command.contains(" install"))
+        .collect::<Vec<&&str>>();
+    if installs.is_empty() {
+        return;
+    }
+
+    for install in installs.iter() {
+        let flags = install.split_whitespace().collect::<Vec<&str>>();
+        if !flags
+            .iter()
+            .any(|flag| ["-y", "--yes", "-qq", "--assume-yes"].contains(flag))
+        {
+            finding(
+                "DL3014",
+                "Use the -y switch to avoid manual input `apt-get -y install <package>`",
+            );
+        }
+        if !flags.contains(&"--no-install-recommends") {
+            finding(
+                "DL3015",
+                "Avoid additional packages by specifying `--no-install-recommends`",
+            );
+        }
+    }
+
+    if !instruction.arguments.contains("rm -rf /var/lib/apt/lists") {
+        finding(
+            "DL3009",
+            "Delete the apt-get lists after installing something",
+        );
+    }
+}
+
+// The subset of hadolint's rules that matter for the Dockerfiles we generate
+pub fn lint_dockerfile(content: &str) -> Vec<LintFinding> {
+    let instructions = parse_dockerfile(content);
+    let mut findings = Vec::new();
+    let mut aliases = BTreeSet::new();
+    let mut stages = 0;
+    let mut last_user: Option<&Instruction> = None;
+
+    for instruction in instructions.iter() {
+        let mut finding = |rule: &'static str, message: String| {
+            findings.push(LintFinding {
+                rule,
+                line: instruction.line,
+                message,
+            })
+        };
+
+        match instruction.keyword.as_str() {
+            "FROM" => {
+                let (image, alias) = from_parts(&instruction.arguments);
+                let name = image.rsplit('/').next().unwrap_or_default();
+                let is_stage = aliases.contains(&image.to_lowercase());
+
+                if image != "scratch" && !is_stage && !name.contains([':', '@']) {
+                    finding(
+                        "DL3006",
+                        format!("Always tag the version of an image explicitly: {}", image),
+                    );
+                }
+                if name.ends_with(":latest") {
+                    finding(
+                        "DL3007",
+                        format!("Using latest is prone to errors, pin a version: {}", image),
+                    );
+                }
+                if let Some(alias) = alias {
+                    if !aliases.insert(alias.clone()) {
+                        finding("DL3024", format!("FROM aliases must be unique: {}", alias));
+                    }
+                }
+
+                stages += 1;
+                last_user = None;
+            }
+            "MAINTAINER" => finding("DL4000", "MAINTAINER is deprecated".to_string()),
+            "WORKDIR" => {
+                let dir = instruction.arguments.trim_matches('"');
+                if !dir.starts_with('/') && !dir.starts_with('$') {
+                    finding("DL3000", format!("Use absolute WORKDIR: {}", dir));
+                }
+            }
+            "ADD" => {
+                let sources = instruction
+                    .arguments
+                    .split_whitespace()
+                    .filter(|word| !word.starts_with("--"))
+                    .collect::<Vec<&str>>();
+                let sources = &sources[..sources.len().saturating_sub(1)];
+                if sources
+                    .iter()
+                    .any(|source| !source.contains("://") && !is_archive(source))
+                {
+                    finding(
+                        "DL3020",
+                        "Use COPY instead of ADD for files and folders".to_string(),
+                    );
+                }
+            }
+            "COPY" => {
+                let from = instruction
+                    .arguments
+                    .split_whitespace()
+                    .find_map(|word| word.strip_prefix("--from="));
+                if let Some(from) = from {
+                    let is_index = from.parse::<usize>().is_ok_and(|index| index < stages);
+                    let is_image = from.contains([':', '/']);
+                    if !aliases.contains(&from.to_lowercase()) && !is_index && !is_image {
+                        finding(
+                            "DL3022",
+                            format!(
+                                "COPY --from should reference a previously defined FROM alias: {}",
+                                from
+                            ),
+                        );
+                    }
+                }
+            }
+            "RUN" => lint_run(instruction, &mut findings),
+            "CMD" | "ENTRYPOINT" if !instruction.arguments.starts_with('[') => finding(
+                "DL3025",
+                format!("Use arguments JSON notation for {}", instruction.keyword),
+            ),
+            "USER" => last_user = Some(instruction),
+            _ => {}
+        }
+    }
+
+    if let Some(user) = last_user {
+        let name = user.arguments.split(':').next().unwrap_or_default();
+        if name == "root" || name == "0" {
+            findings.push(LintFinding {
+                rule: "DL3002",
+                line: user.line,
+                message: "Last USER should not be root".to_string(),
+            });
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lints_dockerfile() {
+        let dockerfile = "# builder\nFROM rust AS build\nMAINTAINER someone\nWORKDIR app\nADD . .\nRUN cd app && apt-get update \\\n    && apt-get install curl\n\nFROM debian:latest AS build\nCOPY --from=builder /app/server /server\nUSER root\nCMD /server\n";
+
+        let rules = lint_dockerfile(dockerfile)
+            .iter()
+            .map(|finding| format!("{} {}", finding.rule, finding.line))
+            .collect::<Vec<String>>();
+        assert_eq!(
+            rules,
+            vec![
+                "DL3006 2",
+                "DL4000 3",
+                "DL3000 4",
+                "DL3020 5",
+                "DL3003 6",
+                "DL3014 6",
+                "DL3015 6",
+                "DL3009 6",
+                "DL3007 9",
+                "DL3024 9",
+                "DL3022 10",
+                "DL3025 12",
+                "DL3002 11",
+            ]
+        );
+
+        let clean = "FROM rust:1-slim AS builder\nRUN apt-get update \\\n    && apt-get install -y --no-install-recommends pkg-config \\\n    && rm -rf /var/lib/apt/lists/*\nFROM debian:bookworm-slim\nCOPY --from=builder /app/server /server\nUSER app\nCMD [\"/server\"]\n";
+        assert!(lint_dockerfile(clean).is_empty());
+    }
+}
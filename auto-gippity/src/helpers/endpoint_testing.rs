@@ -0,0 +1,455 @@
+use super::{
+    command_line::PrintCommand, contract_testing::validate_response, route_parser::dynamic_segments,
+};
+use crate::models::{
+    agents::agent_traits::{EndpointTestResult, RouteObject},
+    general::api_schema::{FieldSchema, HttpMethod},
+};
+use reqwest::Client;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+// Resource a route acts on, i.e. `/item/{id}` -> `/item`
+pub fn resource_base(route: &str) -> String {
+    let mut segments = route
+        .trim_end_matches('/')
+        .split('/')
+        .collect::<Vec<&str>>();
+
+    while segments
+        .last()
+        .map(|segment| segment.starts_with('{'))
+        .unwrap_or(false)
+    {
+        segments.pop();
+    }
+
+    match segments.join("/") {
+        base if base.is_empty() => "/".to_string(),
+        base => base,
+    }
+}
+
+// Create -> read -> update -> delete within a resource
+fn crud_phase(route: &RouteObject) -> u8 {
+    match (route.method, route.is_route_dynamic) {
+        (HttpMethod::Post, false) => 0,
+        (HttpMethod::Get, false) => 1,
+        (HttpMethod::Get, true) => 2,
+        (HttpMethod::Put | HttpMethod::Patch, _) => 3,
+        (HttpMethod::Delete, _) => 5,
+        _ => 4,
+    }
+}
+
+// Order routes so every resource is created before it is read, updated and deleted
+pub fn plan_crud_sequence(routes: &[RouteObject]) -> Vec<RouteObject> {
+    let mut bases = Vec::new();
+    for route in routes {
+        let base = resource_base(&route.route);
+        if !bases.contains(&base) {
+            bases.push(base);
+        }
+    }
+
+    let mut planned = routes.to_vec();
+    planned.sort_by_key(|route| {
+        let base = resource_base(&route.route);
+        let position = bases.iter().position(|known| known == &base);
+        (position, crud_phase(route))
+    });
+
+    planned
+}
+
+// Placeholder JSON value matching a schema
+pub fn sample_value(schema: &FieldSchema) -> Value {
+    match schema {
+        FieldSchema::None | FieldSchema::NotProvided => Value::Null,
+        FieldSchema::Number => Value::from(1),
+        FieldSchema::String => Value::from("test"),
+        FieldSchema::Bool => Value::from(true),
+        FieldSchema::Array(item) => Value::Array(vec![sample_value(item)]),
+        FieldSchema::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(name, field)| (name.clone(), sample_value(field)))
+                .collect(),
+        ),
+    }
+}
+
+fn value_to_path_segment(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(flag) => Some(flag.to_string()),
+        _ => None,
+    }
+}
+
+// Values seen so far for each resource, used to fill `{id}` style segments
+#[derive(Debug, Default)]
+pub struct CrudContext {
+    known: HashMap<String, Map<String, Value>>,
+}
+
+impl CrudContext {
+    // Remember the fields of a created resource, or of the first item of a listing
+    pub fn remember(&mut self, base: &str, value: &Value) {
+        let fields = match value {
+            Value::Object(fields) => fields,
+            Value::Array(items) => match items.first() {
+                Some(Value::Object(fields)) => fields,
+                _ => return,
+            },
+            _ => return,
+        };
+
+        let known = self.known.entry(base.to_string()).or_default();
+        for (name, field) in fields {
+            known.insert(name.clone(), field.clone());
+        }
+    }
+
+    pub fn knows(&self, base: &str) -> bool {
+        self.known.contains_key(base)
+    }
+
+    // Learn from a successful call: what a create returned (or sent), or a first listing
+    pub fn remember_success(
+        &mut self,
+        route: &RouteObject,
+        response: Option<&Value>,
+        sent: Option<&Value>,
+    ) {
+        let base = resource_base(&route.route);
+        let is_create = route.method == HttpMethod::Post && !route.is_route_dynamic;
+        let is_listing = route.method == HttpMethod::Get && !route.is_route_dynamic;
+
+        match (response, sent) {
+            (Some(created), _) if is_create => self.remember(&base, created),
+            (None, Some(sent)) if is_create => self.remember(&base, sent),
+            (Some(listed), _) if is_listing && !self.knows(&base) => self.remember(&base, listed),
+            _ => {}
+        }
+    }
+
+    fn path_value(&self, base: &str, segment: &str) -> Option<String> {
+        let own = self.known.get(base);
+
+        own.and_then(|fields| fields.get(segment))
+            .or_else(|| own.and_then(|fields| fields.get("id")))
+            .or_else(|| self.known.values().find_map(|fields| fields.get(segment)))
+            .and_then(value_to_path_segment)
+    }
+
+    // Known or placeholder values for the dynamic segments of a route, in order
+    pub fn path_values(&self, route: &RouteObject) -> Vec<String> {
+        let base = resource_base(&route.route);
+
+        dynamic_segments(&route.route)
+            .iter()
+            .map(|name| {
+                self.path_value(&base, name)
+                    .unwrap_or_else(|| "1".to_string())
+            })
+            .collect()
+    }
+
+    // Route with every dynamic segment replaced by a known or placeholder value
+    pub fn fill_path(&self, route: &RouteObject) -> String {
+        let base = resource_base(&route.route);
+
+        route
+            .route
+            .split('/')
+            .map(
+                |segment| match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+                    Some(inner) => {
+                        let name = inner.split(':').next().unwrap_or(inner).trim();
+                        self.path_value(&base, name)
+                            .unwrap_or_else(|| "1".to_string())
+                    }
+                    None => segment.to_string(),
+                },
+            )
+            .collect::<Vec<String>>()
+            .join("/")
+    }
+
+    // Body synthesized from the route schema, reusing known values when updating
+    pub fn request_body(&self, route: &RouteObject) -> Option<Value> {
+        if route.request_body.is_none() {
+            return None;
+        }
+
+        let mut body = sample_value(&route.request_body);
+
+        if route.method != HttpMethod::Post {
+            if let (Value::Object(fields), Some(known)) =
+                (&mut body, self.known.get(&resource_base(&route.route)))
+            {
+                for (name, field) in fields.iter_mut() {
+                    if let Some(known_value) = known.get(name) {
+                        *field = known_value.clone();
+                    }
+                }
+            }
+        }
+
+        Some(body)
+    }
+}
+
+const RESPONSE_EXCERPT_LEN: usize = 300;
+
+fn excerpt(text: &str, max_chars: usize) -> String {
+    match text.chars().count() > max_chars {
+        true => format!("{}...", text.chars().take(max_chars).collect::<String>()),
+        false => text.to_string(),
+    }
+}
+
+// reqwest only names the outer error, the cause (i.e. "connection reset by peer") sits in the sources
+fn error_chain(error: &dyn std::error::Error) -> String {
+    let mut chain = error.to_string();
+    let mut source = error.source();
+
+    while let Some(cause) = source {
+        chain.push_str(": ");
+        chain.push_str(&cause.to_string());
+        source = cause.source();
+    }
+
+    chain
+}
+
+// Call every route of the generated server in CRUD order and record the outcome
+pub async fn run_endpoint_tests(
+    client: &Client,
+    base_url: &str,
+    routes: &[RouteObject],
+    agent_position: &str,
+) -> anyhow::Result<Vec<EndpointTestResult>> {
+    let mut context = CrudContext::default();
+    let mut results = Vec::new();
+
+    for route in plan_crud_sequence(routes) {
+        let url = format!("{}{}", base_url, context.fill_path(&route));
+        let body = context.request_body(&route);
+
+        let testing_msg = format!("Testing endpoint {} '{}'...", route.method, url);
+        PrintCommand::UnitTest.print_agent_message(agent_position, &testing_msg)?;
+
+        let mut request = client.request(route.method.as_reqwest(), &url);
+        if let Some(body) = body.as_ref() {
+            request = request.json(body);
+        }
+
+        let result = match request.send().await {
+            Ok(response) => {
+                let status = response.status().as_u16();
+                let response_text = response.text().await.unwrap_or_default();
+                let response_body = serde_json::from_str::<Value>(&response_text).ok();
+                let is_success = (200..=299).contains(&status);
+
+                let contract_violations = match is_success {
+                    true => validate_response(&route.response, &response_text),
+                    false => Vec::new(),
+                };
+
+                if is_success {
+                    context.remember_success(&route, response_body.as_ref(), body.as_ref());
+                }
+
+                let message = if status >= 500 {
+                    format!(
+                        "Server error {}: {}",
+                        status,
+                        excerpt(&response_text, RESPONSE_EXCERPT_LEN)
+                    )
+                } else if !is_success {
+                    format!("Unexpected status code {}", status)
+                } else if !contract_violations.is_empty() {
+                    format!(
+                        "Response does not match the API schema in {} place(s)",
+                        contract_violations.len()
+                    )
+                } else {
+                    "OK".to_string()
+                };
+
+                EndpointTestResult {
+                    method: route.method,
+                    route: route.route.clone(),
+                    url,
+                    status: Some(status),
+                    passed: is_success && contract_violations.is_empty(),
+                    message,
+                    contract_violations,
+                }
+            }
+            Err(e) => EndpointTestResult {
+                method: route.method,
+                route: route.route.clone(),
+                url,
+                status: None,
+                passed: false,
+                message: format!("Request failed: {}", error_chain(&e)),
+                contract_violations: Vec::new(),
+            },
+        };
+
+        match result.passed {
+            true => {
+                let passed_msg = format!("PASSED {} '{}'", result.method, result.url);
+                PrintCommand::UnitTest.print_agent_message(agent_position, &passed_msg)?;
+            }
+            false => {
+                let failed_msg = format!(
+                    "FAILED {} '{}': {}",
+                    result.method, result.url, result.message
+                );
+                PrintCommand::Issue.print_agent_message(agent_position, &failed_msg)?;
+            }
+        }
+
+        results.push(result);
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    fn crud_routes() -> Vec<RouteObject> {
+        vec![
+            RouteObject::test(HttpMethod::Delete, "/item/{id}"),
+            RouteObject::test(HttpMethod::Get, "/item/{id}"),
+            RouteObject::test(HttpMethod::Get, "/health"),
+            RouteObject::test(HttpMethod::Put, "/item/{id}")
+                .with_body(json!({ "id": "number", "name": "string" })),
+            RouteObject::test(HttpMethod::Post, "/item").with_body(json!({ "name": "string" })),
+            RouteObject::test(HttpMethod::Get, "/item"),
+        ]
+    }
+
+    #[test]
+    fn test_plans_crud_sequence() {
+        let planned = plan_crud_sequence(&crud_routes())
+            .iter()
+            .map(|route| format!("{} {}", route.method, route.route))
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            planned,
+            vec![
+                "POST /item",
+                "GET /item",
+                "GET /item/{id}",
+                "PUT /item/{id}",
+                "DELETE /item/{id}",
+                "GET /health",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fills_path_and_body_from_known_values() {
+        let mut context = CrudContext::default();
+        let update = RouteObject::test(HttpMethod::Put, "/item/{id}")
+            .with_body(json!({ "id": "number", "name": "string" }));
+
+        assert_eq!(context.fill_path(&update), "/item/1");
+
+        context.remember("/item", &json!({ "id": 42, "name": "created" }));
+
+        assert_eq!(context.fill_path(&update), "/item/42");
+        assert_eq!(
+            context.request_body(&update),
+            Some(json!({ "id": 42, "name": "created" }))
+        );
+        assert_eq!(
+            resource_base("/users/{user_id}/posts/{id}"),
+            "/users/{user_id}/posts"
+        );
+    }
+
+    // Minimal HTTP server answering every request with the created item
+    async fn spawn_fake_server(requests: Arc<Mutex<Vec<String>>>) -> anyhow::Result<String> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buffer = vec![0; 4096];
+                let read = socket.read(&mut buffer).await.unwrap_or_default();
+                let request = String::from_utf8_lossy(&buffer[..read]).to_string();
+                let request_line = request.lines().next().unwrap_or_default().to_string();
+                requests.lock().unwrap().push(request_line);
+
+                let body = r#"{"id":7,"name":"created"}"#;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        Ok(format!("http://{}", address))
+    }
+
+    #[tokio::test]
+    async fn test_runs_crud_sequence_against_server() -> anyhow::Result<()> {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let base_url = spawn_fake_server(requests.clone()).await?;
+        let client = Client::new();
+
+        let results = run_endpoint_tests(&client, &base_url, &crud_routes(), "Tester").await?;
+
+        assert_eq!(results.len(), 6);
+        assert!(results.iter().all(|result| result.passed));
+        assert_eq!(
+            requests.lock().unwrap()[..5].to_vec(),
+            vec![
+                "POST /item HTTP/1.1",
+                "GET /item HTTP/1.1",
+                "GET /item/7 HTTP/1.1",
+                "PUT /item/7 HTTP/1.1",
+                "DELETE /item/7 HTTP/1.1",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_flags_contract_violations() -> anyhow::Result<()> {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let base_url = spawn_fake_server(requests).await?;
+        let client = Client::new();
+
+        let mut health = RouteObject::test(HttpMethod::Get, "/health");
+        health.response = FieldSchema::from_value(&json!({ "id": "string", "name": "string" }));
+
+        let results = run_endpoint_tests(&client, &base_url, &[health], "Tester").await?;
+
+        assert_eq!(results[0].status, Some(200));
+        assert!(!results[0].passed);
+        assert_eq!(results[0].contract_violations.len(), 1);
+        assert_eq!(results[0].contract_violations[0].path, "$.id");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,480 @@
+use crate::models::{agents::agent_traits::RouteObject, general::api_schema::HttpMethod};
+use reqwest::{header::ORIGIN, Client};
+use std::{path::PathBuf, str::FromStr};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+    task::JoinHandle,
+};
+
+// A request the frontend sends to the backend, with dynamic parts written as {param}
+#[derive(Debug, Clone, PartialEq)]
+pub struct FetchCall {
+    pub method: HttpMethod,
+    pub path: String,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FrontendCallReport {
+    pub loaded_files: Vec<String>,
+    pub fetch_calls: Vec<FetchCall>,
+    pub issues: Vec<String>,
+}
+
+impl FrontendCallReport {
+    // Bug description of everything that went wrong, None when the page works
+    pub fn failure_report(&self) -> Option<String> {
+        match self.issues.is_empty() {
+            true => None,
+            false => Some(format!(
+                "FRONTEND CALL CHECK FAILED:\n{}",
+                self.issues.join("\n")
+            )),
+        }
+    }
+}
+
+// Index of the parenthesis closing the one opened just before `start`
+fn closing_paren(code: &[char], start: usize) -> usize {
+    let mut depth = 1;
+    let mut quote: Option<char> = None;
+    let mut index = start;
+
+    while index < code.len() {
+        let current = code[index];
+        match quote {
+            Some(_) if current == '\\' => index += 1,
+            Some(open) if current == open => quote = None,
+            Some(_) => {}
+            None => match current {
+                '\'' | '"' | '`' => quote = Some(current),
+                '(' | '{' | '[' => depth += 1,
+                ')' | '}' | ']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return index;
+                    }
+                }
+                _ => {}
+            },
+        }
+        index += 1;
+    }
+
+    code.len()
+}
+
+// End of the first argument, i.e. the first top level comma
+fn first_argument_end(arguments: &[char]) -> usize {
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    let mut index = 0;
+
+    while index < arguments.len() {
+        let current = arguments[index];
+        match quote {
+            Some(_) if current == '\\' => index += 1,
+            Some(open) if current == open => quote = None,
+            Some(_) => {}
+            None => match current {
+                '\'' | '"' | '`' => quote = Some(current),
+                '(' | '{' | '[' => depth += 1,
+                ')' | '}' | ']' => depth -= 1,
+                ',' if depth == 0 => return index,
+                _ => {}
+            },
+        }
+        index += 1;
+    }
+
+    arguments.len()
+}
+
+// Append a dynamic value to the path, unless it is the base url in front of it
+fn push_dynamic(path: &mut String) {
+    if !path.is_empty() {
+        path.push_str("{param}");
+    }
+}
+
+// Turn `${API_BASE_URL}/item/${id}` or API_BASE_URL + "/item/" + id into /item/{param}
+// None when the url is built elsewhere, i.e. fetch(url)
+fn url_expression_to_path(expression: &[char]) -> Option<String> {
+    let mut path = String::new();
+    let mut has_literal = false;
+    let mut index = 0;
+    let mut in_expression = false;
+
+    while index < expression.len() {
+        let current = expression[index];
+        match current {
+            '\'' | '"' | '`' => {
+                in_expression = false;
+                has_literal = true;
+                index += 1;
+                while index < expression.len() && expression[index] != current {
+                    if current == '`'
+                        && expression[index] == '$'
+                        && expression.get(index + 1) == Some(&'{')
+                    {
+                        index = closing_paren(expression, index + 2);
+                        push_dynamic(&mut path);
+                    } else {
+                        path.push(expression[index]);
+                    }
+                    index += 1;
+                }
+            }
+            '+' => in_expression = false,
+            current if current.is_whitespace() => {}
+            _ => {
+                if !in_expression {
+                    push_dynamic(&mut path);
+                    in_expression = true;
+                }
+            }
+        }
+        index += 1;
+    }
+
+    if !has_literal {
+        return None;
+    }
+
+    // Drop the origin of absolute urls and any query string
+    let path = match path.split_once("://") {
+        Some((_, rest)) => rest.find('/').map(|slash| &rest[slash..]).unwrap_or("/"),
+        None => path.as_str(),
+    };
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+
+    match path.starts_with('/') {
+        true => Some(path.to_string()),
+        false => Some(format!("/{}", path)),
+    }
+}
+
+fn fetch_method(options: &str) -> HttpMethod {
+    let Some(start) = options.find("method") else {
+        return HttpMethod::Get;
+    };
+
+    options[start + "method".len()..]
+        .trim_start_matches(|c: char| c.is_whitespace() || c == ':' || c == '"' || c == '\'')
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .next()
+        .and_then(|method| HttpMethod::from_str(method).ok())
+        .unwrap_or(HttpMethod::Get)
+}
+
+// Every fetch(...) call in the page scripts
+pub fn extract_fetch_calls(code: &str) -> Vec<FetchCall> {
+    let chars = code.chars().collect::<Vec<char>>();
+    let pattern = "fetch(".chars().collect::<Vec<char>>();
+    let mut calls = Vec::new();
+    let mut index = 0;
+
+    while index + pattern.len() <= chars.len() {
+        let is_call = chars[index..index + pattern.len()] == pattern[..]
+            && (index == 0 || !(chars[index - 1].is_alphanumeric() || chars[index - 1] == '_'));
+        if !is_call {
+            index += 1;
+            continue;
+        }
+
+        let start = index + pattern.len();
+        let end = closing_paren(&chars, start);
+        let arguments = &chars[start..end];
+        let split = first_argument_end(arguments);
+
+        if let Some(path) = url_expression_to_path(&arguments[..split]) {
+            calls.push(FetchCall {
+                method: fetch_method(&arguments[split..].iter().collect::<String>()),
+                path,
+            });
+        }
+        index = end;
+    }
+
+    calls
+}
+
+fn is_dynamic_segment(segment: &str) -> bool {
+    segment.starts_with('{') && segment.ends_with('}')
+}
+
+// Whether the page path can reach the route, treating {..} on either side as a wildcard
+pub fn path_matches_route(path: &str, route: &str) -> bool {
+    let path_segments = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<&str>>();
+    let route_segments = route
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<&str>>();
+
+    path_segments.len() == route_segments.len()
+        && path_segments
+            .iter()
+            .zip(route_segments.iter())
+            .all(|(path_segment, route_segment)| {
+                path_segment == route_segment
+                    || is_dynamic_segment(path_segment)
+                    || is_dynamic_segment(route_segment)
+            })
+}
+
+// Local scripts referenced with <script src="...">
+pub fn local_script_sources(html: &str) -> Vec<String> {
+    html.split("<script")
+        .skip(1)
+        .filter_map(|tag| {
+            let tag = &tag[..tag.find('>').unwrap_or(tag.len())];
+            let start = tag.find("src=")? + "src=".len();
+            let quote = tag[start..].chars().next()?;
+            let value = tag[start + 1..].split(quote).next()?;
+            Some(value.to_string())
+        })
+        .filter(|source| !source.contains("://") && !source.starts_with("//"))
+        .collect()
+}
+
+fn content_type(path: &str) -> &'static str {
+    match path.rsplit('.').next().unwrap_or_default() {
+        "html" => "text/html; charset=utf-8",
+        "js" => "text/javascript; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "json" => "application/json",
+        _ => "application/octet-stream",
+    }
+}
+
+// Serve a directory of static files the way a browser would load them
+pub async fn serve_directory(directory: &str) -> anyhow::Result<(String, JoinHandle<()>)> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let address = listener.local_addr()?;
+    let root = PathBuf::from(directory);
+
+    let handle = tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let mut buffer = vec![0; 4096];
+            let read = socket.read(&mut buffer).await.unwrap_or_default();
+            let request = String::from_utf8_lossy(&buffer[..read]).to_string();
+            let path = request
+                .split_whitespace()
+                .nth(1)
+                .unwrap_or("/")
+                .split('?')
+                .next()
+                .unwrap_or("/")
+                .trim_start_matches('/')
+                .to_string();
+            let path = match path.is_empty() {
+                true => "index.html".to_string(),
+                false => path,
+            };
+
+            let file = match path.contains("..") {
+                true => None,
+                false => tokio::fs::read(root.join(&path)).await.ok(),
+            };
+
+            let (status, content_type, body) = match file {
+                Some(body) => ("200 OK", content_type(&path), body),
+                None => ("404 Not Found", "text/plain", b"not found".to_vec()),
+            };
+
+            let head = format!(
+                "HTTP/1.1 {}\r\ncontent-type: {}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+                status,
+                content_type,
+                body.len()
+            );
+            let _ = socket.write_all(head.as_bytes()).await;
+            let _ = socket.write_all(&body).await;
+        }
+    });
+
+    Ok((format!("http://{}", address), handle))
+}
+
+// Static check of the calls the page makes: index.html and its local scripts are fetched over HTTP
+// but never run, and the fetch(...) calls found in their source are matched against the routes.
+// Side effect free GETs are replayed against the live backend. Calls built at runtime, i.e.
+// fetch(url), and errors only a browser would hit are not caught.
+pub async fn run_frontend_call_check(
+    client: &Client,
+    frontend_url: &str,
+    backend_url: Option<&str>,
+    routes: &[RouteObject],
+) -> anyhow::Result<FrontendCallReport> {
+    let mut report = FrontendCallReport::default();
+
+    let page = client.get(format!("{}/", frontend_url)).send().await?;
+    if !page.status().is_success() {
+        report
+            .issues
+            .push(format!("index.html did not load ({})", page.status()));
+        return Ok(report);
+    }
+
+    let html = page.text().await?;
+    report.loaded_files.push("index.html".to_string());
+
+    if !html.to_lowercase().contains("<html") {
+        report
+            .issues
+            .push("index.html is not an HTML document".to_string());
+    }
+
+    let mut scripts = html.clone();
+    for source in local_script_sources(&html) {
+        let url = format!(
+            "{}/{}",
+            frontend_url,
+            source.trim_start_matches("./").trim_start_matches('/')
+        );
+        let response = client.get(&url).send().await?;
+
+        match response.status().is_success() {
+            true => {
+                scripts.push_str(&response.text().await?);
+                report.loaded_files.push(source);
+            }
+            false => report.issues.push(format!(
+                "script {} did not load ({})",
+                source,
+                response.status()
+            )),
+        }
+    }
+
+    report.fetch_calls = extract_fetch_calls(&scripts);
+    if report.fetch_calls.is_empty() && !routes.is_empty() {
+        report
+            .issues
+            .push("The page never calls the backend with fetch".to_string());
+    }
+
+    for call in report.fetch_calls.iter() {
+        let route = routes.iter().find(|route| {
+            route.method == call.method && path_matches_route(&call.path, &route.route)
+        });
+
+        let Some(route) = route else {
+            report.issues.push(format!(
+                "The page calls {} {}, which the backend does not serve",
+                call.method, call.path
+            ));
+            continue;
+        };
+
+        // Only side effect free calls are replayed against the live backend
+        let Some(backend_url) = backend_url else {
+            continue;
+        };
+        if call.method != HttpMethod::Get || route.is_route_dynamic {
+            continue;
+        }
+
+        let url = format!("{}{}", backend_url, call.path);
+        match client.get(&url).header(ORIGIN, frontend_url).send().await {
+            Ok(response) if response.status().is_server_error() => report.issues.push(format!(
+                "GET {} from the page failed with {}",
+                call.path,
+                response.status()
+            )),
+            Ok(response) if !response.headers().contains_key("access-control-allow-origin") => {
+                report.issues.push(format!(
+                    "GET {} from the page is blocked by CORS, no access-control-allow-origin header",
+                    call.path
+                ))
+            }
+            Ok(_) => {}
+            Err(e) => report
+                .issues
+                .push(format!("GET {} from the page failed: {}", call.path, e)),
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<head><script src="./app.js"></script></head>
+<body>
+<script>
+const API_BASE_URL = window.API_BASE_URL || "http://127.0.0.1:8080";
+async function load() {
+    const tasks = await fetch(`${API_BASE_URL}/task`).then((r) => r.json());
+    await fetch(API_BASE_URL + "/task/" + tasks[0].id, { method: "DELETE" });
+    await fetch(refreshUrl);
+    await fetch(`${API_BASE_URL}/task/${id}?full=true`, {
+        method: 'PUT',
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify({ name: "a, b" }),
+    });
+}
+</script>
+</body>
+</html>"#;
+
+    #[test]
+    fn test_extracts_fetch_calls() {
+        assert_eq!(
+            extract_fetch_calls(PAGE),
+            vec![
+                FetchCall {
+                    method: HttpMethod::Get,
+                    path: "/task".to_string()
+                },
+                FetchCall {
+                    method: HttpMethod::Delete,
+                    path: "/task/{param}".to_string()
+                },
+                FetchCall {
+                    method: HttpMethod::Put,
+                    path: "/task/{param}".to_string()
+                },
+            ]
+        );
+        assert_eq!(local_script_sources(PAGE), vec!["./app.js".to_string()]);
+        assert!(path_matches_route("/task/{param}", "/task/{id}"));
+        assert!(!path_matches_route("/task", "/task/{id}"));
+    }
+
+    #[tokio::test]
+    async fn test_checks_calls_of_served_page() -> anyhow::Result<()> {
+        let directory = std::env::temp_dir().join(format!("frontend_check_{}", std::process::id()));
+        std::fs::create_dir_all(&directory)?;
+        std::fs::write(directory.join("index.html"), PAGE)?;
+        std::fs::write(directory.join("app.js"), "fetch(`${API_BASE_URL}/health`);")?;
+
+        let (frontend_url, server) = serve_directory(&directory.to_string_lossy()).await?;
+        let client = Client::new();
+        let routes = vec![
+            RouteObject::test(HttpMethod::Get, "/task"),
+            RouteObject::test(HttpMethod::Delete, "/task/{id}"),
+            RouteObject::test(HttpMethod::Put, "/task/{id}"),
+        ];
+
+        let report = run_frontend_call_check(&client, &frontend_url, None, &routes).await?;
+        server.abort();
+        std::fs::remove_dir_all(&directory)?;
+
+        assert_eq!(report.loaded_files, vec!["index.html", "./app.js"]);
+        assert_eq!(report.fetch_calls.len(), 4);
+        assert_eq!(
+            report.issues,
+            vec!["The page calls GET /health, which the backend does not serve".to_string()]
+        );
+
+        Ok(())
+    }
+}
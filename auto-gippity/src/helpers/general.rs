@@ -1,13 +1,44 @@
-use super::command_line::PrintCommand;
-use crate::{apis::call_request::call_gpt, models::general::llm::Message};
+use super::{
+    client_sdk::{
+        client_operations, rust_client_code, rust_client_manifest, rust_smoke_code, smoke_calls,
+        typescript_client_code, typescript_smoke_code, TYPESCRIPT_CONFIG, TYPESCRIPT_PACKAGE,
+    },
+    command_line::PrintCommand,
+    dependencies::{
+        collect_used_crates, default_manifest, reconcile_manifest, CrateSpec, DependencyReport,
+    },
+    migrations::{migration_file_stem, migration_runner_code},
+    openapi::openapi_yaml,
+};
+use crate::{
+    apis::call_request::call_gpt,
+    models::{
+        agents::agent_traits::RouteObject,
+        general::{database_schema::DatabaseDesign, llm::Message},
+    },
+};
 use reqwest::Client;
 use serde::de::DeserializeOwned;
-use std::fs;
+use std::{fs, path::Path};
 
-const CODE_TEMPLATE_PATH: &str = "template/code_template.rs";
 const EXEC_MAIN_PATH: &str = "template/main.rs";
+const EXEC_MANIFEST_PATH: &str = "template/Cargo.toml";
+const EXEC_TESTS_PATH: &str = "template/main_tests.rs";
+const FRONTEND_MAIN_PATH: &str = "template/static/index.html";
+const MIGRATIONS_DIR_PATH: &str = "template/migrations";
+const MIGRATION_RUNNER_PATH: &str = "template/migrations.rs";
+const DATA_ACCESS_PATH: &str = "template/db.rs";
+const AUTH_PATH: &str = "template/auth.rs";
 const API_SCHEMA_PATH: &str = "schema/api_schema.json";
+const OPENAPI_JSON_PATH: &str = "template/openapi.json";
+const OPENAPI_YAML_PATH: &str = "template/openapi.yaml";
+pub const PROJECT_README_PATH: &str = "template/README.md";
 pub const WEB_SERVER_PROJECT_PATH: &str = "template";
+pub const FRONTEND_PROJECT_PATH: &str = "template/static";
+pub const RUST_CLIENT_PATH: &str = "template/clients/rust";
+pub const TYPESCRIPT_CLIENT_PATH: &str = "template/clients/typescript";
+// Shared with the server build so the client reuses its compiled dependencies
+pub const EXEC_TARGET_PATH: &str = "template/target";
 
 // Extend ai function to encourage specific output
 pub fn extend_ai_function(ai_func: fn(&str) -> &'static str, func_input: &str) -> Message {
@@ -73,8 +104,7 @@ pub async fn check_status_code(client: &Client, url: &str) -> anyhow::Result<u16
 }
 
 // Get Code Template
-pub fn read_code_template_contents() -> String {
-    let path = String::from(CODE_TEMPLATE_PATH);
+pub fn read_code_template_contents(path: &str) -> String {
     fs::read_to_string(path).expect("Failed to read code template")
 }
 
@@ -90,12 +120,170 @@ pub fn save_backend_code(backend_code: &str) {
     fs::write(path, backend_code).expect("Failed to write main.rs file");
 }
 
+// Module declaration pulling the generated tests into main.rs
+pub const GENERATED_TESTS_MODULE: &str =
+    "\n#[cfg(test)]\n#[path = \"main_tests.rs\"]\nmod generated_tests;\n";
+
+// Get Generated Tests, empty when none were written yet
+pub fn read_exec_tests_contents() -> String {
+    let path = String::from(EXEC_TESTS_PATH);
+    fs::read_to_string(path).unwrap_or_default()
+}
+
+// Save Generated Tests
+pub fn save_backend_tests(backend_tests: &str) {
+    let path = String::from(EXEC_TESTS_PATH);
+    fs::write(path, backend_tests).expect("Failed to write main_tests.rs file");
+    attach_backend_tests();
+}
+
+// Declare the generated tests in main.rs, which loses the declaration whenever it is rewritten
+pub fn attach_backend_tests() {
+    let main_code = read_exec_main_contents();
+    if !main_code.contains(GENERATED_TESTS_MODULE.trim()) {
+        save_backend_code(&format!("{}{}", main_code, GENERATED_TESTS_MODULE));
+    }
+}
+
+// Save Frontend Page
+pub fn save_frontend_code(frontend_code: &str) {
+    fs::create_dir_all(FRONTEND_PROJECT_PATH).expect("Failed to create static folder");
+    let path = String::from(FRONTEND_MAIN_PATH);
+    fs::write(path, frontend_code).expect("Failed to write index.html file");
+}
+
+// Save Migrations, their runner module and the data access module next to main.rs
+pub fn save_database_files(design: &DatabaseDesign) {
+    // Migrations from an earlier design must not linger
+    let _ = fs::remove_dir_all(MIGRATIONS_DIR_PATH);
+    fs::create_dir_all(MIGRATIONS_DIR_PATH).expect("Failed to create migrations folder");
+
+    for (index, migration) in design.migrations.iter().enumerate() {
+        let path = format!(
+            "{}/{}.sql",
+            MIGRATIONS_DIR_PATH,
+            migration_file_stem(index, migration)
+        );
+        fs::write(path, &migration.sql).expect("Failed to write migration file");
+    }
+
+    fs::write(
+        MIGRATION_RUNNER_PATH,
+        migration_runner_code(&design.migrations),
+    )
+    .expect("Failed to write migrations.rs file");
+    fs::write(DATA_ACCESS_PATH, &design.data_access_code).expect("Failed to write db.rs file");
+}
+
+// Save the auth module next to main.rs
+pub fn save_auth_code(auth_code: &str) {
+    fs::write(AUTH_PATH, auth_code).expect("Failed to write auth.rs file");
+}
+
+// Get Exec Manifest, falling back to a fresh one for new projects
+pub fn read_exec_manifest_contents() -> String {
+    let path = String::from(EXEC_MANIFEST_PATH);
+    fs::read_to_string(path).unwrap_or_else(|_| default_manifest())
+}
+
+// Save Exec Manifest
+pub fn save_exec_manifest(manifest: &str) {
+    let path = String::from(EXEC_MANIFEST_PATH);
+    fs::write(path, manifest).expect("Failed to write Cargo.toml file");
+}
+
+// Align the generated Cargo.toml with the crates used by the generated code
+pub fn update_exec_dependencies(allowed: &[CrateSpec]) -> anyhow::Result<DependencyReport> {
+    let main_code = read_exec_main_contents();
+    let mut used_crates = collect_used_crates(&main_code);
+    used_crates.extend(collect_used_crates(&read_exec_tests_contents()));
+
+    // Database and auth modules only count once main.rs declares them
+    for (module, path) in [
+        ("auth", AUTH_PATH),
+        ("migrations", MIGRATION_RUNNER_PATH),
+        ("db", DATA_ACCESS_PATH),
+    ] {
+        if main_code.contains(&format!("mod {};", module)) {
+            let module_code = fs::read_to_string(path).unwrap_or_default();
+            used_crates.extend(collect_used_crates(&module_code));
+        }
+    }
+    let (manifest, report) =
+        reconcile_manifest(&read_exec_manifest_contents(), &used_crates, allowed)?;
+    save_exec_manifest(&manifest);
+    Ok(report)
+}
+
 // Save JSON API Endpoint Schema
 pub fn save_api_endpoints(api_endpoints: &str) {
     let path = String::from(API_SCHEMA_PATH);
     fs::write(path, api_endpoints).expect("Failed to write api_schema.json file");
 }
 
+fn write_generated_files(dir: &str, files: &[(&str, String)]) -> anyhow::Result<()> {
+    for (name, contents) in files.iter() {
+        let path = Path::new(dir).join(name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, contents)?;
+    }
+    Ok(())
+}
+
+// Save the Rust and TypeScript clients generated from the endpoint schema
+pub fn save_client_sdks(routes: &[RouteObject]) -> anyhow::Result<()> {
+    let operations = client_operations(routes);
+    let calls = smoke_calls(routes, &operations);
+
+    // Files from an earlier schema must not linger
+    let _ = fs::remove_dir_all(Path::new(RUST_CLIENT_PATH).join("src"));
+    let _ = fs::remove_dir_all(Path::new(TYPESCRIPT_CLIENT_PATH).join("dist"));
+
+    write_generated_files(
+        RUST_CLIENT_PATH,
+        &[
+            ("Cargo.toml", rust_client_manifest()),
+            ("src/lib.rs", rust_client_code(&operations)),
+            ("src/bin/smoke.rs", rust_smoke_code(&calls)),
+        ],
+    )?;
+
+    // dist/ holds what tsc would emit, so the client runs on machines without it
+    write_generated_files(
+        TYPESCRIPT_CLIENT_PATH,
+        &[
+            ("package.json", TYPESCRIPT_PACKAGE.to_string()),
+            ("tsconfig.json", TYPESCRIPT_CONFIG.to_string()),
+            ("client.ts", typescript_client_code(&operations, true)),
+            ("smoke.ts", typescript_smoke_code(&calls, true)),
+            ("dist/client.js", typescript_client_code(&operations, false)),
+            ("dist/smoke.js", typescript_smoke_code(&calls, false)),
+        ],
+    )?;
+
+    Ok(())
+}
+
+// Save the README of the generated project
+pub fn save_project_readme(readme: &str) -> anyhow::Result<()> {
+    fs::write(PROJECT_README_PATH, readme)?;
+    Ok(())
+}
+
+// Save the container files at the root of the generated project
+pub fn save_deployment_files(files: &[(&str, String)]) -> anyhow::Result<()> {
+    write_generated_files(WEB_SERVER_PROJECT_PATH, files)
+}
+
+// Save the OpenAPI document next to the generated project, as JSON and YAML
+pub fn save_openapi_files(document: &serde_json::Value) -> anyhow::Result<()> {
+    fs::write(OPENAPI_JSON_PATH, serde_json::to_string_pretty(document)?)?;
+    fs::write(OPENAPI_YAML_PATH, openapi_yaml(document)?)?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
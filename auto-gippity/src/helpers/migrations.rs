@@ -0,0 +1,261 @@
+use crate::models::general::database_schema::{DatabaseDesign, Migration};
+use rusqlite::Connection;
+use std::{
+    collections::BTreeSet,
+    fs, process,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+// Table recording which migrations a database already ran
+pub const MIGRATIONS_TABLE: &str = "schema_migrations";
+
+// Crate the generated data access code talks to SQLite with
+pub const SQLITE_CRATE: &str = "rusqlite";
+
+// Name of a migration file, numbered in the order migrations run
+pub fn migration_file_stem(index: usize, migration: &Migration) -> String {
+    let name = migration
+        .name
+        .trim()
+        .trim_end_matches(".sql")
+        .trim_start_matches(|c: char| c.is_ascii_digit() || c == '_' || c == '-')
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() {
+            true => c.to_ascii_lowercase(),
+            false => '_',
+        })
+        .collect::<String>();
+
+    match name.is_empty() {
+        true => format!("{:04}_migration", index + 1),
+        false => format!("{:04}_{}", index + 1, name),
+    }
+}
+
+// Run every migration not recorded yet, returning the ones that ran
+pub fn apply_migrations(
+    conn: &mut Connection,
+    migrations: &[Migration],
+) -> anyhow::Result<Vec<String>> {
+    conn.execute_batch(&format!(
+        "CREATE TABLE IF NOT EXISTS {} (name TEXT PRIMARY KEY NOT NULL)",
+        MIGRATIONS_TABLE
+    ))?;
+
+    let mut applied = Vec::new();
+    for (index, migration) in migrations.iter().enumerate() {
+        let name = migration_file_stem(index, migration);
+        let already_applied = conn.query_row(
+            &format!("SELECT COUNT(*) FROM {} WHERE name = ?1", MIGRATIONS_TABLE),
+            [&name],
+            |row| row.get::<_, i64>(0),
+        )? > 0;
+
+        if already_applied {
+            continue;
+        }
+
+        let transaction = conn.transaction()?;
+        transaction
+            .execute_batch(&migration.sql)
+            .map_err(|e| anyhow::anyhow!("Migration {} failed: {}", name, e))?;
+        transaction.execute(
+            &format!("INSERT INTO {} (name) VALUES (?1)", MIGRATIONS_TABLE),
+            [&name],
+        )?;
+        transaction.commit()?;
+
+        applied.push(name);
+    }
+
+    Ok(applied)
+}
+
+fn table_columns(conn: &Connection, table: &str) -> anyhow::Result<BTreeSet<String>> {
+    let mut statement = conn.prepare("SELECT name FROM pragma_table_info(?1)")?;
+    let columns = statement
+        .query_map([table], |row| row.get::<_, String>(0))?
+        .collect::<Result<BTreeSet<String>, _>>()?;
+    Ok(columns)
+}
+
+// Problems found applying the migrations to an empty database, empty when they match the design
+pub fn check_migrations(conn: &mut Connection, design: &DatabaseDesign) -> Vec<String> {
+    let mut issues = Vec::new();
+
+    if let Err(e) = apply_migrations(conn, &design.migrations) {
+        issues.push(e.to_string());
+        return issues;
+    }
+
+    // Starting the server twice must not run anything again
+    match apply_migrations(conn, &design.migrations) {
+        Ok(applied) if !applied.is_empty() => issues.push(format!(
+            "Migrations ran twice on the same database: {}",
+            applied.join(", ")
+        )),
+        Ok(_) => {}
+        Err(e) => issues.push(format!("Re-running migrations failed: {}", e)),
+    }
+
+    for table in design.tables.iter() {
+        let columns = match table_columns(conn, &table.name) {
+            Ok(columns) => columns,
+            Err(e) => {
+                issues.push(format!("Could not inspect table {}: {}", table.name, e));
+                continue;
+            }
+        };
+
+        if columns.is_empty() {
+            issues.push(format!(
+                "Table {} is not created by the migrations",
+                table.name
+            ));
+            continue;
+        }
+
+        for column in table.columns.iter() {
+            if !columns.contains(&column.name) {
+                issues.push(format!(
+                    "Column {}.{} is not created by the migrations",
+                    table.name, column.name
+                ));
+            }
+        }
+    }
+
+    issues
+}
+
+// Apply the migrations to a throwaway database file and report what does not match the design
+pub fn verify_migrations_on_temp_db(design: &DatabaseDesign) -> anyhow::Result<Vec<String>> {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+    let path = std::env::temp_dir().join(format!(
+        "auto_gippity_migrations_{}_{}.db",
+        process::id(),
+        nanos
+    ));
+
+    let issues = {
+        let mut conn = Connection::open(&path)?;
+        check_migrations(&mut conn, design)
+    };
+
+    let _ = fs::remove_file(&path);
+
+    Ok(issues)
+}
+
+// Rust module embedding the migration files, so the server brings its database up to date on start
+pub fn migration_runner_code(migrations: &[Migration]) -> String {
+    let entries = migrations
+        .iter()
+        .enumerate()
+        .map(|(index, migration)| {
+            let stem = migration_file_stem(index, migration);
+            format!(
+                "    (\"{}\", include_str!(\"migrations/{}.sql\")),\n",
+                stem, stem
+            )
+        })
+        .collect::<String>();
+
+    format!(
+        r#"// Generated from the files in migrations/, do not edit by hand
+use {crate_name}::Connection;
+
+const MIGRATIONS: &[(&str, &str)] = &[
+{entries}];
+
+// Run every migration the database has not seen yet
+pub fn run_migrations(conn: &mut Connection) -> {crate_name}::Result<()> {{
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS {table} (name TEXT PRIMARY KEY NOT NULL)",
+    )?;
+
+    for (name, sql) in MIGRATIONS {{
+        let applied: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM {table} WHERE name = ?1",
+            [name],
+            |row| row.get(0),
+        )?;
+        if applied > 0 {{
+            continue;
+        }}
+
+        let transaction = conn.transaction()?;
+        transaction.execute_batch(sql)?;
+        transaction.execute("INSERT INTO {table} (name) VALUES (?1)", [name])?;
+        transaction.commit()?;
+    }}
+
+    Ok(())
+}}
+"#,
+        crate_name = SQLITE_CRATE,
+        entries = entries,
+        table = MIGRATIONS_TABLE
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::general::database_schema::{ColumnSchema, TableSchema};
+
+    fn column(name: &str, sql_type: &str) -> ColumnSchema {
+        ColumnSchema {
+            name: name.to_string(),
+            sql_type: sql_type.to_string(),
+            primary_key: name == "id",
+            nullable: false,
+            references: None,
+        }
+    }
+
+    fn design(sql: &str) -> DatabaseDesign {
+        DatabaseDesign {
+            tables: vec![TableSchema {
+                name: "tasks".to_string(),
+                columns: vec![column("id", "INTEGER"), column("name", "TEXT")],
+            }],
+            migrations: vec![Migration {
+                name: "0001_create tasks.sql".to_string(),
+                sql: sql.to_string(),
+            }],
+            data_access_code: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_names_migration_files() {
+        let migrations = &design("").migrations;
+        assert_eq!(migration_file_stem(0, &migrations[0]), "0001_create_tasks");
+
+        let code = migration_runner_code(migrations);
+        assert!(code.contains(
+            "(\"0001_create_tasks\", include_str!(\"migrations/0001_create_tasks.sql\")),"
+        ));
+        assert!(code.contains("pub fn run_migrations(conn: &mut Connection)"));
+    }
+
+    #[test]
+    fn test_verifies_migrations_on_temp_db() -> anyhow::Result<()> {
+        let valid = design("CREATE TABLE tasks (id INTEGER PRIMARY KEY, name TEXT NOT NULL);");
+        assert!(verify_migrations_on_temp_db(&valid)?.is_empty());
+
+        let missing_column = design("CREATE TABLE tasks (id INTEGER PRIMARY KEY);");
+        assert_eq!(
+            verify_migrations_on_temp_db(&missing_column)?,
+            vec!["Column tasks.name is not created by the migrations".to_string()]
+        );
+
+        let broken = design("CREATE TABLE tasks (id INTEGER PRIMARY KEY,);");
+        let issues = verify_migrations_on_temp_db(&broken)?;
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].starts_with("Migration 0001_create_tasks failed"));
+
+        Ok(())
+    }
+}
@@ -1,3 +1,22 @@
 // helpers
+pub mod acceptance_testing;
+pub mod auth_testing;
+pub mod cargo_testing;
+pub mod client_sdk;
+pub mod client_testing;
 pub mod command_line;
+pub mod contract_testing;
+pub mod dependencies;
+pub mod devops;
+pub mod docker_lint;
+pub mod endpoint_testing;
+pub mod frontend_check;
 pub mod general;
+pub mod migrations;
+pub mod openapi;
+pub mod project_readme;
+pub mod route_diff;
+pub mod route_parser;
+pub mod runtime_failures;
+pub mod security_scan;
+pub mod test_server;
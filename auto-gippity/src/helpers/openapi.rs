@@ -0,0 +1,373 @@
+use crate::models::{agents::agent_traits::RouteObject, general::api_schema::FieldSchema};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeSet;
+
+pub const OPENAPI_VERSION: &str = "3.1.0";
+
+const HTTP_METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+// JSON Schema of a body, None when the route sends or returns nothing
+pub fn json_schema(schema: &FieldSchema) -> Option<Value> {
+    let value = match schema {
+        FieldSchema::None => return None,
+        FieldSchema::NotProvided => json!({}),
+        FieldSchema::Number => json!({ "type": "number" }),
+        FieldSchema::String => json!({ "type": "string" }),
+        FieldSchema::Bool => json!({ "type": "boolean" }),
+        FieldSchema::Array(item) => json!({
+            "type": "array",
+            "items": json_schema(item).unwrap_or_else(|| json!({})),
+        }),
+        FieldSchema::Object(fields) => {
+            let properties = fields
+                .iter()
+                .filter_map(|(name, field)| Some((name.clone(), json_schema(field)?)))
+                .collect::<Map<String, Value>>();
+            let required = properties.keys().cloned().collect::<Vec<String>>();
+            json!({ "type": "object", "properties": properties, "required": required })
+        }
+    };
+
+    Some(value)
+}
+
+// Path template with its parameters, i.e. /item/{id:\d+} and /item/<id> both become /item/{id}
+pub fn openapi_path(route: &str) -> (String, Vec<(String, Option<String>)>) {
+    let mut parameters = Vec::new();
+
+    let path = route
+        .split('/')
+        .map(|segment| {
+            let inner = segment
+                .strip_prefix('{')
+                .and_then(|inner| inner.strip_suffix('}'))
+                .or_else(|| {
+                    segment
+                        .strip_prefix('<')
+                        .and_then(|inner| inner.strip_suffix('>'))
+                });
+
+            match inner {
+                Some(inner) => {
+                    let (name, pattern) = match inner.split_once(':') {
+                        Some((name, pattern)) => (name, Some(pattern.trim().to_string())),
+                        None => (inner, None),
+                    };
+                    let name = name.trim().trim_end_matches("..").to_string();
+                    parameters.push((name.clone(), pattern));
+                    format!("{{{}}}", name)
+                }
+                None => segment.to_string(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("/");
+
+    (path, parameters)
+}
+
+fn operation_id(route: &RouteObject, path: &str) -> String {
+    if let Some(handler) = route.handler.as_ref() {
+        return handler.clone();
+    }
+
+    let slug = path
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<&str>>()
+        .join("_");
+    format!("{}_{}", route.method.to_string().to_lowercase(), slug)
+}
+
+// One unique id per route, also used to name the generated client methods.
+// Handlers shared by several routes get a numbered suffix.
+pub fn operation_ids(routes: &[RouteObject]) -> Vec<String> {
+    let mut seen = BTreeSet::new();
+
+    routes
+        .iter()
+        .map(|route| {
+            let base_id = operation_id(route, &openapi_path(&route.route).0);
+            let mut operation_id = base_id.clone();
+            let mut suffix = 2;
+            while !seen.insert(operation_id.clone()) {
+                operation_id = format!("{}_{}", base_id, suffix);
+                suffix += 1;
+            }
+            operation_id
+        })
+        .collect()
+}
+
+// OpenAPI 3.1 document describing the generated backend
+pub fn openapi_document(title: &str, description: &str, routes: &[RouteObject]) -> Value {
+    let mut paths = Map::new();
+
+    for (route, operation_id) in routes.iter().zip(operation_ids(routes)) {
+        let (path, parameters) = openapi_path(&route.route);
+
+        let mut operation = Map::new();
+        operation.insert("operationId".to_string(), json!(operation_id));
+
+        if !parameters.is_empty() {
+            let parameters = parameters
+                .iter()
+                .map(|(name, pattern)| {
+                    let mut schema = json!({ "type": "string" });
+                    if let Some(pattern) = pattern {
+                        schema["pattern"] = json!(format!("^{}$", pattern));
+                    }
+                    json!({ "name": name, "in": "path", "required": true, "schema": schema })
+                })
+                .collect::<Vec<Value>>();
+            operation.insert("parameters".to_string(), json!(parameters));
+        }
+
+        if let Some(schema) = json_schema(&route.request_body) {
+            operation.insert(
+                "requestBody".to_string(),
+                json!({
+                    "required": true,
+                    "content": { "application/json": { "schema": schema } },
+                }),
+            );
+        }
+
+        let mut success = json!({ "description": "Successful response" });
+        if let Some(schema) = json_schema(&route.response) {
+            success["content"] = json!({ "application/json": { "schema": schema } });
+        }
+        operation.insert("responses".to_string(), json!({ "200": success }));
+
+        let path_item = paths
+            .entry(path)
+            .or_insert_with(|| Value::Object(Map::new()));
+        path_item[route.method.to_string().to_lowercase()] = Value::Object(operation);
+    }
+
+    json!({
+        "openapi": OPENAPI_VERSION,
+        "info": {
+            "title": title,
+            "description": description,
+            "version": "0.1.0",
+        },
+        "paths": paths,
+    })
+}
+
+fn validate_operation(location: &str, path: &str, operation: &Value, issues: &mut Vec<String>) {
+    let templated = openapi_path(path)
+        .1
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect::<BTreeSet<String>>();
+
+    let declared = operation["parameters"]
+        .as_array()
+        .map(|parameters| {
+            parameters
+                .iter()
+                .filter(|parameter| parameter["in"] == "path")
+                .map(|parameter| {
+                    let name = parameter["name"].as_str().unwrap_or_default().to_string();
+                    if parameter["required"] != true {
+                        issues.push(format!(
+                            "{}: path parameter {} must be required",
+                            location, name
+                        ));
+                    }
+                    name
+                })
+                .collect::<BTreeSet<String>>()
+        })
+        .unwrap_or_default();
+
+    for missing in templated.difference(&declared) {
+        issues.push(format!(
+            "{}: path parameter {} is not declared",
+            location, missing
+        ));
+    }
+    for extra in declared.difference(&templated) {
+        issues.push(format!(
+            "{}: parameter {} is not in the path",
+            location, extra
+        ));
+    }
+
+    match operation["responses"].as_object() {
+        Some(responses) if !responses.is_empty() => {
+            for (status, response) in responses.iter() {
+                let is_status = status == "default"
+                    || (status.len() == 3
+                        && status.chars().all(|c| c.is_ascii_digit() || c == 'X'));
+                if !is_status {
+                    issues.push(format!("{}: {} is not a response status", location, status));
+                }
+                if !response["description"].is_string() {
+                    issues.push(format!(
+                        "{}: response {} has no description",
+                        location, status
+                    ));
+                }
+            }
+        }
+        _ => issues.push(format!("{}: no responses", location)),
+    }
+}
+
+// Structural checks of the parts of OpenAPI 3.1 the document uses, empty when valid
+pub fn validate_openapi(document: &Value) -> Vec<String> {
+    let mut issues = Vec::new();
+
+    if !document["openapi"]
+        .as_str()
+        .is_some_and(|version| version.starts_with("3.1."))
+    {
+        issues.push("openapi must be a 3.1.x version".to_string());
+    }
+    for field in ["title", "version"] {
+        if !document["info"][field].is_string() {
+            issues.push(format!("info.{} is required", field));
+        }
+    }
+
+    let Some(paths) = document["paths"].as_object() else {
+        issues.push("paths must be an object".to_string());
+        return issues;
+    };
+
+    let mut operation_ids = BTreeSet::new();
+    let mut templates = BTreeSet::new();
+
+    for (path, item) in paths.iter() {
+        if !path.starts_with('/') {
+            issues.push(format!("path {} must start with /", path));
+        }
+
+        // /item/{id} and /item/{name} are the same path to OpenAPI
+        let (template, _) = openapi_path(path);
+        let shape = template
+            .split('/')
+            .map(|segment| match segment.starts_with('{') {
+                true => "{}",
+                false => segment,
+            })
+            .collect::<Vec<&str>>()
+            .join("/");
+        if !templates.insert(shape) {
+            issues.push(format!("path {} duplicates another templated path", path));
+        }
+
+        let Some(item) = item.as_object() else {
+            issues.push(format!("path {} must be an object", path));
+            continue;
+        };
+
+        for (method, operation) in item.iter() {
+            let location = format!("{} {}", method.to_uppercase(), path);
+            if !HTTP_METHODS.contains(&method.as_str()) {
+                issues.push(format!("{}: unknown http method", location));
+                continue;
+            }
+
+            if let Some(id) = operation["operationId"].as_str() {
+                if !operation_ids.insert(id.to_string()) {
+                    issues.push(format!("{}: operationId {} is not unique", location, id));
+                }
+            }
+
+            validate_operation(&location, path, operation, &mut issues);
+        }
+    }
+
+    issues
+}
+
+pub fn openapi_yaml(document: &Value) -> anyhow::Result<String> {
+    Ok(serde_yaml::to_string(document)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::general::api_schema::HttpMethod;
+
+    #[test]
+    fn test_builds_valid_document() -> anyhow::Result<()> {
+        let routes = vec![
+            RouteObject::test(HttpMethod::Post, "/task")
+                .with_handler("create_task")
+                .with_body(json!({ "name": "string", "done": "bool" }))
+                .with_response(json!({ "id": "number" })),
+            RouteObject::test(HttpMethod::Get, "/task/{id:\\d+}")
+                .with_handler("read_task")
+                .with_response(json!({ "id": "number", "tags": ["string"] })),
+            RouteObject::test(HttpMethod::Delete, "/task/<id>").with_response(Value::Null),
+        ];
+
+        let document = openapi_document("web_server", "Tracks tasks", &routes);
+        assert!(validate_openapi(&document).is_empty());
+
+        let read = &document["paths"]["/task/{id}"]["get"];
+        assert_eq!(read["operationId"], "read_task");
+        assert_eq!(read["parameters"][0]["schema"]["pattern"], "^\\d+$");
+        assert_eq!(
+            read["responses"]["200"]["content"]["application/json"]["schema"]["properties"]["tags"],
+            json!({ "type": "array", "items": { "type": "string" } })
+        );
+
+        let delete = &document["paths"]["/task/{id}"]["delete"];
+        assert_eq!(delete["operationId"], "delete_task_id");
+        assert!(delete["requestBody"].is_null());
+        assert!(delete["responses"]["200"]["content"].is_null());
+
+        let create = &document["paths"]["/task"]["post"]["requestBody"]["content"]
+            ["application/json"]["schema"];
+        assert_eq!(create["required"], json!(["done", "name"]));
+        assert_eq!(create["properties"]["done"]["type"], "boolean");
+
+        let yaml = openapi_yaml(&document)?;
+        assert!(yaml.contains("openapi: 3.1.0\n"));
+        assert_eq!(serde_yaml::from_str::<Value>(&yaml)?, document);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reports_invalid_document() {
+        let document = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "web_server" },
+            "paths": {
+                "/task/{id}": {
+                    "get": { "operationId": "read", "parameters": [{ "name": "key", "in": "path", "required": false }], "responses": { "ok": {} } },
+                    "fetch": {}
+                },
+                "/task/{name}": { "put": { "operationId": "read", "parameters": [{ "name": "name", "in": "path", "required": true }], "responses": {} } },
+                "task": {}
+            }
+        });
+
+        assert_eq!(
+            validate_openapi(&document),
+            vec![
+                "openapi must be a 3.1.x version",
+                "info.version is required",
+                "FETCH /task/{id}: unknown http method",
+                "GET /task/{id}: path parameter key must be required",
+                "GET /task/{id}: path parameter id is not declared",
+                "GET /task/{id}: parameter key is not in the path",
+                "GET /task/{id}: ok is not a response status",
+                "GET /task/{id}: response ok has no description",
+                "path /task/{name} duplicates another templated path",
+                "PUT /task/{name}: operationId read is not unique",
+                "PUT /task/{name}: no responses",
+                "path task must start with /",
+            ]
+        );
+    }
+}
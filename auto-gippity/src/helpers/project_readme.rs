@@ -0,0 +1,374 @@
+use super::{
+    auth_testing::{guarded_handlers, protected_routes},
+    endpoint_testing::{plan_crud_sequence, resource_base, CrudContext},
+    openapi::openapi_path,
+    test_server::{DEFAULT_SERVER_HOST, DEFAULT_SERVER_PORT, SERVER_HOST_ENV, SERVER_PORT_ENV},
+};
+use crate::models::{
+    agents::agent_traits::{FactSheet, RouteObject},
+    general::{
+        api_schema::{FieldSchema, HttpMethod},
+        auth_schema::AuthSetup,
+        project_docs::{CurlExample, COOKIE_JAR},
+    },
+};
+use serde_json::{json, Value};
+use std::{collections::BTreeSet, path::Path, process::Stdio, time::Duration};
+use tokio::process::Command;
+
+const CURL_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Account the documented auth examples sign up and log in with
+const DEMO_USERNAME: &str = "demo";
+const DEMO_PASSWORD: &str = "change-me-please";
+
+// Address the README's examples assume, the server's defaults
+pub fn documented_base_url() -> String {
+    format!("http://{}:{}", DEFAULT_SERVER_HOST, DEFAULT_SERVER_PORT)
+}
+
+// Routes in the order a reader would try them: sign up and log in, then create before reading,
+// updating and deleting, then check the session and log out
+pub fn plan_examples(routes: &[RouteObject], auth: Option<&AuthSetup>) -> Vec<RouteObject> {
+    let Some(auth) = auth else {
+        return plan_crud_sequence(routes);
+    };
+
+    let find = |route: &str| routes.iter().find(|known| known.route == route).cloned();
+    let app_routes = routes
+        .iter()
+        .filter(|route| !AuthSetup::is_auth_route(&route.route))
+        .cloned()
+        .collect::<Vec<RouteObject>>();
+
+    [find(&auth.signup_route), find(&auth.login_route)]
+        .into_iter()
+        .flatten()
+        .chain(plan_crud_sequence(&app_routes))
+        .chain(
+            [find(&auth.current_user_route), find(&auth.logout_route)]
+                .into_iter()
+                .flatten(),
+        )
+        .collect()
+}
+
+async fn run_curl(
+    example: &CurlExample,
+    base_url: &str,
+    work_dir: &Path,
+) -> (Option<u16>, String, Option<String>) {
+    let mut command = Command::new("curl");
+    command
+        .args(["-sS", "-w", "\n%{http_code}"])
+        .args(example.args(base_url))
+        .current_dir(work_dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    let output = match tokio::time::timeout(CURL_TIMEOUT, command.output()).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) => {
+            return (
+                None,
+                String::new(),
+                Some(format!("could not run curl: {}", e)),
+            )
+        }
+        Err(_) => return (None, String::new(), Some("curl timed out".to_string())),
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let (body, status) = stdout.rsplit_once('\n').unwrap_or(("", &stdout));
+    let status = status
+        .trim()
+        .parse::<u16>()
+        .ok()
+        .filter(|status| *status > 0);
+
+    match output.status.success() {
+        true => (status, body.to_string(), None),
+        false => (
+            status,
+            body.to_string(),
+            Some(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+        ),
+    }
+}
+
+// Run every example with curl, in order and sharing a cookie jar, the way the README shows them
+pub async fn run_curl_examples(
+    routes: &[RouteObject],
+    auth: Option<&AuthSetup>,
+    base_url: &str,
+    work_dir: &Path,
+) -> Vec<CurlExample> {
+    let mut context = CrudContext::default();
+    let mut examples = Vec::new();
+
+    for route in plan_examples(routes, auth) {
+        let is_login = auth.is_some_and(|auth| route.route == auth.login_route);
+        let is_signup = auth.is_some_and(|auth| route.route == auth.signup_route);
+
+        let body = match is_login || is_signup {
+            true => Some(json!({ "username": DEMO_USERNAME, "password": DEMO_PASSWORD })),
+            false => context.request_body(&route),
+        };
+
+        let mut example = CurlExample {
+            method: route.method,
+            route: route.route.clone(),
+            path: context.fill_path(&route),
+            body,
+            save_session: is_login,
+            send_session: auth.is_some() && !is_login && !is_signup,
+            status: None,
+            error: None,
+        };
+
+        let (status, response, error) = run_curl(&example, base_url, work_dir).await;
+        example.status = status;
+        example.error = error;
+
+        if example.passed() && matches!(route.method, HttpMethod::Post | HttpMethod::Get) {
+            if let Ok(value) = serde_json::from_str::<Value>(&response) {
+                context.remember(&resource_base(&route.route), &value);
+            }
+        }
+
+        examples.push(example);
+    }
+
+    examples
+}
+
+fn schema_cell(schema: &FieldSchema) -> String {
+    match schema {
+        FieldSchema::None => "-".to_string(),
+        schema => format!("`{}`", schema.to_value().to_string().replace('|', "\\|")),
+    }
+}
+
+// Markdown table of every endpoint, marking those that need a logged in user
+pub fn endpoint_table(routes: &[RouteObject], protected: &BTreeSet<String>) -> String {
+    let mut table =
+        "| Method | Path | Request body | Response | Login required |\n|---|---|---|---|---|\n"
+            .to_string();
+
+    for route in routes.iter() {
+        table.push_str(&format!(
+            "| {} | `{}` | {} | {} | {} |\n",
+            route.method,
+            openapi_path(&route.route).0,
+            schema_cell(&route.request_body),
+            schema_cell(&route.response),
+            match protected.contains(&route.route) {
+                true => "yes",
+                false => "no",
+            }
+        ));
+    }
+
+    table
+}
+
+// Routes that reject anonymous requests: guarded handlers, plus the session routes of the auth module
+fn protected_route_paths(factsheet: &FactSheet, routes: &[RouteObject]) -> BTreeSet<String> {
+    let Some(auth) = factsheet.auth_setup.as_ref() else {
+        return BTreeSet::new();
+    };
+
+    let guarded = guarded_handlers(
+        factsheet.backend_code.as_deref().unwrap_or_default(),
+        &auth.guard_type,
+    );
+    protected_routes(routes, &guarded)
+        .into_iter()
+        .map(|route| route.route.clone())
+        .chain([auth.current_user_route.clone(), auth.logout_route.clone()])
+        .collect()
+}
+
+// README of the generated project, showing only the examples that worked against the test server
+pub fn project_readme(factsheet: &FactSheet, title: &str, examples: &[CurlExample]) -> String {
+    let routes = factsheet.api_endpoint_schema.clone().unwrap_or_default();
+    let mut readme = format!(
+        "# {}\n\n{}\n\n## Run\n\n```sh\ncargo run\n```\n\nThe server listens on {}.\n",
+        title,
+        factsheet.project_description.trim(),
+        documented_base_url()
+    );
+    if factsheet.deployment.is_some() {
+        readme.push_str("To run it in a container, see [DEPLOYMENT.md](DEPLOYMENT.md).\n");
+    }
+
+    readme.push_str(&format!(
+        "\n## Environment\n\n| Variable | Default | Description |\n|---|---|---|\n\
+        | `{}` | `{}` | Address the server binds to |\n\
+        | `{}` | `{}` | Port the server listens on |\n",
+        SERVER_HOST_ENV, DEFAULT_SERVER_HOST, SERVER_PORT_ENV, DEFAULT_SERVER_PORT
+    ));
+    if let Some(urls) = factsheet
+        .external_urls
+        .as_ref()
+        .filter(|urls| !urls.is_empty())
+    {
+        readme.push_str(
+            "\nThe server calls these external APIs, so it needs outbound network access:\n\n",
+        );
+        for url in urls.iter() {
+            readme.push_str(&format!("- {}\n", url));
+        }
+    }
+
+    if !routes.is_empty() {
+        readme.push_str("\n## Endpoints\n\n");
+        readme.push_str(&endpoint_table(
+            &routes,
+            &protected_route_paths(factsheet, &routes),
+        ));
+        readme.push_str("\nThe full description is in `openapi.json` and `openapi.yaml`.\n");
+    }
+
+    let passed = examples
+        .iter()
+        .filter(|example| example.passed())
+        .collect::<Vec<&CurlExample>>();
+    if !passed.is_empty() {
+        readme.push_str(
+            "\n## Examples\n\nRun them in order, later examples use what earlier ones created.",
+        );
+        if factsheet.auth_setup.is_some() {
+            readme.push_str(&format!(
+                " Logging in stores the session in `{}`, which the other examples send.",
+                COOKIE_JAR
+            ));
+        }
+        readme.push('\n');
+        for example in passed {
+            readme.push_str(&format!(
+                "\n{} `{}`\n\n```sh\n{}\n```\n",
+                example.method,
+                example.route,
+                example.command(&documented_base_url())
+            ));
+        }
+    }
+
+    readme
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    fn routes() -> Vec<RouteObject> {
+        vec![
+            RouteObject::test(HttpMethod::Get, "/task/{id}")
+                .with_response(json!({ "id": "number", "name": "string" })),
+            RouteObject::test(HttpMethod::Delete, "/task/{id}").with_response(Value::Null),
+            RouteObject::test(HttpMethod::Post, "/task")
+                .with_body(json!({ "name": "string" }))
+                .with_response(json!({ "id": "number", "name": "string" })),
+        ]
+    }
+
+    #[test]
+    fn test_renders_readme() {
+        let routes = routes();
+        let factsheet = FactSheet {
+            project_description: "Tracks tasks.".to_string(),
+            api_endpoint_schema: Some(routes),
+            ..Default::default()
+        };
+        let mut examples = vec![CurlExample {
+            method: HttpMethod::Post,
+            route: "/task".to_string(),
+            path: "/task".to_string(),
+            body: Some(json!({ "name": "it's done" })),
+            save_session: false,
+            send_session: true,
+            status: Some(201),
+            error: None,
+        }];
+        examples.push(CurlExample {
+            route: "/task/{id}".to_string(),
+            path: "/task/7".to_string(),
+            body: None,
+            status: Some(500),
+            ..examples[0].clone()
+        });
+
+        let readme = project_readme(&factsheet, "web_server", &examples);
+        assert!(readme.starts_with("# web_server\n\nTracks tasks.\n\n## Run\n"));
+        assert!(readme.contains("| `PORT` | `8080` |"));
+        assert!(readme.contains(
+            "| POST | `/task` | `{\"name\":\"string\"}` | `{\"id\":\"number\",\"name\":\"string\"}` | no |\n"
+        ));
+        assert!(readme.contains("| DELETE | `/task/{id}` | - | - | no |\n"));
+        assert!(readme.contains(
+            "curl -X POST http://127.0.0.1:8080/task -H 'Content-Type: application/json' -d '{\"name\":\"it'\\''s done\"}' -b cookies.txt\n"
+        ));
+        // Only examples that worked are documented
+        assert!(!readme.contains("/task/7"));
+    }
+
+    #[tokio::test]
+    async fn test_runs_examples_with_curl() -> anyhow::Result<()> {
+        if std::process::Command::new("curl")
+            .arg("--version")
+            .output()
+            .is_err()
+        {
+            return Ok(());
+        }
+
+        // Stand in for the generated server: creates task 7, then answers every call
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let base_url = format!("http://{}", listener.local_addr()?);
+        let server = tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buffer = vec![0; 4096];
+                let read = stream.read(&mut buffer).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buffer[..read]).to_string();
+                let (status, body) = match request.lines().next().unwrap_or_default() {
+                    "POST /task HTTP/1.1" => ("201 Created", r#"{"id":7,"name":"test"}"#),
+                    "GET /task/7 HTTP/1.1" => ("200 OK", r#"{"id":7,"name":"test"}"#),
+                    _ => ("404 Not Found", "{}"),
+                };
+                let response = format!(
+                    "HTTP/1.1 {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let examples = run_curl_examples(&routes(), None, &base_url, &std::env::temp_dir()).await;
+        server.abort();
+
+        let outcome = examples
+            .iter()
+            .map(|example| format!("{} {} {:?}", example.method, example.path, example.status))
+            .collect::<Vec<String>>();
+        assert_eq!(
+            outcome,
+            vec![
+                "POST /task Some(201)",
+                "GET /task/7 Some(200)",
+                "DELETE /task/7 Some(404)"
+            ]
+        );
+        assert_eq!(examples[0].body, Some(json!({ "name": "test" })));
+        assert!(!examples[2].passed());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,227 @@
+use super::route_parser::route_shape;
+use crate::models::{
+    agents::agent_traits::RouteObject,
+    general::{
+        api_schema::{FieldSchema, HttpMethod},
+        auth_schema::AuthSetup,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RouteKey {
+    pub method: HttpMethod,
+    pub route: String,
+}
+
+impl fmt::Display for RouteKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.route)
+    }
+}
+
+impl From<&RouteObject> for RouteKey {
+    fn from(route: &RouteObject) -> Self {
+        Self {
+            method: route.method,
+            route: route.route.clone(),
+        }
+    }
+}
+
+// Planned route the code serves differently
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RouteMismatch {
+    pub planned: RouteKey,
+    pub difference: String,
+}
+
+// Where the implemented routes leave the architect's route plan
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RouteDiff {
+    pub missing: Vec<RouteKey>,
+    pub extra: Vec<RouteKey>,
+    pub mismatched: Vec<RouteMismatch>,
+}
+
+impl RouteDiff {
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty() && self.mismatched.is_empty()
+    }
+
+    // Differences for the fix loop, None when the code follows the plan
+    pub fn report(&self) -> Option<String> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let mut lines = vec!["ROUTES DO NOT MATCH THE ROUTE PLAN:".to_string()];
+        lines.extend(
+            self.missing
+                .iter()
+                .map(|route| format!("Missing: {} is planned but not implemented", route)),
+        );
+        lines.extend(
+            self.extra
+                .iter()
+                .map(|route| format!("Extra: {} is implemented but not planned", route)),
+        );
+        lines.extend(
+            self.mismatched.iter().map(|mismatch| {
+                format!("Mismatched: {}: {}", mismatch.planned, mismatch.difference)
+            }),
+        );
+        Some(lines.join("\n"))
+    }
+}
+
+fn field_names(schema: &FieldSchema) -> Option<Vec<&String>> {
+    match schema {
+        FieldSchema::Object(fields) => Some(fields.keys().collect()),
+        _ => None,
+    }
+}
+
+// Request bodies only count as different when both sides are known
+fn body_difference(planned: &FieldSchema, found: &FieldSchema) -> Option<String> {
+    match (field_names(planned), field_names(found)) {
+        (Some(planned), Some(found)) => {
+            let missing = planned
+                .iter()
+                .filter(|name| !found.contains(name))
+                .map(|name| name.as_str())
+                .collect::<Vec<&str>>();
+            let unexpected = found
+                .iter()
+                .filter(|name| !planned.contains(name))
+                .map(|name| name.as_str())
+                .collect::<Vec<&str>>();
+
+            let mut differences = Vec::new();
+            if !missing.is_empty() {
+                differences.push(format!("lacks {}", missing.join(", ")));
+            }
+            if !unexpected.is_empty() {
+                differences.push(format!("adds {}", unexpected.join(", ")));
+            }
+            match differences.is_empty() {
+                true => None,
+                false => Some(format!("request body {}", differences.join(" and "))),
+            }
+        }
+        (Some(_), None) if found.is_none() => Some("request body is not read".to_string()),
+        (None, Some(_)) if planned.is_none() => {
+            Some("request body is read but none is planned".to_string())
+        }
+        _ => None,
+    }
+}
+
+// Compare the routes found in the code with the plan. Auth routes come from the vetted module
+// and are left out, dynamic segments match whatever they are called.
+pub fn diff_routes(planned: &[RouteObject], implemented: &[RouteObject]) -> RouteDiff {
+    let is_app_route = |route: &&RouteObject| !AuthSetup::is_auth_route(&route.route);
+    let mut unmatched = implemented
+        .iter()
+        .filter(is_app_route)
+        .collect::<Vec<&RouteObject>>();
+    let mut diff = RouteDiff::default();
+
+    let mut unplaced = Vec::new();
+    for plan in planned.iter().filter(is_app_route) {
+        let shape = route_shape(&plan.route);
+        let found = unmatched
+            .iter()
+            .position(|route| route.method == plan.method && route_shape(&route.route) == shape);
+
+        match found {
+            Some(index) => {
+                let route = unmatched.remove(index);
+                if let Some(difference) = body_difference(&plan.request_body, &route.request_body) {
+                    diff.mismatched.push(RouteMismatch {
+                        planned: plan.into(),
+                        difference,
+                    });
+                }
+            }
+            None => unplaced.push(plan),
+        }
+    }
+
+    // The same path under another method is one mismatch, not a missing and an extra route
+    for plan in unplaced {
+        let shape = route_shape(&plan.route);
+        match unmatched
+            .iter()
+            .position(|route| route_shape(&route.route) == shape)
+        {
+            Some(index) => {
+                let route = unmatched.remove(index);
+                diff.mismatched.push(RouteMismatch {
+                    planned: plan.into(),
+                    difference: format!("implemented as {} {}", route.method, route.route),
+                });
+            }
+            None => diff.missing.push(plan.into()),
+        }
+    }
+
+    diff.extra = unmatched.into_iter().map(RouteKey::from).collect();
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diffs_routes_against_plan() {
+        let planned = [
+            RouteObject::test(HttpMethod::Post, "/task")
+                .with_body(json!({ "title": "string", "done": "bool" })),
+            RouteObject::test(HttpMethod::Get, "/task/{id}"),
+            RouteObject::test(HttpMethod::Put, "/task/{id}")
+                .with_body(json!({ "title": "string" })),
+            RouteObject::test(HttpMethod::Delete, "/task/{id}"),
+            RouteObject::test(HttpMethod::Post, "/auth/login").with_body(json!({})),
+        ];
+        let implemented = [
+            RouteObject::test(HttpMethod::Get, "/task/{task_id}"),
+            RouteObject::test(HttpMethod::Post, "/task/")
+                .with_body(json!({ "title": "string", "priority": "number" })),
+            RouteObject::test(HttpMethod::Patch, "/task/{id}")
+                .with_body(json!({ "title": "string" })),
+            RouteObject::test(HttpMethod::Get, "/crypto"),
+            RouteObject::test(HttpMethod::Get, "/auth/me"),
+        ];
+
+        let diff = diff_routes(&planned, &implemented);
+        let keys = |routes: &[RouteKey]| routes.iter().map(RouteKey::to_string).collect::<Vec<_>>();
+        assert_eq!(keys(&diff.missing), vec!["DELETE /task/{id}"]);
+        assert_eq!(keys(&diff.extra), vec!["GET /crypto"]);
+        assert_eq!(
+            diff.mismatched
+                .iter()
+                .map(|mismatch| format!("{}: {}", mismatch.planned, mismatch.difference))
+                .collect::<Vec<String>>(),
+            vec![
+                "POST /task: request body lacks done and adds priority",
+                "PUT /task/{id}: implemented as PATCH /task/{id}",
+            ]
+        );
+        assert!(diff
+            .report()
+            .is_some_and(|report| report.contains("Missing: DELETE /task/{id}")));
+
+        assert_eq!(
+            diff_routes(&planned[..2], &implemented[..1]).missing.len(),
+            1
+        );
+        assert_eq!(
+            diff_routes(&planned[1..2], &implemented[..1]).report(),
+            None
+        );
+    }
+}
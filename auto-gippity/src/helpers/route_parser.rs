@@ -0,0 +1,909 @@
+use crate::models::{
+    agents::agent_traits::RouteObject,
+    general::api_schema::{FieldSchema, HttpMethod},
+};
+use quote::ToTokens;
+use std::{collections::HashMap, str::FromStr};
+use syn::{
+    punctuated::Punctuated, visit::Visit, Expr, ExprMethodCall, Fields, FnArg, ItemFn, ItemStruct,
+    Lit, Meta, Token, Type,
+};
+
+// Route declared with an attribute macro such as #[get("/item/{id}")]
+#[derive(Debug, Clone)]
+struct AttributeRoute {
+    methods: Vec<HttpMethod>,
+    path: String,
+}
+
+#[derive(Default)]
+struct RouteCollector {
+    handlers: HashMap<String, ItemFn>,
+    structs: HashMap<String, ItemStruct>,
+    attribute_routes: HashMap<String, AttributeRoute>,
+    // (method, path, handler) in registration order
+    registered: Vec<(HttpMethod, String, Option<String>)>,
+    registered_services: Vec<(String, String)>,
+}
+
+impl<'ast> Visit<'ast> for RouteCollector {
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        let name = node.sig.ident.to_string();
+
+        if let Some(route) = parse_route_attribute(node) {
+            self.attribute_routes.insert(name.clone(), route);
+        }
+
+        self.handlers.insert(name, node.clone());
+        syn::visit::visit_item_fn(self, node);
+    }
+
+    fn visit_item_struct(&mut self, node: &'ast ItemStruct) {
+        self.structs.insert(node.ident.to_string(), node.clone());
+    }
+}
+
+// Read the methods and path out of #[get(..)], #[actix_web::post(..)], #[route(..)] or rocket's
+// #[post("/item", data = "<item>")]
+fn parse_route_attribute(item: &ItemFn) -> Option<AttributeRoute> {
+    for attr in item.attrs.iter() {
+        let Meta::List(list) = &attr.meta else {
+            continue;
+        };
+        let Some(name) = list.path.segments.last().map(|seg| seg.ident.to_string()) else {
+            continue;
+        };
+
+        // Other list attributes such as #[allow(dead_code)] carry no path
+        let Ok(args) = list.parse_args_with(Punctuated::<Expr, Token![,]>::parse_terminated) else {
+            continue;
+        };
+        let Some(path) = args.iter().find_map(string_literal) else {
+            continue;
+        };
+
+        if let Ok(method) = HttpMethod::from_str(&name) {
+            return Some(AttributeRoute {
+                methods: vec![method],
+                path,
+            });
+        }
+
+        if name == "route" {
+            let methods = args
+                .iter()
+                .filter_map(|arg| match arg {
+                    Expr::Assign(assign)
+                        if expr_ident(&assign.left).as_deref() == Some("method") =>
+                    {
+                        string_literal(&assign.right)
+                            .and_then(|method| HttpMethod::from_str(&method).ok())
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<HttpMethod>>();
+
+            return Some(AttributeRoute { methods, path });
+        }
+    }
+
+    None
+}
+
+fn string_literal(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(expr_lit) => match &expr_lit.lit {
+            Lit::Str(lit_str) => Some(lit_str.value()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// Last segment of a path expression, i.e. `handlers::read_item` -> `read_item`
+fn expr_ident(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Path(expr_path) => expr_path
+            .path
+            .segments
+            .last()
+            .map(|seg| seg.ident.to_string()),
+        _ => None,
+    }
+}
+
+// Split `root.a(..).b(..)` into `root` and the list of chained calls
+fn flatten_chain(expr: &Expr) -> (&Expr, Vec<&ExprMethodCall>) {
+    let mut calls = Vec::new();
+    let mut current = expr;
+
+    while let Expr::MethodCall(method_call) = current {
+        calls.push(method_call);
+        current = &method_call.receiver;
+    }
+
+    calls.reverse();
+    (current, calls)
+}
+
+// Name of the function called at the root of a chain, i.e. `web::scope("/api")` -> `scope`
+fn root_call(expr: &Expr) -> Option<(String, Vec<&Expr>)> {
+    match expr {
+        Expr::Call(call) => {
+            expr_ident(&call.func).map(|name| (name, call.args.iter().collect::<Vec<&Expr>>()))
+        }
+        _ => None,
+    }
+}
+
+fn join_paths(prefix: &str, path: &str) -> String {
+    let joined = format!(
+        "{}/{}",
+        prefix.trim_end_matches('/'),
+        path.trim_start_matches('/')
+    );
+
+    match joined.len() > 1 {
+        true => joined.trim_end_matches('/').to_string(),
+        false => joined,
+    }
+}
+
+impl RouteCollector {
+    // `web::get().to(handler)` or `web::method(Method::GET).to(handler)`
+    fn parse_route_target(&self, expr: &Expr) -> Option<(HttpMethod, Option<String>)> {
+        let (root, calls) = flatten_chain(expr);
+        let (name, args) = root_call(root)?;
+
+        let method = match name.as_str() {
+            "method" => {
+                HttpMethod::from_str(&args.first().and_then(|arg| expr_ident(arg))?).ok()?
+            }
+            _ => HttpMethod::from_str(&name).ok()?,
+        };
+
+        let handler = calls
+            .iter()
+            .find(|call| call.method == "to")
+            .and_then(|call| call.args.first())
+            .and_then(expr_ident);
+
+        Some((method, handler))
+    }
+
+    // Walk `.route(..)`, `.service(..)` and `.configure(..)` calls made on an app, scope or config
+    fn collect_calls(&mut self, calls: &[&ExprMethodCall], prefix: &str) {
+        for call in calls {
+            let args = call.args.iter().collect::<Vec<&Expr>>();
+
+            match (call.method.to_string().as_str(), args.as_slice()) {
+                ("route", [path, target]) => {
+                    if let (Some(path), Some((method, handler))) =
+                        (string_literal(path), self.parse_route_target(target))
+                    {
+                        self.registered
+                            .push((method, join_paths(prefix, &path), handler));
+                    }
+                }
+                ("service", [service]) => self.collect_service(service, prefix),
+                ("configure", [config]) => {
+                    if let Some(config_fn) = expr_ident(config) {
+                        self.collect_config_fn(&config_fn, prefix);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn collect_service(&mut self, service: &Expr, prefix: &str) {
+        let (root, calls) = flatten_chain(service);
+
+        match root_call(root) {
+            Some((name, args)) if name == "scope" => {
+                if let Some(scope_path) = args.first().and_then(|arg| string_literal(arg)) {
+                    self.collect_calls(&calls, &join_paths(prefix, &scope_path));
+                }
+            }
+            Some((name, args)) if name == "resource" => {
+                let Some(path) = args.first().and_then(|arg| string_literal(arg)) else {
+                    return;
+                };
+                let path = join_paths(prefix, &path);
+
+                for call in calls {
+                    let Some(arg) = call.args.first() else {
+                        continue;
+                    };
+
+                    if call.method == "route" {
+                        if let Some((method, handler)) = self.parse_route_target(arg) {
+                            self.registered.push((method, path.clone(), handler));
+                        }
+                    } else if call.method == "to" {
+                        self.registered
+                            .push((HttpMethod::Get, path.clone(), expr_ident(arg)));
+                    }
+                }
+            }
+            None if calls.is_empty() => {
+                // `.service(handler)` for a handler declared with an attribute macro
+                if let Some(handler) = expr_ident(root) {
+                    self.registered_services.push((prefix.to_string(), handler));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Follow `fn config(cfg: &mut web::ServiceConfig)` bodies passed to `.configure(..)`
+    fn collect_config_fn(&mut self, config_fn: &str, prefix: &str) {
+        let Some(item) = self.handlers.get(config_fn).cloned() else {
+            return;
+        };
+
+        for stmt in item.block.stmts.iter() {
+            if let syn::Stmt::Expr(expr, _) = stmt {
+                let (root, calls) = flatten_chain(expr);
+                if matches!(root, Expr::Path(_)) {
+                    self.collect_calls(&calls, prefix);
+                }
+            }
+        }
+    }
+
+    // Find `App::new()...` chains anywhere in the code
+    fn collect_apps(&mut self, file: &syn::File) {
+        for (_, app) in ChainFinder::find(file, ["App", "new"]).iter() {
+            let (_, calls) = flatten_chain(app);
+            self.collect_calls(&calls, "");
+        }
+    }
+
+    // axum's `get(list).post(create)` -> [(Get, list), (Post, create)]
+    fn method_router(expr: &Expr) -> Vec<(HttpMethod, Option<String>)> {
+        let (root, calls) = flatten_chain(expr);
+        let Some((name, args)) = root_call(root) else {
+            return Vec::new();
+        };
+
+        std::iter::once((name, args.first().and_then(|arg| expr_ident(arg))))
+            .chain(calls.iter().map(|call| {
+                (
+                    call.method.to_string(),
+                    call.args.first().and_then(expr_ident),
+                )
+            }))
+            .filter_map(|(name, handler)| Some((HttpMethod::from_str(&name).ok()?, handler)))
+            .collect()
+    }
+
+    // Walk `.route(..)`, `.nest(..)` and `.merge(..)` calls made on an axum Router
+    fn collect_router_calls(&mut self, calls: &[&ExprMethodCall], prefix: &str) {
+        for call in calls {
+            let args = call.args.iter().collect::<Vec<&Expr>>();
+
+            match (call.method.to_string().as_str(), args.as_slice()) {
+                ("route", [path, methods]) => {
+                    if let Some(path) = string_literal(path) {
+                        for (method, handler) in Self::method_router(methods) {
+                            self.registered
+                                .push((method, join_paths(prefix, &path), handler));
+                        }
+                    }
+                }
+                ("nest", [path, router]) => {
+                    if let Some(path) = string_literal(path) {
+                        self.collect_router(router, &join_paths(prefix, &path));
+                    }
+                }
+                ("merge", [router]) => self.collect_router(router, prefix),
+                _ => {}
+            }
+        }
+    }
+
+    // A router given inline as `Router::new()...` or built by a function in the same file
+    fn collect_router(&mut self, router: &Expr, prefix: &str) {
+        let (root, calls) = flatten_chain(router);
+
+        if let Some((name, _)) = root_call(root) {
+            let built_by = self.handlers.get(&name).cloned();
+            match (name.as_str(), built_by) {
+                ("new", _) => {}
+                (_, Some(item)) => {
+                    if let Some(syn::Stmt::Expr(tail, None)) = item.block.stmts.last() {
+                        self.collect_router(tail, prefix);
+                    }
+                }
+                _ => return,
+            }
+        }
+
+        self.collect_router_calls(&calls, prefix);
+    }
+
+    // Find `Router::new()...` chains, leaving out routers that are nested or merged into another
+    fn collect_routers(&mut self, file: &syn::File) {
+        let routers = ChainFinder::find(file, ["Router", "new"]);
+
+        let mut included = Vec::new();
+        for (_, router) in routers.iter() {
+            let (_, calls) = flatten_chain(router);
+            for call in calls {
+                if call.method == "nest" || call.method == "merge" {
+                    if let Some((name, _)) = call.args.last().and_then(root_call) {
+                        included.push(name);
+                    }
+                }
+            }
+        }
+
+        for (built_in, router) in routers.iter() {
+            if !included.contains(built_in) {
+                self.collect_router(router, "");
+            }
+        }
+    }
+
+    // Routes declared with attribute macros. They only count once registered, unless nothing
+    // registers them explicitly.
+    fn attribute_route_objects(&self) -> Vec<RouteObject> {
+        let mut attribute_handlers = self
+            .registered_services
+            .iter()
+            .filter(|(_, handler)| self.attribute_routes.contains_key(handler))
+            .cloned()
+            .collect::<Vec<(String, String)>>();
+
+        if attribute_handlers.is_empty() {
+            let mut names = self
+                .attribute_routes
+                .keys()
+                .cloned()
+                .collect::<Vec<String>>();
+            names.sort();
+            attribute_handlers = names
+                .into_iter()
+                .map(|handler| (String::new(), handler))
+                .collect();
+        }
+
+        let mut routes = Vec::new();
+        for (prefix, handler) in attribute_handlers {
+            let attribute_route = &self.attribute_routes[&handler];
+            let path = join_paths(&prefix, &attribute_route.path);
+
+            for method in attribute_route.methods.iter() {
+                routes.push(self.route_object(*method, &path, Some(handler.clone())));
+            }
+        }
+        routes
+    }
+
+    fn registered_route_objects(&self) -> Vec<RouteObject> {
+        self.registered
+            .iter()
+            .map(|(method, path, handler)| self.route_object(*method, path, handler.clone()))
+            .collect()
+    }
+
+    fn request_body(&self, extractors: &[Type]) -> FieldSchema {
+        extractors
+            .iter()
+            .find_map(|ty| generic_argument(ty, "Json"))
+            .map(|ty| self.type_schema(&ty))
+            .unwrap_or(FieldSchema::None)
+    }
+
+    // JSON shape of a Rust type, following structs declared in the same file
+    fn type_schema(&self, ty: &Type) -> FieldSchema {
+        let Type::Path(type_path) = ty else {
+            return FieldSchema::NotProvided;
+        };
+        let Some(last) = type_path.path.segments.last() else {
+            return FieldSchema::NotProvided;
+        };
+
+        let name = last.ident.to_string();
+
+        if name == "Vec" || name == "Option" || name == "Box" {
+            if let Some(inner) = generic_argument(ty, &name) {
+                let inner_schema = self.type_schema(&inner);
+                return match name.as_str() {
+                    "Vec" => FieldSchema::Array(Box::new(inner_schema)),
+                    _ => inner_schema,
+                };
+            }
+        }
+
+        match self.structs.get(&name).map(|item| &item.fields) {
+            Some(Fields::Named(fields)) => FieldSchema::Object(
+                fields
+                    .named
+                    .iter()
+                    .filter_map(|field| {
+                        let field_name = field.ident.as_ref()?.to_string();
+                        Some((field_name, self.type_schema(&field.ty)))
+                    })
+                    .collect(),
+            ),
+            _ => match FieldSchema::from_value(&serde_json::Value::String(name)) {
+                FieldSchema::Number => FieldSchema::Number,
+                FieldSchema::String => FieldSchema::String,
+                FieldSchema::Bool => FieldSchema::Bool,
+                _ => FieldSchema::NotProvided,
+            },
+        }
+    }
+
+    fn route_object(&self, method: HttpMethod, path: &str, handler: Option<String>) -> RouteObject {
+        let extractors = handler
+            .as_ref()
+            .and_then(|name| self.handlers.get(name))
+            .map(|item| {
+                item.sig
+                    .inputs
+                    .iter()
+                    .filter_map(|input| match input {
+                        FnArg::Typed(pat_type) => Some((*pat_type.ty).clone()),
+                        FnArg::Receiver(_) => None,
+                    })
+                    .collect::<Vec<Type>>()
+            })
+            .unwrap_or_default();
+
+        let path = braced_segments(path);
+        let dynamic_segments = dynamic_segments(&path);
+
+        RouteObject {
+            is_route_dynamic: !dynamic_segments.is_empty(),
+            method,
+            request_body: self.request_body(&extractors),
+            response: FieldSchema::NotProvided,
+            route: path,
+            handler,
+            dynamic_segments,
+            extractors: extractors
+                .iter()
+                .map(|ty| ty.to_token_stream().to_string().replace(' ', ""))
+                .collect(),
+        }
+    }
+}
+
+// Finds the method chains started by a constructor, i.e. `App::new()` or `Router::new()`,
+// along with the function they are built in
+struct ChainFinder {
+    constructor: [&'static str; 2],
+    current_fn: String,
+    chains: Vec<(String, Expr)>,
+}
+
+impl ChainFinder {
+    fn find(file: &syn::File, constructor: [&'static str; 2]) -> Vec<(String, Expr)> {
+        let mut finder = Self {
+            constructor,
+            current_fn: String::new(),
+            chains: Vec::new(),
+        };
+        finder.visit_file(file);
+        finder.chains
+    }
+}
+
+impl<'ast> Visit<'ast> for ChainFinder {
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        self.current_fn = node.sig.ident.to_string();
+        syn::visit::visit_item_fn(self, node);
+    }
+
+    fn visit_expr_method_call(&mut self, node: &'ast ExprMethodCall) {
+        let expr = Expr::MethodCall(node.clone());
+        let (root, _) = flatten_chain(&expr);
+
+        let is_constructed = match root {
+            Expr::Call(call) => match &*call.func {
+                Expr::Path(expr_path) => {
+                    let segments = expr_path
+                        .path
+                        .segments
+                        .iter()
+                        .map(|seg| seg.ident.to_string())
+                        .collect::<Vec<String>>();
+                    segments.ends_with(&self.constructor.map(String::from))
+                }
+                _ => false,
+            },
+            _ => false,
+        };
+
+        // Only the outermost call of a chain is kept, inner calls are part of it
+        if is_constructed {
+            self.chains.push((self.current_fn.clone(), expr));
+        } else {
+            syn::visit::visit_expr_method_call(self, node);
+        }
+    }
+}
+
+// Handlers listed in rocket's `.mount("/base", routes![a, b])`
+#[derive(Default)]
+struct MountFinder {
+    mounts: Vec<(String, String)>,
+}
+
+impl<'ast> Visit<'ast> for MountFinder {
+    fn visit_expr_method_call(&mut self, node: &'ast ExprMethodCall) {
+        if let (true, [base, Expr::Macro(mac)]) = (
+            node.method == "mount",
+            node.args.iter().collect::<Vec<&Expr>>().as_slice(),
+        ) {
+            let is_routes = mac
+                .mac
+                .path
+                .segments
+                .last()
+                .is_some_and(|seg| seg.ident == "routes");
+            let handlers = mac
+                .mac
+                .parse_body_with(Punctuated::<syn::Path, Token![,]>::parse_terminated);
+
+            if let (true, Some(base), Ok(handlers)) = (is_routes, string_literal(base), handlers) {
+                for handler in handlers.iter().filter_map(|path| path.segments.last()) {
+                    self.mounts.push((base.clone(), handler.ident.to_string()));
+                }
+            }
+        }
+
+        syn::visit::visit_expr_method_call(self, node);
+    }
+}
+
+// `web::Json<Task>` with wrapper `Json` -> `Task`
+fn generic_argument(ty: &Type, wrapper: &str) -> Option<Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let last = type_path.path.segments.last()?;
+    if last.ident != wrapper {
+        return None;
+    }
+
+    match &last.arguments {
+        syn::PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+            syn::GenericArgument::Type(inner) => Some(inner.clone()),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+// Names of the `{id}` style segments of a route, ignoring regex constraints like `{id:\d+}`
+pub fn dynamic_segments(route: &str) -> Vec<String> {
+    route
+        .split('/')
+        .filter_map(|segment| {
+            let inner = segment.strip_prefix('{')?.strip_suffix('}')?;
+            let name = inner.split(':').next().unwrap_or(inner).trim();
+            Some(name.to_string())
+        })
+        .collect()
+}
+
+// Write axum 0.7's `:id` / `*rest` and rocket's `<id>` / `<rest..>` segments as `{id}`
+fn braced_segments(route: &str) -> String {
+    route
+        .split('/')
+        .map(|segment| {
+            let name = segment
+                .strip_prefix(':')
+                .or_else(|| segment.strip_prefix('*'))
+                .or_else(|| {
+                    segment
+                        .strip_prefix('<')
+                        .and_then(|inner| inner.strip_suffix('>'))
+                        .map(|inner| inner.trim_end_matches(".."))
+                });
+            match name {
+                Some(name) if !name.is_empty() => format!("{{{}}}", name),
+                _ => segment.to_string(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("/")
+}
+
+// Route with its dynamic segments unnamed, so /task/{id} matches /task/{task_id}, /task/:id
+// and /task/<id>
+pub fn route_shape(route: &str) -> String {
+    route
+        .trim_end_matches('/')
+        .split('/')
+        .map(|segment| match segment.starts_with(['{', ':', '<']) {
+            true => "{}",
+            false => segment,
+        })
+        .collect::<Vec<&str>>()
+        .join("/")
+}
+
+// Derive the route table of actix-web code without asking the LLM.
+// Response shapes are left as "not_provided" for the LLM to fill in.
+pub fn extract_actix_routes(code: &str) -> anyhow::Result<Vec<RouteObject>> {
+    let file = syn::parse_file(code)?;
+
+    let mut collector = RouteCollector::default();
+    collector.visit_file(&file);
+    collector.collect_apps(&file);
+
+    let mut routes = collector.registered_route_objects();
+    routes.extend(collector.attribute_route_objects());
+
+    Ok(routes)
+}
+
+// Same for axum code, following `.nest(..)` and `.merge(..)` into routers built in the same file
+pub fn extract_axum_routes(code: &str) -> anyhow::Result<Vec<RouteObject>> {
+    let file = syn::parse_file(code)?;
+
+    let mut collector = RouteCollector::default();
+    collector.visit_file(&file);
+    collector.collect_routers(&file);
+
+    Ok(collector.registered_route_objects())
+}
+
+// Same for rocket code, where attribute routes count once mounted with `routes![..]`
+pub fn extract_rocket_routes(code: &str) -> anyhow::Result<Vec<RouteObject>> {
+    let file = syn::parse_file(code)?;
+
+    let mut collector = RouteCollector::default();
+    collector.visit_file(&file);
+
+    let mut mounts = MountFinder::default();
+    mounts.visit_file(&file);
+    collector.registered_services = mounts.mounts;
+
+    Ok(collector.attribute_route_objects())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACTIX_CODE: &str = r#"
+        use actix_web::{get, web, App, HttpResponse, HttpServer, Responder};
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Serialize, Deserialize)]
+        struct Item {
+            id: u64,
+            name: String,
+            tags: Vec<String>,
+            done: Option<bool>,
+        }
+
+        async fn create_item(data: web::Data<AppState>, item: web::Json<Item>) -> impl Responder {
+            HttpResponse::Ok().finish()
+        }
+
+        async fn read_item(data: web::Data<AppState>, id: web::Path<u64>) -> impl Responder {
+            HttpResponse::Ok().finish()
+        }
+
+        async fn health() -> impl Responder {
+            HttpResponse::Ok().finish()
+        }
+
+        #[get("/prices/{symbol:[A-Z]+}")]
+        async fn prices(symbol: web::Path<String>) -> impl Responder {
+            HttpResponse::Ok().finish()
+        }
+
+        fn user_routes(cfg: &mut web::ServiceConfig) {
+            cfg.route("/users", web::get().to(health));
+        }
+
+        #[actix_web::main]
+        async fn main() -> std::io::Result<()> {
+            HttpServer::new(move || {
+                App::new()
+                    .route("/item", web::post().to(create_item))
+                    .route("/item/{id}", web::get().to(read_item))
+                    .service(web::resource("/health").route(web::head().to(health)))
+                    .service(
+                        web::scope("/api")
+                            .service(prices)
+                            .configure(user_routes),
+                    )
+            })
+            .bind("127.0.0.1:8080")?
+            .run()
+            .await
+        }
+    "#;
+
+    fn find<'a>(routes: &'a [RouteObject], method: HttpMethod, route: &str) -> &'a RouteObject {
+        routes
+            .iter()
+            .find(|r| r.method == method && r.route == route)
+            .unwrap_or_else(|| panic!("Missing route {} {}", method, route))
+    }
+
+    #[test]
+    fn test_extracts_actix_routes() -> anyhow::Result<()> {
+        let routes = extract_actix_routes(ACTIX_CODE)?;
+        assert_eq!(routes.len(), 5);
+
+        let create = find(&routes, HttpMethod::Post, "/item");
+        assert!(!create.is_route_dynamic);
+        assert_eq!(create.handler.as_deref(), Some("create_item"));
+        assert_eq!(
+            create.extractors,
+            vec!["web::Data<AppState>", "web::Json<Item>"]
+        );
+        assert_eq!(
+            create.request_body.to_value(),
+            serde_json::json!({
+                "id": "number",
+                "name": "string",
+                "tags": ["string"],
+                "done": "bool"
+            })
+        );
+        assert_eq!(create.response, FieldSchema::NotProvided);
+
+        let read = find(&routes, HttpMethod::Get, "/item/{id}");
+        assert!(read.is_route_dynamic);
+        assert_eq!(read.dynamic_segments, vec!["id"]);
+        assert!(read.request_body.is_none());
+
+        find(&routes, HttpMethod::Head, "/health");
+        find(&routes, HttpMethod::Get, "/api/users");
+
+        let prices = find(&routes, HttpMethod::Get, "/api/prices/{symbol:[A-Z]+}");
+        assert_eq!(prices.dynamic_segments, vec!["symbol"]);
+        assert_eq!(prices.handler.as_deref(), Some("prices"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_extracts_routes_from_code_template() -> anyhow::Result<()> {
+        let code = std::fs::read_to_string("template/frameworks/actix_web.rs")?;
+        let routes = extract_actix_routes(&code)?;
+
+        assert_eq!(routes.len(), 5);
+        let update = find(&routes, HttpMethod::Put, "/task/{id}");
+        assert_eq!(update.request_body.to_value()["name"], "string");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_extracts_axum_routes() -> anyhow::Result<()> {
+        let code = r#"
+            #[derive(Deserialize)]
+            struct NewUser {
+                name: String,
+            }
+
+            async fn create_user(State(state): State<AppState>, Json(user): Json<NewUser>) {}
+            async fn read_user(Path(id): Path<u64>) {}
+            async fn health() {}
+
+            fn user_routes() -> Router<AppState> {
+                Router::new()
+                    .route("/", post(create_user))
+                    .route("/:id", get(read_user).delete(read_user))
+            }
+
+            #[tokio::main]
+            async fn main() {
+                let app = Router::new()
+                    .route("/health", get(health))
+                    .nest("/users", user_routes())
+                    .merge(Router::new().route("/ping", head(health)))
+                    .layer(CorsLayer::permissive())
+                    .with_state(state);
+            }
+        "#;
+
+        let routes = extract_axum_routes(code)?;
+        assert_eq!(routes.len(), 5);
+        find(&routes, HttpMethod::Get, "/health");
+        find(&routes, HttpMethod::Head, "/ping");
+        assert_eq!(
+            find(&routes, HttpMethod::Post, "/users")
+                .request_body
+                .to_value(),
+            serde_json::json!({ "name": "string" })
+        );
+        let read = find(&routes, HttpMethod::Get, "/users/{id}");
+        assert_eq!(read.dynamic_segments, vec!["id"]);
+        find(&routes, HttpMethod::Delete, "/users/{id}");
+
+        let template = std::fs::read_to_string("template/frameworks/axum.rs")?;
+        let routes = extract_axum_routes(&template)?;
+        assert_eq!(routes.len(), 5);
+        assert_eq!(
+            find(&routes, HttpMethod::Put, "/task/{id}")
+                .handler
+                .as_deref(),
+            Some("update_task")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_extracts_rocket_routes() -> anyhow::Result<()> {
+        let code = r#"
+            #[get("/files/<path..>")]
+            fn files(path: PathBuf) {}
+
+            #[post("/", data = "<item>")]
+            fn create_item(item: Json<Item>) {}
+
+            #[get("/unmounted")]
+            fn unmounted() {}
+
+            #[catch(404)]
+            fn not_found() {}
+
+            #[launch]
+            fn rocket() -> _ {
+                rocket::build()
+                    .mount("/", routes![files])
+                    .mount("/item", routes![create_item])
+                    .register("/", catchers![not_found])
+            }
+        "#;
+
+        let routes = extract_rocket_routes(code)?;
+        assert_eq!(routes.len(), 2);
+        assert_eq!(
+            find(&routes, HttpMethod::Get, "/files/{path}").dynamic_segments,
+            vec!["path"]
+        );
+        assert_eq!(
+            find(&routes, HttpMethod::Post, "/item").extractors,
+            vec!["Json<Item>"]
+        );
+
+        let template = std::fs::read_to_string("template/frameworks/rocket.rs")?;
+        let routes = extract_rocket_routes(&template)?;
+        assert_eq!(routes.len(), 5);
+        let update = find(&routes, HttpMethod::Put, "/task/{_id}");
+        assert_eq!(update.request_body.to_value()["name"], "string");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_skips_other_attributes_before_route_attribute() -> anyhow::Result<()> {
+        let code = r#"
+            #[allow(dead_code)]
+            #[doc(hidden)]
+            #[get("/x")]
+            async fn read_x() -> impl Responder {
+                HttpResponse::Ok().finish()
+            }
+        "#;
+
+        let routes = extract_actix_routes(code)?;
+        assert_eq!(routes.len(), 1);
+        assert_eq!(
+            find(&routes, HttpMethod::Get, "/x").handler.as_deref(),
+            Some("read_x")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rejects_unparsable_code() {
+        assert!(extract_actix_routes("fn main( {").is_err());
+    }
+}
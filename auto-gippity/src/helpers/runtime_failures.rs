@@ -0,0 +1,123 @@
+use crate::models::agents::agent_traits::EndpointTestResult;
+
+// Lines of context kept after each panic message, enough for the note and a short backtrace
+const PANIC_CONTEXT_LINES: usize = 4;
+
+// Panic messages the server printed, each with the lines that follow it
+pub fn find_panics(stderr: &str) -> Vec<String> {
+    let lines = stderr.lines().collect::<Vec<&str>>();
+
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.contains("panicked at") || line.contains("' panicked"))
+        .map(|(index, _)| {
+            lines[index..]
+                .iter()
+                .take(PANIC_CONTEXT_LINES + 1)
+                .cloned()
+                .collect::<Vec<&str>>()
+                .join("\n")
+        })
+        .collect()
+}
+
+// Failures caused by the server itself rather than by the data sent to it
+pub fn is_runtime_failure(result: &EndpointTestResult) -> bool {
+    match result.status {
+        Some(status) => status >= 500,
+        None => !result.passed,
+    }
+}
+
+// Evidence for every runtime failure seen while testing, None when the server behaved
+pub fn runtime_failure_report(
+    results: &[EndpointTestResult],
+    stderr: &str,
+    exit_status: Option<&str>,
+) -> Option<String> {
+    let mut sections = Vec::new();
+
+    if let Some(status) = exit_status {
+        sections.push(format!(
+            "The server stopped while being tested ({})",
+            status
+        ));
+    }
+
+    let failed_requests = results
+        .iter()
+        .filter(|result| is_runtime_failure(result))
+        .map(|result| format!("{} {}: {}", result.method, result.url, result.message))
+        .collect::<Vec<String>>();
+
+    if !failed_requests.is_empty() {
+        sections.push(format!("FAILED REQUESTS:\n{}", failed_requests.join("\n")));
+    }
+
+    let panics = find_panics(stderr);
+    if !panics.is_empty() {
+        sections.push(format!("PANICS IN SERVER STDERR:\n{}", panics.join("\n\n")));
+    }
+
+    match sections.is_empty() {
+        true => None,
+        false => Some(format!(
+            "RUNTIME FAILURES: the server compiled but failed while handling requests\n{}",
+            sections.join("\n")
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::general::api_schema::HttpMethod;
+
+    fn result(status: Option<u16>, passed: bool, message: &str) -> EndpointTestResult {
+        EndpointTestResult {
+            method: HttpMethod::Get,
+            route: "/item/{id}".to_string(),
+            url: "http://127.0.0.1:8080/item/1".to_string(),
+            status,
+            passed,
+            message: message.to_string(),
+            contract_violations: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_finds_panics_with_context() {
+        let stderr = "   Compiling web_server\n\
+            thread 'actix-rt|system:0|arbiter:0' panicked at main.rs:42:10:\n\
+            called `Option::unwrap()` on a `None` value\n\
+            note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace\n";
+
+        let panics = find_panics(stderr);
+        assert_eq!(panics.len(), 1);
+        assert!(panics[0].starts_with("thread 'actix-rt"));
+        assert!(panics[0].contains("on a `None` value"));
+        assert!(find_panics("   Compiling web_server\n    Finished").is_empty());
+    }
+
+    #[test]
+    fn test_reports_only_runtime_failures() {
+        let results = vec![
+            result(Some(200), true, "OK"),
+            result(Some(404), false, "Unexpected status code 404"),
+        ];
+        assert_eq!(runtime_failure_report(&results, "", None), None);
+
+        let results = vec![
+            result(Some(500), false, "Server error 500: database locked"),
+            result(None, false, "Request failed: connection reset by peer"),
+        ];
+        let report =
+            runtime_failure_report(&results, "", Some("exit status: 101")).unwrap_or_default();
+
+        assert!(report.contains("stopped while being tested (exit status: 101)"));
+        assert!(report.contains("Server error 500: database locked"));
+        assert!(report.contains("connection reset by peer"));
+        assert!(!report.contains("404"));
+    }
+}
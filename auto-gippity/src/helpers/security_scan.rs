@@ -0,0 +1,385 @@
+use crate::models::general::security_review::{FindingSource, SecurityFinding, Severity};
+use proc_macro2::Span;
+use reqwest::Url;
+use std::collections::HashMap;
+use syn::{
+    spanned::Spanned, visit::Visit, Expr, ExprCall, ExprLit, ExprUnsafe, ItemConst, ItemFn,
+    ItemImpl, ItemStatic, Lit, LitStr, Local, Pat,
+};
+
+// Calls writing to or deleting from the filesystem, matched on the last two path segments
+const FILESYSTEM_WRITES: &[(&str, &str)] = &[
+    ("fs", "write"),
+    ("fs", "remove_file"),
+    ("fs", "remove_dir"),
+    ("fs", "remove_dir_all"),
+    ("fs", "create_dir"),
+    ("fs", "create_dir_all"),
+    ("fs", "rename"),
+    ("fs", "copy"),
+    ("File", "create"),
+    ("OpenOptions", "new"),
+];
+
+// Prefixes of well known API keys and private keys
+const SECRET_PREFIXES: &[&str] = &[
+    "sk-",
+    "sk_live_",
+    "AKIA",
+    "ghp_",
+    "github_pat_",
+    "xoxb-",
+    "AIza",
+    "-----BEGIN",
+];
+
+// Variable names that suggest a credential when bound to a string literal
+const SECRET_NAMES: &[&str] = &["secret", "api_key", "apikey", "token", "password", "passwd"];
+
+const LOCAL_HOSTS: &[&str] = &["localhost", "127.0.0.1", "0.0.0.0", "[::1]"];
+
+struct SecurityCollector<'a> {
+    file: &'a str,
+    allowed_hosts: Vec<String>,
+    // String constants, so fs::write(DB_PATH, ..) resolves to its path
+    constants: HashMap<String, String>,
+    findings: Vec<SecurityFinding>,
+}
+
+impl SecurityCollector<'_> {
+    fn push(&mut self, severity: Severity, category: &str, span: Span, message: String) {
+        self.findings.push(SecurityFinding {
+            severity,
+            category: category.to_string(),
+            file: self.file.to_string(),
+            line: Some(span.start().line),
+            message,
+            source: FindingSource::Static,
+        });
+    }
+
+    fn literal_path(&self, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(lit), ..
+            }) => Some(lit.value()),
+            Expr::Path(path) => path
+                .path
+                .get_ident()
+                .and_then(|ident| self.constants.get(&ident.to_string()).cloned()),
+            Expr::Reference(reference) => self.literal_path(&reference.expr),
+            _ => None,
+        }
+    }
+
+    fn check_filesystem_write(&mut self, call: &ExprCall, function: &str) {
+        if function == "OpenOptions::new" {
+            self.push(
+                Severity::Low,
+                "filesystem",
+                call.span(),
+                "Opens files with OpenOptions, check the path stays inside the project".to_string(),
+            );
+            return;
+        }
+
+        let paths = call
+            .args
+            .iter()
+            .take(2)
+            .map(|arg| self.literal_path(arg))
+            .collect::<Vec<Option<String>>>();
+
+        match paths.iter().flatten().find(|path| is_outside_project(path)) {
+            Some(path) => self.push(
+                Severity::High,
+                "filesystem",
+                call.span(),
+                format!(
+                    "{} touches '{}', outside the project folder",
+                    function, path
+                ),
+            ),
+            None if paths.first().is_some_and(|path| path.is_none()) => self.push(
+                Severity::Low,
+                "filesystem",
+                call.span(),
+                format!(
+                    "{} uses a computed path, check it stays inside the project",
+                    function
+                ),
+            ),
+            None => {}
+        }
+    }
+
+    fn check_string(&mut self, lit: &LitStr) {
+        let value = lit.value();
+
+        if SECRET_PREFIXES
+            .iter()
+            .any(|prefix| value.starts_with(prefix) && value.len() > prefix.len() + 8)
+        {
+            self.push(
+                Severity::High,
+                "secret",
+                lit.span(),
+                "String literal looks like an API key or private key".to_string(),
+            );
+        }
+
+        if value.starts_with("http://") || value.starts_with("https://") {
+            // format! templates such as "http://{}:{}" are not a fixed destination
+            let Ok(url) = Url::parse(&value) else {
+                return;
+            };
+            let host = url.host_str().unwrap_or_default().to_lowercase();
+            if !LOCAL_HOSTS.contains(&host.as_str()) && !self.allowed_hosts.contains(&host) {
+                self.push(
+                    Severity::Medium,
+                    "url",
+                    lit.span(),
+                    format!(
+                        "Calls {} which is not one of the project's external urls",
+                        host
+                    ),
+                );
+            }
+        }
+    }
+
+    fn check_named_secret(&mut self, name: &str, expr: &Expr) {
+        let name = name.to_lowercase();
+        if !SECRET_NAMES.iter().any(|secret| name.contains(secret)) {
+            return;
+        }
+
+        if let Expr::Lit(ExprLit {
+            lit: Lit::Str(lit), ..
+        }) = expr
+        {
+            if lit.value().len() >= 8 {
+                self.push(
+                    Severity::Medium,
+                    "secret",
+                    lit.span(),
+                    format!(
+                        "{} is hard-coded, read it from the environment instead",
+                        name
+                    ),
+                );
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for SecurityCollector<'_> {
+    fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+        if let Expr::Path(path) = call.func.as_ref() {
+            let segments = path
+                .path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect::<Vec<String>>();
+
+            if let [.., owner, function] = segments.as_slice() {
+                if owner == "Command" && function == "new" {
+                    self.push(
+                        Severity::High,
+                        "process",
+                        call.span(),
+                        "Spawns a process with Command::new".to_string(),
+                    );
+                }
+
+                if FILESYSTEM_WRITES.contains(&(owner.as_str(), function.as_str())) {
+                    self.check_filesystem_write(call, &format!("{}::{}", owner, function));
+                }
+            }
+        }
+
+        syn::visit::visit_expr_call(self, call);
+    }
+
+    fn visit_expr_unsafe(&mut self, node: &'ast ExprUnsafe) {
+        self.push(
+            Severity::High,
+            "unsafe",
+            node.unsafe_token.span,
+            "Uses an unsafe block".to_string(),
+        );
+        syn::visit::visit_expr_unsafe(self, node);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        if let Some(unsafety) = node.sig.unsafety {
+            self.push(
+                Severity::High,
+                "unsafe",
+                unsafety.span,
+                format!("Declares unsafe fn {}", node.sig.ident),
+            );
+        }
+        syn::visit::visit_item_fn(self, node);
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+        if let Some(unsafety) = node.unsafety {
+            self.push(
+                Severity::High,
+                "unsafe",
+                unsafety.span,
+                "Declares an unsafe impl".to_string(),
+            );
+        }
+        syn::visit::visit_item_impl(self, node);
+    }
+
+    fn visit_item_const(&mut self, node: &'ast ItemConst) {
+        self.check_named_secret(&node.ident.to_string(), &node.expr);
+        syn::visit::visit_item_const(self, node);
+    }
+
+    fn visit_item_static(&mut self, node: &'ast ItemStatic) {
+        self.check_named_secret(&node.ident.to_string(), &node.expr);
+        syn::visit::visit_item_static(self, node);
+    }
+
+    fn visit_local(&mut self, node: &'ast Local) {
+        if let (Pat::Ident(pat), Some(init)) = (&node.pat, node.init.as_ref()) {
+            self.check_named_secret(&pat.ident.to_string(), &init.expr);
+        }
+        syn::visit::visit_local(self, node);
+    }
+
+    fn visit_lit_str(&mut self, lit: &'ast LitStr) {
+        self.check_string(lit);
+    }
+}
+
+// Collects `const NAME: &str = "..."` first, as constants are often declared after use
+struct ConstantCollector(HashMap<String, String>);
+
+impl<'ast> Visit<'ast> for ConstantCollector {
+    fn visit_item_const(&mut self, node: &'ast ItemConst) {
+        if let Expr::Lit(ExprLit {
+            lit: Lit::Str(lit), ..
+        }) = node.expr.as_ref()
+        {
+            self.0.insert(node.ident.to_string(), lit.value());
+        }
+    }
+}
+
+pub fn is_outside_project(path: &str) -> bool {
+    let path = path.trim();
+    path.starts_with('/')
+        || path.starts_with('~')
+        || path.starts_with('\\')
+        || path.split(['/', '\\']).any(|segment| segment == "..")
+        || path.chars().nth(1) == Some(':')
+}
+
+// Pattern checks on one generated source file
+pub fn scan_generated_code(
+    file: &str,
+    code: &str,
+    external_urls: &[String],
+) -> Vec<SecurityFinding> {
+    let syntax = match syn::parse_file(code) {
+        Ok(syntax) => syntax,
+        Err(e) => {
+            return vec![SecurityFinding {
+                severity: Severity::Medium,
+                category: "parse".to_string(),
+                file: file.to_string(),
+                line: Some(e.span().start().line),
+                message: format!("Could not parse the file for review: {}", e),
+                source: FindingSource::Static,
+            }]
+        }
+    };
+
+    let mut constants = ConstantCollector(HashMap::new());
+    constants.visit_file(&syntax);
+
+    let allowed_hosts = external_urls
+        .iter()
+        .filter_map(|url| Url::parse(url).ok())
+        .filter_map(|url| url.host_str().map(|host| host.to_lowercase()))
+        .collect();
+
+    let mut collector = SecurityCollector {
+        file,
+        allowed_hosts,
+        constants: constants.0,
+        findings: Vec::new(),
+    };
+    collector.visit_file(&syntax);
+
+    collector.findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn categories(findings: &[SecurityFinding]) -> Vec<(Severity, &str, Option<usize>)> {
+        findings
+            .iter()
+            .map(|finding| (finding.severity, finding.category.as_str(), finding.line))
+            .collect()
+    }
+
+    #[test]
+    fn test_flags_risky_code() {
+        let code = r#"
+const DB_PATH: &str = "../shared/db.json";
+const API_KEY: &str = "abcd1234efgh5678";
+
+fn run() {
+    std::process::Command::new("rm").arg("-rf").spawn().unwrap();
+    fs::write(DB_PATH, "{}").unwrap();
+    let ptr = unsafe { *raw };
+    let url = "https://evil.example.com/collect";
+}
+"#;
+        let findings = scan_generated_code("main.rs", code, &[]);
+
+        assert_eq!(
+            categories(&findings),
+            vec![
+                (Severity::Medium, "secret", Some(3)),
+                (Severity::High, "process", Some(6)),
+                (Severity::High, "filesystem", Some(7)),
+                (Severity::High, "unsafe", Some(8)),
+                (Severity::Medium, "url", Some(9)),
+            ]
+        );
+        assert!(findings[2].message.contains("'../shared/db.json'"));
+    }
+
+    #[test]
+    fn test_allows_expected_code() {
+        let code = r#"
+const DB_PATH: &str = "tasks.json";
+
+async fn prices() {
+    fs::write(DB_PATH, "[]").unwrap();
+    let local = format!("http://127.0.0.1:{}/tasks", 8080);
+    let base = "http://localhost:8080";
+    reqwest::get("https://api.binance.com/api/v3/ticker/price").await;
+}
+"#;
+        let external_urls = vec!["https://api.binance.com/api/v3/exchangeInfo".to_string()];
+        assert!(scan_generated_code("main.rs", code, &external_urls).is_empty());
+
+        assert!(is_outside_project("/etc/passwd"));
+        assert!(is_outside_project("data/../../secrets"));
+        assert!(!is_outside_project("data/tasks.json"));
+
+        let findings = scan_generated_code("main.rs", "fn broken( {", &[]);
+        assert_eq!(findings[0].category, "parse");
+    }
+}
@@ -0,0 +1,349 @@
+use std::{
+    net::{Ipv4Addr, SocketAddr, TcpListener},
+    os::unix::process::CommandExt,
+    process::{self, Stdio},
+    sync::{Arc, Mutex, OnceLock},
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, BufReader},
+    net::TcpStream,
+    process::{Child, Command},
+    task::JoinHandle,
+    time::{self, Instant},
+};
+
+// Environment variables the generated server reads its address from
+pub const SERVER_PORT_ENV: &str = "PORT";
+pub const SERVER_HOST_ENV: &str = "HOST";
+pub const DEFAULT_SERVER_PORT: u16 = 8080;
+pub const DEFAULT_SERVER_HOST: &str = "127.0.0.1";
+
+const READINESS_POLL_INTERVAL: Duration = Duration::from_millis(250);
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(500);
+const PORT_RELEASE_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Ask the OS for a port nobody is listening on
+pub fn allocate_free_port() -> anyhow::Result<u16> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+    Ok(listener.local_addr()?.port())
+}
+
+// Whether anything can still bind the port, i.e. nobody is listening on it
+pub fn is_port_free(port: u16) -> bool {
+    TcpListener::bind((Ipv4Addr::LOCALHOST, port)).is_ok()
+}
+
+// Poll until the port can be bound again
+pub async fn wait_for_port_release(port: u16, timeout: Duration) -> anyhow::Result<()> {
+    let deadline = Instant::now() + timeout;
+
+    while !is_port_free(port) {
+        if Instant::now() >= deadline {
+            anyhow::bail!("Port {} is still in use after stopping the server", port);
+        }
+        time::sleep(READINESS_POLL_INTERVAL).await;
+    }
+
+    Ok(())
+}
+
+// Process groups of every server still running, for the Ctrl-C handler to kill
+fn live_groups() -> &'static Mutex<Vec<i32>> {
+    static LIVE_GROUPS: OnceLock<Mutex<Vec<i32>>> = OnceLock::new();
+    LIVE_GROUPS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+// Kills a whole process group when dropped, so `cargo run` cannot orphan the server binary
+#[derive(Debug)]
+pub struct ProcessGroupGuard {
+    pgid: i32,
+    armed: bool,
+}
+
+impl ProcessGroupGuard {
+    pub fn new(pgid: i32) -> Self {
+        live_groups().lock().unwrap().push(pgid);
+        Self { pgid, armed: true }
+    }
+
+    pub fn pgid(&self) -> i32 {
+        self.pgid
+    }
+
+    pub fn kill(&mut self) {
+        if self.armed {
+            // Unlisted first, so the Ctrl-C handler never kills a group id that was reused
+            live_groups()
+                .lock()
+                .unwrap()
+                .retain(|&pgid| pgid != self.pgid);
+            kill_process_group(self.pgid);
+            self.armed = false;
+        }
+    }
+}
+
+impl Drop for ProcessGroupGuard {
+    fn drop(&mut self) {
+        self.kill();
+    }
+}
+
+fn kill_process_group(pgid: i32) {
+    // A group that already exited is fine, so the result is ignored
+    unsafe {
+        libc::killpg(pgid, libc::SIGKILL);
+    }
+}
+
+// One handler for the whole process: on Ctrl-C it kills every live server group, then exits.
+// It is started again when the runtime it ran on has shut down.
+fn ensure_interrupt_handler() {
+    static HANDLER: Mutex<Option<JoinHandle<()>>> = Mutex::new(None);
+
+    let mut handler = HANDLER.lock().unwrap();
+    if handler.as_ref().is_some_and(|task| !task.is_finished()) {
+        return;
+    }
+
+    *handler = Some(tokio::spawn(async {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            for pgid in live_groups().lock().unwrap().drain(..) {
+                kill_process_group(pgid);
+            }
+            process::exit(130);
+        }
+    }));
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ServerReadiness {
+    Ready,
+    Exited(String),
+    TimedOut,
+}
+
+// Spawned web server under test, with everything it printed so far
+#[derive(Debug)]
+pub struct TestServer {
+    child: Child,
+    port: u16,
+    stdout: Arc<Mutex<String>>,
+    stderr: Arc<Mutex<String>>,
+    group: ProcessGroupGuard,
+}
+
+fn capture_output<R>(reader: Option<R>, buffer: Arc<Mutex<String>>)
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    let Some(reader) = reader else {
+        return;
+    };
+
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(reader).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            let mut buffer = buffer.lock().unwrap();
+            buffer.push_str(&line);
+            buffer.push('\n');
+        }
+    });
+}
+
+impl TestServer {
+    // Run `program args` in `current_dir`, in its own process group, with PORT set to a free port
+    pub fn start(program: &str, args: &[&str], current_dir: &str) -> anyhow::Result<Self> {
+        let port = allocate_free_port()?;
+
+        let mut command = std::process::Command::new(program);
+        command
+            .args(args)
+            .current_dir(current_dir)
+            .env(SERVER_HOST_ENV, DEFAULT_SERVER_HOST)
+            .env(SERVER_PORT_ENV, port.to_string())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .process_group(0);
+
+        let mut child = Command::from(command).kill_on_drop(true).spawn()?;
+
+        // With process_group(0) the group id is the pid of the spawned process
+        let pgid = child
+            .id()
+            .ok_or_else(|| anyhow::anyhow!("Server process exited before it was tracked"))?
+            as i32;
+        let group = ProcessGroupGuard::new(pgid);
+        ensure_interrupt_handler();
+
+        let stdout = Arc::new(Mutex::new(String::new()));
+        let stderr = Arc::new(Mutex::new(String::new()));
+        capture_output(child.stdout.take(), stdout.clone());
+        capture_output(child.stderr.take(), stderr.clone());
+
+        Ok(Self {
+            child,
+            port,
+            stdout,
+            stderr,
+            group,
+        })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    pub fn base_url(&self) -> String {
+        format!("http://127.0.0.1:{}", self.port)
+    }
+
+    pub fn stdout(&self) -> String {
+        self.stdout.lock().unwrap().clone()
+    }
+
+    pub fn stderr(&self) -> String {
+        self.stderr.lock().unwrap().clone()
+    }
+
+    // Poll until the server accepts connections, exits, or the timeout runs out
+    pub async fn wait_until_ready(&mut self, timeout: Duration) -> anyhow::Result<ServerReadiness> {
+        let deadline = Instant::now() + timeout;
+        let address = SocketAddr::from((Ipv4Addr::LOCALHOST, self.port));
+
+        loop {
+            if let Some(status) = self.child.try_wait()? {
+                // Give the output readers a moment to drain the pipes
+                time::sleep(READINESS_POLL_INTERVAL).await;
+                return Ok(ServerReadiness::Exited(status.to_string()));
+            }
+
+            if let Ok(Ok(_)) = time::timeout(CONNECT_TIMEOUT, TcpStream::connect(address)).await {
+                return Ok(ServerReadiness::Ready);
+            }
+
+            if Instant::now() >= deadline {
+                return Ok(ServerReadiness::TimedOut);
+            }
+
+            time::sleep(READINESS_POLL_INTERVAL).await;
+        }
+    }
+
+    // Exit status of a server that stopped on its own, None while it is still running
+    pub fn exit_status(&mut self) -> anyhow::Result<Option<String>> {
+        Ok(self.child.try_wait()?.map(|status| status.to_string()))
+    }
+
+    // Bug description for a server that never became ready
+    pub fn startup_failure(&self, readiness: &ServerReadiness) -> String {
+        let reason = match readiness {
+            ServerReadiness::Ready => return String::new(),
+            ServerReadiness::Exited(status) => format!("the server exited on startup ({})", status),
+            ServerReadiness::TimedOut => format!(
+                "the server did not accept connections on the port given by the {} environment variable ({})",
+                SERVER_PORT_ENV, self.port
+            ),
+        };
+
+        format!(
+            "SERVER STARTUP FAILURE: {} \n STDOUT: {} \n STDERR: {}",
+            reason,
+            self.stdout(),
+            self.stderr()
+        )
+    }
+
+    // Kill every process the server started and make sure the port is free again
+    pub async fn stop(mut self) -> anyhow::Result<()> {
+        self.group.kill();
+        self.child.wait().await?;
+
+        wait_for_port_release(self.port, PORT_RELEASE_TIMEOUT).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allocates_bindable_port() -> anyhow::Result<()> {
+        let port = allocate_free_port()?;
+        assert!(port > 0);
+        TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reports_crash_with_stderr() -> anyhow::Result<()> {
+        let mut server = TestServer::start(
+            "sh",
+            &[
+                "-c",
+                "echo \"listening on $PORT\"; echo 'thread main panicked' >&2; exit 3",
+            ],
+            ".",
+        )?;
+
+        let readiness = server.wait_until_ready(Duration::from_secs(5)).await?;
+        assert!(matches!(readiness, ServerReadiness::Exited(_)));
+
+        let failure = server.startup_failure(&readiness);
+        assert!(failure.contains("thread main panicked"));
+        assert!(failure.contains(&format!("listening on {}", server.port())));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_waits_until_port_accepts_connections() -> anyhow::Result<()> {
+        let mut server = TestServer::start("sleep", &["5"], ".")?;
+
+        let readiness = server.wait_until_ready(Duration::from_millis(600)).await?;
+        assert_eq!(readiness, ServerReadiness::TimedOut);
+
+        // Stand in for the server binding the port it was given
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, server.port()))?;
+        let readiness = server.wait_until_ready(Duration::from_secs(5)).await?;
+        assert_eq!(readiness, ServerReadiness::Ready);
+
+        drop(listener);
+        server.stop().await
+    }
+
+    #[tokio::test]
+    async fn test_stop_kills_grandchildren_and_releases_port() -> anyhow::Result<()> {
+        // The shell stands in for `cargo run`, the background sleep for the server binary
+        let server = TestServer::start("sh", &["-c", "sleep 30 & echo $!; wait"], ".")?;
+        let pgid = server.group.pgid();
+
+        time::sleep(Duration::from_millis(300)).await;
+        let grandchild = server.stdout().trim().parse::<i32>()?;
+        assert_eq!(unsafe { libc::getpgid(grandchild) }, pgid);
+
+        server.stop().await?;
+        time::sleep(Duration::from_millis(100)).await;
+        // Orphans may linger as zombies until reaped, which still counts as dead
+        let status = std::fs::read_to_string(format!("/proc/{}/stat", grandchild));
+        assert!(status.map_or(true, |stat| stat.contains(") Z ")));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_guard_kills_group_on_drop() -> anyhow::Result<()> {
+        let server = TestServer::start("sleep", &["30"], ".")?;
+        let pgid = server.group.pgid();
+        let port = server.port();
+
+        assert!(live_groups().lock().unwrap().contains(&pgid));
+
+        drop(server);
+        time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(unsafe { libc::killpg(pgid, 0) }, -1);
+        assert!(!live_groups().lock().unwrap().contains(&pgid));
+        wait_for_port_release(port, Duration::from_secs(1)).await
+    }
+}
@@ -0,0 +1,79 @@
+use super::{
+    basic_agent::{AgentState, BasicAgent},
+    basic_traits::BasicTraits,
+};
+use crate::helpers::command_line::PrintCommand;
+use std::collections::HashMap;
+
+// Fixes a single check gets before the agent gives up on it
+const MAX_FIX_ATTEMPTS: usize = 2;
+
+// Bugs an agent hands back to its fix loop. Every check (build, tests, routes, ...) keeps its own
+// budget, so fixing one check does not use up the attempts of the checks after it.
+#[derive(Debug, Default)]
+pub struct BugTracker {
+    counts: HashMap<&'static str, usize>,
+    last_error: Option<String>,
+}
+
+impl BugTracker {
+    pub fn has_bugs(&self) -> bool {
+        self.last_error.is_some()
+    }
+
+    // Latest bug, as given to the fix prompt
+    pub fn last_error(&self) -> Option<&String> {
+        self.last_error.as_ref()
+    }
+
+    // Send the agent back to work on the bug, or fail once the check ran out of fixes
+    pub fn record(
+        &mut self,
+        agent: &mut BasicAgent,
+        check: &'static str,
+        error: String,
+    ) -> anyhow::Result<()> {
+        let count = self.counts.entry(check).or_default();
+        *count += 1;
+
+        if *count > MAX_FIX_ATTEMPTS {
+            let err_msg = format!(
+                "{}: still failing after {} fixes, giving up",
+                check, MAX_FIX_ATTEMPTS
+            );
+            PrintCommand::Issue.print_agent_message(agent.get_position(), &err_msg)?;
+            anyhow::bail!("{}\n{}", err_msg, error);
+        }
+
+        self.last_error = Some(error);
+        agent.update_state(AgentState::Working);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keeps_a_budget_per_check() {
+        let mut agent = BasicAgent::new("Test".to_string(), "Tester".to_string());
+        let mut bugs = BugTracker::default();
+        assert!(!bugs.has_bugs());
+
+        for check in ["build", "build", "routes", "routes", "endpoints"] {
+            assert!(bugs.record(&mut agent, check, check.to_string()).is_ok());
+        }
+        assert_eq!(agent.get_state(), &AgentState::Working);
+        assert_eq!(bugs.last_error().map(String::as_str), Some("endpoints"));
+
+        let error = bugs
+            .record(&mut agent, "build", "error[E0425]".to_string())
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("build: still failing after 2 fixes"));
+        assert_eq!(bugs.last_error().map(String::as_str), Some("endpoints"));
+    }
+}
@@ -1,20 +1,30 @@
 // agent architect
 
 use crate::{
-    ai_functions::aifunc_architect::{print_project_scope, print_site_urls},
+    ai_functions::aifunc_architect::{
+        print_backend_framework, print_project_scope, print_requirements_spec, print_route_plan,
+        print_site_urls,
+    },
     helpers::{
         command_line::PrintCommand,
-        general::{ai_task_request_decode, check_status_code},
+        general::{ai_task_request, ai_task_request_decode, check_status_code},
     },
-    models::agent_basic::{
-        basic_agent::{AgentState, BasicAgent},
-        basic_traits::BasicTraits,
+    models::{
+        agent_basic::{
+            basic_agent::{AgentState, BasicAgent},
+            basic_traits::BasicTraits,
+        },
+        general::requirements::RequirementsSpec,
+        templates::{
+            template_registry::{framework_from_env, framework_names, parse_framework},
+            template_traits::BackendFramework,
+        },
     },
 };
 use reqwest::Client;
 use std::time::Duration;
 
-use super::agent_traits::{FactSheet, ProjectScope, SpecialFunctions};
+use super::agent_traits::{FactSheet, ProjectScope, RouteObject, SpecialFunctions};
 
 // Solutions Architect
 #[derive(Debug)]
@@ -40,7 +50,7 @@ impl AgentSolutionArchitect {
         &mut self,
         factsheet: &mut FactSheet,
     ) -> anyhow::Result<ProjectScope> {
-        let msg_context = format!("{}", factsheet.project_description);
+        let msg_context = factsheet.project_context();
 
         // agent_position 和 agent_operation 只是用来打印日志的
         let ai_response = ai_task_request_decode::<ProjectScope>(
@@ -51,11 +61,111 @@ impl AgentSolutionArchitect {
         )
         .await?;
 
-        factsheet.project_scope = Some(ai_response.clone());
+        factsheet.project_scope = Some(ai_response);
 
         Ok(ai_response)
     }
 
+    // Retrieve the requirements specification, the scope flags alone are enough to carry on without it
+    async fn call_requirements_spec(&mut self, factsheet: &mut FactSheet) -> anyhow::Result<()> {
+        let msg_context = format!(
+            "PROJECT_DESCRIPTION: {} \n PROJECT_SCOPE: {}",
+            factsheet.project_context(),
+            serde_json::to_string(&factsheet.project_scope)?
+        );
+
+        match ai_task_request_decode::<RequirementsSpec>(
+            &msg_context,
+            &self.attributes.position,
+            get_function_string!(print_requirements_spec),
+            print_requirements_spec,
+        )
+        .await
+        {
+            Ok(spec) => factsheet.requirements = Some(spec.numbered()),
+            Err(e) => {
+                let err_msg = format!("Could not write the requirements specification: {}", e);
+                PrintCommand::Issue.print_agent_message(&self.attributes.position, &err_msg)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Plan the routes up front, the backend then has to implement exactly these
+    async fn call_route_plan(&mut self, factsheet: &mut FactSheet) -> anyhow::Result<()> {
+        let msg_context = format!(
+            "PROJECT_DESCRIPTION: {} \n REQUIREMENTS: {}",
+            factsheet.project_context(),
+            factsheet
+                .requirements
+                .as_ref()
+                .map(|spec| spec.describe())
+                .unwrap_or_default()
+        );
+
+        match ai_task_request_decode::<Vec<RouteObject>>(
+            &msg_context,
+            &self.attributes.position,
+            get_function_string!(print_route_plan),
+            print_route_plan,
+        )
+        .await
+        {
+            Ok(mut routes) => {
+                for route in routes.iter_mut() {
+                    route.is_route_dynamic = route.route.contains('{');
+                }
+                factsheet.route_plan = Some(routes);
+            }
+            Err(e) => {
+                let err_msg = format!("Could not plan the routes: {}", e);
+                PrintCommand::Issue.print_agent_message(&self.attributes.position, &err_msg)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Retrieve Backend Framework
+    async fn call_backend_framework(
+        &mut self,
+        factsheet: &mut FactSheet,
+    ) -> anyhow::Result<BackendFramework> {
+        // A framework forced by the user always wins over the architect's choice
+        if let Some(framework) = framework_from_env()? {
+            factsheet.backend_framework = Some(framework);
+            return Ok(framework);
+        }
+
+        let msg_context = format!(
+            "{} AVAILABLE_FRAMEWORKS: {}",
+            factsheet.project_context(),
+            framework_names()
+        );
+
+        let ai_response = ai_task_request(
+            &msg_context,
+            &self.attributes.position,
+            get_function_string!(print_backend_framework),
+            print_backend_framework,
+        )
+        .await?;
+
+        let framework = match parse_framework(&ai_response) {
+            Ok(framework) => framework,
+            Err(e) => {
+                let err_msg = format!("{}, falling back to {}", e, BackendFramework::default());
+                PrintCommand::Issue.print_agent_message(&self.attributes.position, &err_msg)?;
+                BackendFramework::default()
+            }
+        };
+
+        factsheet.backend_framework = Some(framework);
+
+        Ok(framework)
+    }
+
     // Retrieve External Urls
     async fn call_determine_external_urls(
         &mut self,
@@ -64,7 +174,7 @@ impl AgentSolutionArchitect {
     ) -> anyhow::Result<()> {
         // agent_position 和 agent_operation 只是用来打印日志的
         let ai_response = ai_task_request_decode::<Vec<String>>(
-            &msg_context,
+            msg_context,
             &self.attributes.position,
             get_function_string!(print_site_urls),
             print_site_urls,
@@ -77,6 +187,12 @@ impl AgentSolutionArchitect {
     }
 }
 
+impl Default for AgentSolutionArchitect {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[async_trait::async_trait]
 impl SpecialFunctions for AgentSolutionArchitect {
     fn get_attributes_from_agent(&self) -> &BasicAgent {
@@ -88,13 +204,13 @@ impl SpecialFunctions for AgentSolutionArchitect {
             match self.attributes.get_state() {
                 AgentState::Discovery => {
                     let project_scope = self.call_project_scope(factsheet).await?;
+                    self.call_requirements_spec(factsheet).await?;
+                    self.call_route_plan(factsheet).await?;
+                    self.call_backend_framework(factsheet).await?;
 
                     if project_scope.is_external_urls_required {
-                        self.call_determine_external_urls(
-                            factsheet,
-                            &factsheet.project_description.clone(),
-                        )
-                        .await?;
+                        self.call_determine_external_urls(factsheet, &factsheet.project_context())
+                            .await?;
 
                         self.attributes.update_state(AgentState::UnitTesting);
                     } else {
@@ -127,7 +243,7 @@ impl SpecialFunctions for AgentSolutionArchitect {
                         }
                     }
 
-                    if exclude_urls.len() > 0 {
+                    if !exclude_urls.is_empty() {
                         let new_urls = urls
                             .iter()
                             .filter(|&url| !exclude_urls.contains(url))
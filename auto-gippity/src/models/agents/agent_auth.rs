@@ -0,0 +1,89 @@
+use super::agent_traits::{FactSheet, SpecialFunctions};
+use crate::{
+    helpers::{
+        command_line::PrintCommand,
+        general::{read_code_template_contents, save_auth_code},
+    },
+    models::{
+        agent_basic::{
+            basic_agent::{AgentState, BasicAgent},
+            basic_traits::BasicTraits,
+        },
+        general::auth_schema::AuthSetup,
+        templates::template_registry::find_template,
+    },
+};
+
+#[derive(Debug)]
+pub struct AgentAuthSpecialist {
+    attributes: BasicAgent,
+}
+
+impl AgentAuthSpecialist {
+    pub fn new() -> Self {
+        let attributes = BasicAgent {
+            objective: "Adds vetted session authentication to the backend".to_string(),
+            position: "Auth Specialist".to_string(),
+            state: AgentState::Discovery,
+            memory: Vec::new(),
+        };
+
+        Self { attributes }
+    }
+}
+
+impl Default for AgentAuthSpecialist {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl SpecialFunctions for AgentAuthSpecialist {
+    fn get_attributes_from_agent(&self) -> &BasicAgent {
+        &self.attributes
+    }
+
+    async fn execute(&mut self, factsheet: &mut FactSheet) -> anyhow::Result<()> {
+        while self.attributes.get_state() != &AgentState::Finished {
+            match self.attributes.get_state() {
+                AgentState::Discovery => {
+                    let is_user_login_and_logout = factsheet
+                        .project_scope
+                        .as_ref()
+                        .is_some_and(|scope| scope.is_user_login_and_logout);
+
+                    if !is_user_login_and_logout {
+                        self.attributes.update_state(AgentState::Finished);
+                        continue;
+                    }
+
+                    self.attributes.update_state(AgentState::Working);
+                }
+                AgentState::Working => {
+                    // Password hashing and sessions are copied from a reviewed module, never generated
+                    let template = find_template(factsheet.backend_framework.unwrap_or_default());
+                    let auth_code = read_code_template_contents(template.auth_template_path());
+                    save_auth_code(&auth_code);
+
+                    let setup = AuthSetup::default();
+                    let added_msg = format!(
+                        "Added session auth for {}: {} guards protected handlers",
+                        template.framework(),
+                        setup.guard_type
+                    );
+                    PrintCommand::AICall
+                        .print_agent_message(self.attributes.get_position(), &added_msg)?;
+
+                    factsheet.auth_setup = Some(setup);
+                    self.attributes.update_state(AgentState::Finished);
+                }
+                _ => {
+                    self.attributes.update_state(AgentState::Finished);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,34 +1,86 @@
-use super::agent_traits::{FactSheet, RouteObject, SpecialFunctions};
+use super::{
+    agent_reviewer::AgentCodeReviewer,
+    agent_security::AgentSecurityReviewer,
+    agent_traits::{FactSheet, RouteObject, SpecialFunctions},
+};
 use crate::{
     ai_functions::aifunc_backend::{
-        print_backend_webserver_code, print_fixed_code, print_improved_webserrver_code,
-        print_rest_api_endpoints,
+        print_backend_tests, print_backend_webserver_code, print_fixed_code,
+        print_improved_webserrver_code, print_rest_api_endpoints, print_route_responses,
     },
     helpers::{
+        acceptance_testing::{acceptance_report, check_acceptance_criteria},
+        auth_testing::run_auth_tests,
+        cargo_testing::run_cargo_tests,
+        client_sdk::{client_operations, smoke_calls},
+        client_testing::{check_rust_client, check_typescript_client, client_failure_report},
         command_line::{confirm_safe_code, PrintCommand},
+        contract_testing::contract_report,
+        dependencies::CrateSpec,
+        endpoint_testing::run_endpoint_tests,
         general::{
-            ai_task_request, check_status_code, read_code_template_contents,
-            read_exec_main_contents, save_api_endpoints, save_backend_code,
-            WEB_SERVER_PROJECT_PATH,
+            ai_task_request, ai_task_request_decode, attach_backend_tests,
+            read_code_template_contents, read_exec_main_contents, read_exec_manifest_contents,
+            read_exec_tests_contents, save_api_endpoints, save_backend_code, save_backend_tests,
+            save_client_sdks, save_openapi_files, update_exec_dependencies, EXEC_TARGET_PATH,
+            RUST_CLIENT_PATH, TYPESCRIPT_CLIENT_PATH, WEB_SERVER_PROJECT_PATH,
         },
+        migrations::SQLITE_CRATE,
+        openapi::{openapi_document, validate_openapi},
+        route_diff::diff_routes,
+        runtime_failures::runtime_failure_report,
+        test_server::{ServerReadiness, TestServer},
     },
-    models::agent_basic::{
-        basic_agent::{AgentState, BasicAgent},
-        basic_traits::BasicTraits,
+    models::{
+        agent_basic::{
+            basic_agent::{AgentState, BasicAgent},
+            basic_traits::BasicTraits,
+            bug_tracker::BugTracker,
+        },
+        general::{
+            api_schema::{FieldSchema, HttpMethod},
+            auth_schema::AuthSetup,
+            code_review::CodeReview,
+            deployment::ServerSetup,
+            requirements::CriterionOutcome,
+        },
+        templates::{
+            template_registry::find_template,
+            template_traits::{BackendTemplate, AUTH_DEPENDENCIES, DATABASE_DEPENDENCIES},
+        },
     },
 };
-use reqwest::Client;
+use reqwest::{
+    header::{HeaderMap, HeaderValue, COOKIE},
+    Client,
+};
+use serde::Deserialize;
 use std::{
+    path::Path,
     process::{Command, Stdio},
     time::Duration,
 };
-use tokio::time;
+
+// How long `cargo run` gets to start listening before the server counts as broken
+const SERVER_STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+// Code review improvement rounds spent on items the verification found unaddressed
+const MAX_CODE_REVIEW_ROUNDS: usize = 2;
+
+// Response shape of a single route, as worked out by the LLM
+#[derive(Debug, Deserialize)]
+struct RouteResponse {
+    method: HttpMethod,
+    route: String,
+    response: FieldSchema,
+}
 
 #[derive(Debug)]
 pub struct AgentBackendDeveloper {
     attributes: BasicAgent,
-    bug_errors: Option<String>,
-    bug_count: usize,
+    bugs: BugTracker,
+    code_reviewer: AgentCodeReviewer,
+    security_reviewer: AgentSecurityReviewer,
 }
 
 impl AgentBackendDeveloper {
@@ -42,17 +94,115 @@ impl AgentBackendDeveloper {
 
         Self {
             attributes,
-            bug_errors: None,
-            bug_count: 0,
+            bugs: BugTracker::default(),
+            code_reviewer: AgentCodeReviewer::new(),
+            security_reviewer: AgentSecurityReviewer::new(),
         }
     }
 
+    // Template of the framework chosen by the architect
+    fn backend_template(factsheet: &FactSheet) -> &'static dyn BackendTemplate {
+        find_template(factsheet.backend_framework.unwrap_or_default())
+    }
+
+    // Framework context, the architect's requirements and plan, plus the database and auth modules
+    // other agents already wrote
+    fn prompt_context(factsheet: &FactSheet) -> String {
+        let template = Self::backend_template(factsheet);
+        let mut context = template.prompt_context();
+
+        if let Some(spec) = factsheet.requirements.as_ref() {
+            context.push_str(&format!(" REQUIREMENTS: {} \n", spec.describe()));
+            if !spec.entities.is_empty() {
+                let models = spec
+                    .entities
+                    .iter()
+                    .map(|entity| entity.rust_struct())
+                    .collect::<Vec<String>>();
+                context.push_str(&format!(" ENTITY_MODELS: {} \n", models.join("\n")));
+            }
+        }
+
+        if let Some(routes) = factsheet.route_plan.as_ref() {
+            let planned = routes
+                .iter()
+                .map(|route| {
+                    serde_json::json!({
+                        "method": route.method,
+                        "route": route.route,
+                        "request_body": route.request_body,
+                        "response": route.response,
+                    })
+                })
+                .collect::<Vec<serde_json::Value>>();
+            context.push_str(&format!(
+                " ROUTE_PLAN: {} \n",
+                serde_json::Value::Array(planned)
+            ));
+        }
+
+        if let Some(design) = factsheet.database_design.as_ref() {
+            context.push_str(&format!(
+                " DATABASE: Data is stored in SQLite through {}. main.rs declares `mod migrations;` and `mod db;`, \
+                opens the database once with db::open_database(\"database.db\") and uses the db functions instead of a JSON file. \
+                TABLES: {} \n DATA_ACCESS_CODE (module db, already written, do not repeat it): {} \n",
+                SQLITE_CRATE,
+                design.describe_tables(),
+                design.data_access_code
+            ));
+        }
+
+        if let Some(setup) = factsheet.auth_setup.as_ref() {
+            context.push_str(&format!(
+                " AUTHENTICATION: main.rs declares `mod auth;`, the module is already written, do not repeat it \
+                or write any other login code. {} Every handler reading or changing user data takes an `auth::{}` \
+                argument. The auth module serves {}, {}, {} and {} itself. \n",
+                template.auth_integration_notes(),
+                setup.guard_type,
+                setup.signup_route,
+                setup.login_route,
+                setup.logout_route,
+                setup.current_user_route
+            ));
+        }
+
+        context
+    }
+
+    fn allowed_dependencies(factsheet: &FactSheet) -> Vec<CrateSpec> {
+        let mut dependencies = Self::backend_template(factsheet).dependencies();
+        if factsheet.database_design.is_some() {
+            dependencies.extend_from_slice(DATABASE_DEPENDENCIES);
+        }
+        if factsheet.auth_setup.is_some() {
+            dependencies.extend_from_slice(AUTH_DEPENDENCIES);
+        }
+        dependencies
+    }
+
+    // Client for the endpoint tests, logged in when the backend has auth
+    fn endpoint_test_client(session_cookie: Option<&str>) -> anyhow::Result<Client> {
+        let mut headers = HeaderMap::new();
+        if let Some(cookie) = session_cookie {
+            headers.insert(COOKIE, HeaderValue::from_str(cookie)?);
+        }
+
+        Ok(Client::builder()
+            .timeout(Duration::from_secs(5))
+            .default_headers(headers)
+            .build()?)
+    }
+
     async fn call_initial_backend_code(&mut self, factsheet: &mut FactSheet) -> anyhow::Result<()> {
-        let code_template_str = read_code_template_contents();
+        let template = Self::backend_template(factsheet);
+        let code_template_str = read_code_template_contents(template.code_template_path());
 
         let msg_context = format!(
-            "CODE TEMPLATE: {} \n PROJECT DESCRIPTION: {} \n",
-            code_template_str, factsheet.project_description
+            "{} CODE TEMPLATE: {} \n PROJECT DESCRIPTION: {} \n{}",
+            Self::prompt_context(factsheet),
+            code_template_str,
+            factsheet.project_context(),
+            factsheet.review_feedback_context()
         );
 
         let ai_response = ai_task_request(
@@ -72,10 +222,14 @@ impl AgentBackendDeveloper {
     async fn call_improved_backend_code(
         &mut self,
         factsheet: &mut FactSheet,
+        review_checklist: &str,
     ) -> anyhow::Result<()> {
         let msg_context = format!(
-            "CODE TEMPLATE: {:?} \n PROJECT DESCRIPTION: {:?} \n",
-            factsheet.backend_code, factsheet
+            "{} CODE TEMPLATE: {:?} \n PROJECT DESCRIPTION: {:?} \n REVIEW_CHECKLIST: {} \n",
+            Self::prompt_context(factsheet),
+            factsheet.backend_code,
+            factsheet,
+            review_checklist
         );
 
         let ai_response = ai_task_request(
@@ -92,11 +246,47 @@ impl AgentBackendDeveloper {
         Ok(())
     }
 
+    // Review the first draft, improve it against the checklist and verify every item was handled
+    async fn call_reviewed_improvement(&mut self, factsheet: &mut FactSheet) -> anyhow::Result<()> {
+        let draft = factsheet.backend_code.clone().unwrap_or_default();
+        let mut review = self.code_reviewer.review(factsheet, &draft).await?;
+
+        for _ in 0..MAX_CODE_REVIEW_ROUNDS {
+            let pending = review.unaddressed();
+            if pending.is_empty() {
+                break;
+            }
+
+            let checklist = CodeReview::checklist(&pending);
+            self.call_improved_backend_code(factsheet, &checklist)
+                .await?;
+
+            let improved = factsheet.backend_code.clone().unwrap_or_default();
+            review.verification = self.code_reviewer.verify(&review, &improved).await?;
+        }
+
+        let unaddressed = review.unaddressed().len();
+        if unaddressed > 0 {
+            let err_msg = format!(
+                "WARNING: {} of {} review items are still unaddressed",
+                unaddressed,
+                review.items.len()
+            );
+            PrintCommand::Issue.print_agent_message(self.attributes.get_position(), &err_msg)?;
+        }
+
+        factsheet.code_review = Some(review);
+
+        Ok(())
+    }
+
     async fn call_fix_code_bugs(&mut self, factsheet: &mut FactSheet) -> anyhow::Result<()> {
         let msg_context = format!(
-            "BROKEN CODE: {:?} \n ERROR BUGS: {:?} \n
+            "{} BROKEN CODE: {:?} \n ERROR BUGS: {:?} \n
             THIS FUNCTION ONLY OUTPUTS CODE. JUST OUTPUT THE CODE",
-            factsheet.backend_code, self.bug_errors
+            Self::prompt_context(factsheet),
+            factsheet.backend_code,
+            self.bugs.last_error()
         );
 
         let ai_response = ai_task_request(
@@ -113,9 +303,48 @@ impl AgentBackendDeveloper {
         Ok(())
     }
 
-    async fn call_extract_rest_api_endpoints(&self) -> anyhow::Result<String> {
+    async fn call_write_backend_tests(&self, factsheet: &FactSheet) -> anyhow::Result<()> {
+        let template = Self::backend_template(factsheet);
+
+        let msg_context = format!(
+            "{} TEST_HARNESS: {} \n CODE_INPUT: {:?}",
+            Self::prompt_context(factsheet),
+            template.test_harness_notes(),
+            factsheet.backend_code
+        );
+
+        let ai_response = ai_task_request(
+            &msg_context,
+            self.attributes.get_position(),
+            get_function_string!(print_backend_tests),
+            print_backend_tests,
+        )
+        .await?;
+
+        save_backend_tests(&ai_response);
+
+        Ok(())
+    }
+
+    async fn call_extract_rest_api_endpoints(
+        &self,
+        template: &dyn BackendTemplate,
+    ) -> anyhow::Result<Vec<RouteObject>> {
         let backend_code = read_exec_main_contents();
-        let msg_context = format!("CODE INPUT: {}", backend_code);
+
+        // Parse the route table from the code itself when the framework supports it
+        if let Some(routes) = template.extract_routes(&backend_code) {
+            return self
+                .call_extract_route_responses(&backend_code, routes)
+                .await;
+        }
+
+        let msg_context = format!(
+            "FRAMEWORK: {} \n ROUTE EXAMPLE: {} \n CODE INPUT: {}",
+            template.framework(),
+            template.route_declaration_example(),
+            backend_code
+        );
 
         let ai_response = ai_task_request(
             &msg_context,
@@ -125,7 +354,67 @@ impl AgentBackendDeveloper {
         )
         .await?;
 
-        Ok(ai_response)
+        let api_endpoints = serde_json::from_str::<Vec<RouteObject>>(&ai_response)
+            .expect("Failed to decode API Endpoints");
+
+        Ok(api_endpoints)
+    }
+
+    // Only the response shapes are left to the LLM once routes are parsed
+    async fn call_extract_route_responses(
+        &self,
+        backend_code: &str,
+        mut routes: Vec<RouteObject>,
+    ) -> anyhow::Result<Vec<RouteObject>> {
+        let route_list = routes
+            .iter()
+            .map(|route| {
+                serde_json::json!({
+                    "method": route.method,
+                    "route": route.route,
+                    "handler": route.handler,
+                })
+            })
+            .collect::<Vec<serde_json::Value>>();
+
+        let msg_context = format!(
+            "CODE INPUT: {} \n ROUTES: {}",
+            backend_code,
+            serde_json::to_string(&route_list)?
+        );
+
+        let ai_response = ai_task_request_decode::<Vec<RouteResponse>>(
+            &msg_context,
+            self.attributes.get_position(),
+            get_function_string!(print_route_responses),
+            print_route_responses,
+        )
+        .await;
+
+        match ai_response {
+            Ok(responses) => {
+                for route in routes.iter_mut() {
+                    if let Some(found) = responses.iter().find(|response| {
+                        response.method == route.method && response.route == route.route
+                    }) {
+                        route.response = found.response.clone();
+                    }
+                }
+            }
+            Err(e) => {
+                let err_msg = format!("Failed to decode route responses, leaving them out: {}", e);
+                PrintCommand::Issue
+                    .print_agent_message(self.attributes.get_position(), &err_msg)?;
+            }
+        }
+
+        Ok(routes)
+    }
+}
+
+impl Default for AgentBackendDeveloper {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -144,8 +433,8 @@ impl SpecialFunctions for AgentBackendDeveloper {
                     continue;
                 }
                 AgentState::Working => {
-                    if self.bug_count == 0 {
-                        self.call_improved_backend_code(factsheet).await?;
+                    if !self.bugs.has_bugs() {
+                        self.call_reviewed_improvement(factsheet).await?;
                     } else {
                         self.call_fix_code_bugs(factsheet).await?;
                     }
@@ -154,17 +443,71 @@ impl SpecialFunctions for AgentBackendDeveloper {
                     continue;
                 }
                 AgentState::UnitTesting => {
+                    // Tests are written once for the first version and kept while fixing bugs
+                    if !self.bugs.has_bugs() || read_exec_tests_contents().is_empty() {
+                        self.call_write_backend_tests(factsheet).await?;
+                    } else {
+                        attach_backend_tests();
+                    }
+
                     PrintCommand::UnitTest.print_agent_message(
                         self.attributes.get_position(),
                         "Backend Code Unit Testing: Requesting user input",
                     )?;
 
-                    let is_safe_code = confirm_safe_code()?;
+                    // Everything `cargo run` and `cargo test` are about to execute
+                    let mut reviewed_files = vec![("main.rs", read_exec_main_contents())];
+                    let tests_code = read_exec_tests_contents();
+                    if !tests_code.is_empty() {
+                        reviewed_files.push(("main_tests.rs", tests_code));
+                    }
+                    if let Some(design) = factsheet.database_design.as_ref() {
+                        reviewed_files.push(("db.rs", design.data_access_code.clone()));
+                    }
+
+                    let security_review = self
+                        .security_reviewer
+                        .review(
+                            &reviewed_files,
+                            factsheet.external_urls.as_deref().unwrap_or_default(),
+                        )
+                        .await?;
+                    let is_safe_code = confirm_safe_code(&security_review.describe())?;
+                    factsheet.security_review = Some(security_review);
 
                     if !is_safe_code {
                         panic!("Better go work on some AI alignment instead...");
                     }
 
+                    // Align Cargo.toml with the crates used by the code
+                    let template = Self::backend_template(factsheet);
+                    let allowed_dependencies = Self::allowed_dependencies(factsheet);
+                    let dependency_report = update_exec_dependencies(&allowed_dependencies)?;
+
+                    for name in dependency_report.added.iter() {
+                        let added_msg =
+                            format!("Backend Code Unit Testing: added dependency {}", name);
+                        PrintCommand::UnitTest
+                            .print_agent_message(self.attributes.get_position(), &added_msg)?;
+                    }
+
+                    for name in dependency_report.removed.iter() {
+                        let removed_msg =
+                            format!("Backend Code Unit Testing: removed dependency {}", name);
+                        PrintCommand::UnitTest
+                            .print_agent_message(self.attributes.get_position(), &removed_msg)?;
+                    }
+
+                    if dependency_report.has_disallowed() {
+                        let error = dependency_report.describe_disallowed(&allowed_dependencies);
+                        PrintCommand::Issue
+                            .print_agent_message(self.attributes.get_position(), &error)?;
+
+                        self.bugs
+                            .record(&mut self.attributes, "dependencies", error)?;
+                        continue;
+                    }
+
                     // Build and Test Code
                     PrintCommand::UnitTest.print_agent_message(
                         self.attributes.get_position(),
@@ -180,112 +523,311 @@ impl SpecialFunctions for AgentBackendDeveloper {
                         .expect("Failed to build backend server");
 
                     if build_backend_server.status.success() {
-                        self.bug_count = 0;
                         PrintCommand::UnitTest.print_agent_message(
                             self.attributes.get_position(),
                             "Backend Code Unit Testing: Test server build successful...",
                         )?;
                     } else {
                         let error = String::from_utf8(build_backend_server.stderr)?;
-                        self.bug_count += 1;
-                        self.bug_errors = Some(error);
+                        self.bugs.record(&mut self.attributes, "build", error)?;
+                        continue;
+                    }
+
+                    PrintCommand::UnitTest.print_agent_message(
+                        self.attributes.get_position(),
+                        "Backend Code Unit Testing: running generated tests...",
+                    )?;
+
+                    let test_report = run_cargo_tests(WEB_SERVER_PROJECT_PATH)?;
 
-                        if self.bug_count > 2 {
-                            PrintCommand::Issue.print_agent_message(
-                                self.attributes.get_position(),
-                                "Backend Code Unit Testing: Too many bugs found in code",
+                    // Tests that do not compile say nothing about the code, so they are dropped
+                    if test_report.errors_only_in("main_tests.rs") {
+                        PrintCommand::Issue.print_agent_message(
+                            self.attributes.get_position(),
+                            "Backend Code Unit Testing: generated tests do not compile, skipping them",
+                        )?;
+                        save_backend_tests("");
+                        factsheet.backend_test_results = None;
+                    } else {
+                        let tests_msg = format!(
+                            "Backend Code Unit Testing: {} of {} generated tests passed",
+                            test_report.passed_count(),
+                            test_report.tests.len()
+                        );
+                        PrintCommand::UnitTest
+                            .print_agent_message(self.attributes.get_position(), &tests_msg)?;
+
+                        factsheet.backend_test_results = Some(test_report.tests.clone());
+
+                        if let Some(report) = test_report.failure_report() {
+                            PrintCommand::Issue
+                                .print_agent_message(self.attributes.get_position(), &report)?;
+                            self.bugs.record(
+                                &mut self.attributes,
+                                "generated tests",
+                                format!(
+                                    "{} \n GENERATED TESTS: {}",
+                                    report,
+                                    read_exec_tests_contents()
+                                ),
                             )?;
-                            panic!("Error: Too many bugs");
+                            continue;
                         }
-
-                        self.attributes.update_state(AgentState::Working);
-                        continue;
                     }
 
-                    let api_endpoints_str = self.call_extract_rest_api_endpoints().await?;
-                    let api_endpoints =
-                        serde_json::from_str::<Vec<RouteObject>>(&api_endpoints_str)
-                            .expect("Failed to decode API Endpoints");
+                    let mut api_endpoints = self.call_extract_rest_api_endpoints(template).await?;
 
-                    let checked_endpoints = api_endpoints
-                        .iter()
-                        .filter(|route| route.method == "GET" && route.is_route_dynamic == "false")
-                        .cloned()
-                        .collect::<Vec<RouteObject>>();
+                    // The code has to serve exactly the routes the architect planned
+                    if let Some(route_plan) = factsheet.route_plan.as_ref() {
+                        let route_diff = diff_routes(route_plan, &api_endpoints);
+                        factsheet.route_diff = Some(route_diff.clone());
+
+                        if let Some(report) = route_diff.report() {
+                            PrintCommand::Issue
+                                .print_agent_message(self.attributes.get_position(), &report)?;
+                            self.bugs
+                                .record(&mut self.attributes, "route plan", report)?;
+                            continue;
+                        }
+
+                        let plan_msg = format!(
+                            "Backend Code Unit Testing: all {} planned routes implemented",
+                            route_plan.len()
+                        );
+                        PrintCommand::UnitTest
+                            .print_agent_message(self.attributes.get_position(), &plan_msg)?;
+                    }
+
+                    // Auth routes live in auth.rs, which endpoint extraction does not read
+                    if let Some(setup) = factsheet.auth_setup.as_ref() {
+                        for route in setup.routes() {
+                            let is_listed = api_endpoints.iter().any(|listed| {
+                                listed.method == route.method && listed.route == route.route
+                            });
+                            if !is_listed {
+                                api_endpoints.push(route);
+                            }
+                        }
+                    }
 
                     // Store the API Endpoints
-                    factsheet.api_endpoint_schema = Some(checked_endpoints.clone());
+                    factsheet.api_endpoint_schema = Some(api_endpoints.clone());
 
                     PrintCommand::UnitTest.print_agent_message(
                         self.attributes.get_position(),
                         "Backend Code Unit Testing: starting web server...",
                     )?;
 
-                    let mut run_backend_server = Command::new("cargo")
-                        .arg("run")
-                        .current_dir(WEB_SERVER_PROJECT_PATH)
-                        .stdout(Stdio::piped())
-                        .stderr(Stdio::piped())
-                        .spawn()
-                        .expect("Failed to run backend application");
+                    let mut backend_server =
+                        TestServer::start("cargo", &["run"], WEB_SERVER_PROJECT_PATH)?;
+
+                    let waiting_msg = format!(
+                        "Backend Code Unit Testing: waiting for server on port {}...",
+                        backend_server.port()
+                    );
+                    PrintCommand::UnitTest
+                        .print_agent_message(self.attributes.get_position(), &waiting_msg)?;
+
+                    let readiness = backend_server
+                        .wait_until_ready(SERVER_STARTUP_TIMEOUT)
+                        .await?;
+
+                    // A server that never listens is a bug in the code, not a connection error
+                    if readiness != ServerReadiness::Ready {
+                        let error = backend_server.startup_failure(&readiness);
+                        backend_server.stop().await?;
+
+                        PrintCommand::Issue
+                            .print_agent_message(self.attributes.get_position(), &error)?;
+                        self.bugs
+                            .record(&mut self.attributes, "server startup", error)?;
+                        continue;
+                    }
 
-                    PrintCommand::UnitTest.print_agent_message(
-                        self.attributes.get_position(),
-                        "Backend Code Unit Testing: launching tests on server in 5 seconds...",
-                    )?;
+                    let client = Self::endpoint_test_client(None)?;
+
+                    // Protected routes must turn anonymous users away before anything else is tested
+                    let mut auth_results = Vec::new();
+                    let mut session_cookie = None;
+                    if let Some(setup) = factsheet.auth_setup.as_ref() {
+                        let auth_report = run_auth_tests(
+                            &client,
+                            &backend_server.base_url(),
+                            setup,
+                            &api_endpoints,
+                            &read_exec_main_contents(),
+                            self.attributes.get_position(),
+                        )
+                        .await?;
+
+                        if let Some(report) = auth_report.failure_report() {
+                            backend_server.stop().await?;
+
+                            PrintCommand::Issue
+                                .print_agent_message(self.attributes.get_position(), &report)?;
+                            self.bugs.record(&mut self.attributes, "auth", report)?;
+                            continue;
+                        }
+
+                        auth_results = auth_report.results;
+                        session_cookie = auth_report.session_cookie;
+                    }
 
-                    let seconds_sleep = Duration::from_secs(5);
-                    time::sleep(seconds_sleep).await;
+                    let crud_endpoints = api_endpoints
+                        .iter()
+                        .filter(|route| !AuthSetup::is_auth_route(&route.route))
+                        .cloned()
+                        .collect::<Vec<RouteObject>>();
 
-                    let client = Client::builder().timeout(Duration::from_secs(5)).build()?;
+                    // Exercise every endpoint in create -> read -> update -> delete order
+                    let mut test_results = run_endpoint_tests(
+                        &Self::endpoint_test_client(session_cookie.as_deref())?,
+                        &backend_server.base_url(),
+                        &crud_endpoints,
+                        self.attributes.get_position(),
+                    )
+                    .await?;
+                    test_results.extend(auth_results);
+
+                    let failed_count = test_results.iter().filter(|result| !result.passed).count();
+                    if failed_count > 0 {
+                        let err_msg = format!(
+                            "WARNING: {} of {} backend endpoints failed",
+                            failed_count,
+                            test_results.len()
+                        );
+                        PrintCommand::Issue
+                            .print_agent_message(self.attributes.get_position(), &err_msg)?;
+                    }
 
-                    // Check status code
-                    for endpoint in checked_endpoints {
-                        let testing_msg = format!("Testing endpoint '{}'...", endpoint.route);
+                    factsheet.endpoint_test_results = Some(test_results.clone());
+
+                    // The architect's acceptance criteria are checked on the same calls
+                    let acceptance_results = factsheet
+                        .requirements
+                        .as_ref()
+                        .map(|spec| {
+                            check_acceptance_criteria(&spec.acceptance_criteria, &test_results)
+                        })
+                        .unwrap_or_default();
+                    if !acceptance_results.is_empty() {
+                        let count = |outcome: CriterionOutcome| {
+                            acceptance_results
+                                .iter()
+                                .filter(|result| result.outcome == outcome)
+                                .count()
+                        };
+                        let acceptance_msg = format!(
+                            "Backend Code Unit Testing: acceptance criteria {} met, {} unmet, {} not checked by the endpoint tests",
+                            count(CriterionOutcome::Met),
+                            count(CriterionOutcome::Unmet),
+                            count(CriterionOutcome::Unchecked)
+                        );
                         PrintCommand::UnitTest
-                            .print_agent_message(self.attributes.get_position(), &testing_msg)?;
-
-                        // Test url
-                        let url = format!("http://localhost:8080{}", endpoint.route);
-                        match check_status_code(&client, &url).await {
-                            Ok(status_code) => {
-                                if !(200..=299).contains(&status_code) {
-                                    let err_msg = format!(
-                                        "WARNING: Failed to call backend url endpoint {} {}",
-                                        url, status_code
-                                    );
-
-                                    PrintCommand::Issue.print_agent_message(
-                                        self.attributes.get_position(),
-                                        &err_msg,
-                                    )?;
-                                }
-                            }
-                            Err(e) => {
-                                // kill $(lsof -t -i:8080)
-                                run_backend_server
-                                    .kill()
-                                    .expect("Failed to kill backend web server");
-                                let err_msg = format!("Error checking backend {}", e);
-                                PrintCommand::Issue.print_agent_message(
-                                    self.attributes.get_position(),
-                                    &err_msg,
-                                )?;
-                            }
-                        }
+                            .print_agent_message(self.attributes.get_position(), &acceptance_msg)?;
+                    }
+                    factsheet.acceptance_results = Some(acceptance_results.clone());
+
+                    save_api_endpoints(&serde_json::to_string_pretty(&api_endpoints)?);
+
+                    // Downstream tools read the OpenAPI files rather than the internal schema
+                    let openapi = openapi_document(
+                        "web_server",
+                        &factsheet.project_description,
+                        &api_endpoints,
+                    );
+                    let openapi_issues = validate_openapi(&openapi);
+                    if openapi_issues.is_empty() {
+                        save_openapi_files(&openapi)?;
+                    } else {
+                        let err_msg = format!(
+                            "WARNING: OpenAPI document is invalid, not writing it:\n{}",
+                            openapi_issues.join("\n")
+                        );
+                        PrintCommand::Issue
+                            .print_agent_message(self.attributes.get_position(), &err_msg)?;
                     }
 
-                    save_api_endpoints(&api_endpoints_str);
+                    // Every service ships with typed clients, checked against the running server
+                    PrintCommand::UnitTest.print_agent_message(
+                        self.attributes.get_position(),
+                        "Backend Code Unit Testing: building client SDKs...",
+                    )?;
+                    save_client_sdks(&api_endpoints)?;
+                    let smoke_calls =
+                        smoke_calls(&api_endpoints, &client_operations(&api_endpoints));
+
+                    let client_checks = vec![
+                        check_rust_client(
+                            Path::new(RUST_CLIENT_PATH),
+                            &std::env::current_dir()?.join(EXEC_TARGET_PATH),
+                            &backend_server.base_url(),
+                            &smoke_calls,
+                            session_cookie.as_deref(),
+                        )
+                        .await?,
+                        check_typescript_client(
+                            Path::new(TYPESCRIPT_CLIENT_PATH),
+                            &backend_server.base_url(),
+                            &smoke_calls,
+                            session_cookie.as_deref(),
+                        )
+                        .await?,
+                    ];
+
+                    for check in client_checks.iter() {
+                        let summary = format!("Backend Code Unit Testing: {}", check.summary());
+                        PrintCommand::UnitTest
+                            .print_agent_message(self.attributes.get_position(), &summary)?;
+
+                        // A client that does not build is a generator problem, not a backend bug
+                        if let Some(error) = check.build_error.as_ref() {
+                            let err_msg = format!(
+                                "WARNING: {} client could not be built:\n{}",
+                                check.client, error
+                            );
+                            PrintCommand::Issue
+                                .print_agent_message(self.attributes.get_position(), &err_msg)?;
+                        }
+                    }
 
                     PrintCommand::UnitTest.print_agent_message(
                         self.attributes.get_position(),
                         "Backend testing complete...",
                     )?;
 
-                    run_backend_server
-                        .kill()
-                        .expect("Failed to kill backend web server on completion");
+                    // Collect the evidence before the server goes away
+                    let exit_status = backend_server.exit_status()?;
+                    let server_stderr = backend_server.stderr();
+                    backend_server.stop().await?;
+
+                    // Crashes, 5xx responses and schema mismatches all go back to the fix loop
+                    let reports = [
+                        runtime_failure_report(
+                            &test_results,
+                            &server_stderr,
+                            exit_status.as_deref(),
+                        ),
+                        contract_report(&test_results),
+                        acceptance_report(&acceptance_results),
+                        client_failure_report(&client_checks),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<String>>();
+
+                    if !reports.is_empty() {
+                        let report = reports.join("\n\n");
+                        PrintCommand::Issue
+                            .print_agent_message(self.attributes.get_position(), &report)?;
+                        self.bugs
+                            .record(&mut self.attributes, "endpoint checks", report)?;
+                        continue;
+                    }
 
+                    // What devops and docs package and document, taken from the manifest we built
+                    factsheet.server_setup =
+                        Some(ServerSetup::from_manifest(&read_exec_manifest_contents()));
                     self.attributes.update_state(AgentState::Finished);
                 }
                 AgentState::Finished => {}
@@ -314,8 +856,19 @@ impl SpecialFunctions for AgentBackendDeveloper {
 //                 is_external_urls_required: true,
 //             },),
 //             external_urls: Some(vec!["https://api.exchangeratesapi.io/latest?base=USD".to_string(),],),
+//             backend_framework: None,
 //             backend_code: None,
+//             server_setup: None,
 //             api_endpoint_schema: None,
+//             endpoint_test_results: None,
+//             backend_test_results: None,
+//             frontend_code: None,
+//             database_design: None,
+//             auth_setup: None,
+//             security_review: None,
+//             code_review: None,
+//             deployment: None,
+//             project_docs: None,
 //         };
 
 //         agent.execute(&mut factsheet).await?;
@@ -0,0 +1,175 @@
+use super::agent_traits::{FactSheet, SpecialFunctions};
+use crate::{
+    ai_functions::aifunc_database::{
+        print_data_access_code, print_database_design, print_fixed_database_design,
+    },
+    helpers::{
+        command_line::PrintCommand,
+        general::{ai_task_request, ai_task_request_decode, save_database_files},
+        migrations::{migration_runner_code, verify_migrations_on_temp_db},
+    },
+    models::{
+        agent_basic::{
+            basic_agent::{AgentState, BasicAgent},
+            basic_traits::BasicTraits,
+            bug_tracker::BugTracker,
+        },
+        general::database_schema::DatabaseDesign,
+    },
+};
+
+#[derive(Debug)]
+pub struct AgentDatabaseDesigner {
+    attributes: BasicAgent,
+    design: Option<DatabaseDesign>,
+    bugs: BugTracker,
+}
+
+impl AgentDatabaseDesigner {
+    pub fn new() -> Self {
+        let attributes = BasicAgent {
+            objective: "Designs the SQLite schema, migrations and data access code".to_string(),
+            position: "Database Designer".to_string(),
+            state: AgentState::Discovery,
+            memory: Vec::new(),
+        };
+
+        Self {
+            attributes,
+            design: None,
+            bugs: BugTracker::default(),
+        }
+    }
+
+    async fn call_database_design(&mut self, factsheet: &FactSheet) -> anyhow::Result<()> {
+        let msg_context = format!(
+            "PROJECT_DESCRIPTION: {} \n{}",
+            factsheet.project_context(),
+            factsheet.review_feedback_context()
+        );
+
+        let ai_response = ai_task_request_decode::<DatabaseDesign>(
+            &msg_context,
+            self.attributes.get_position(),
+            get_function_string!(print_database_design),
+            print_database_design,
+        )
+        .await?;
+
+        self.design = Some(ai_response);
+
+        Ok(())
+    }
+
+    async fn call_fix_database_design(&mut self, factsheet: &FactSheet) -> anyhow::Result<()> {
+        let msg_context = format!(
+            "PROJECT_DESCRIPTION: {} \n BROKEN_DESIGN: {} \n ERROR_BUGS: {:?}",
+            factsheet.project_context(),
+            serde_json::to_string(&self.design)?,
+            self.bugs.last_error()
+        );
+
+        let ai_response = ai_task_request_decode::<DatabaseDesign>(
+            &msg_context,
+            self.attributes.get_position(),
+            get_function_string!(print_fixed_database_design),
+            print_fixed_database_design,
+        )
+        .await?;
+
+        self.design = Some(ai_response);
+
+        Ok(())
+    }
+
+    async fn call_data_access_code(&mut self, design: &DatabaseDesign) -> anyhow::Result<String> {
+        let msg_context = format!(
+            "TABLES: {} \n MIGRATION_RUNNER: {}",
+            design.describe_tables(),
+            migration_runner_code(&design.migrations)
+        );
+
+        ai_task_request(
+            &msg_context,
+            self.attributes.get_position(),
+            get_function_string!(print_data_access_code),
+            print_data_access_code,
+        )
+        .await
+    }
+}
+
+impl Default for AgentDatabaseDesigner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl SpecialFunctions for AgentDatabaseDesigner {
+    fn get_attributes_from_agent(&self) -> &BasicAgent {
+        &self.attributes
+    }
+
+    async fn execute(&mut self, factsheet: &mut FactSheet) -> anyhow::Result<()> {
+        while self.attributes.get_state() != &AgentState::Finished {
+            match self.attributes.get_state() {
+                AgentState::Discovery => {
+                    let is_curd_required = factsheet
+                        .project_scope
+                        .as_ref()
+                        .is_some_and(|scope| scope.is_curd_required);
+
+                    // Sites without CRUD keep whatever storage the backend template uses
+                    if !is_curd_required {
+                        self.attributes.update_state(AgentState::Finished);
+                        continue;
+                    }
+
+                    self.call_database_design(factsheet).await?;
+                    self.attributes.update_state(AgentState::UnitTesting);
+                }
+                AgentState::Working => {
+                    self.call_fix_database_design(factsheet).await?;
+                    self.attributes.update_state(AgentState::UnitTesting);
+                }
+                AgentState::UnitTesting => {
+                    PrintCommand::UnitTest.print_agent_message(
+                        self.attributes.get_position(),
+                        "Database Migration Check: applying migrations to a temporary database...",
+                    )?;
+
+                    let mut design = self.design.clone().unwrap_or_default();
+                    let issues = verify_migrations_on_temp_db(&design)?;
+
+                    if !issues.is_empty() {
+                        let error = format!("MIGRATION CHECK FAILED:\n{}", issues.join("\n"));
+                        PrintCommand::Issue
+                            .print_agent_message(self.attributes.get_position(), &error)?;
+                        self.bugs
+                            .record(&mut self.attributes, "migrations", error)?;
+                        continue;
+                    }
+
+                    let applied_msg = format!(
+                        "Database Migration Check: {} migration(s) create {} table(s) cleanly",
+                        design.migrations.len(),
+                        design.tables.len()
+                    );
+                    PrintCommand::UnitTest
+                        .print_agent_message(self.attributes.get_position(), &applied_msg)?;
+
+                    // Data access code is only written for a schema known to work
+                    design.data_access_code = self.call_data_access_code(&design).await?;
+                    save_database_files(&design);
+                    factsheet.database_design = Some(design);
+
+                    self.attributes.update_state(AgentState::Finished);
+                }
+                AgentState::Finished => {}
+            }
+        }
+
+        Ok(())
+    }
+}
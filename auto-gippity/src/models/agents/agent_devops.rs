@@ -0,0 +1,129 @@
+use super::agent_traits::{FactSheet, SpecialFunctions};
+use crate::{
+    helpers::{
+        command_line::PrintCommand,
+        devops::{check_deployment_files, deployment_files},
+        general::save_deployment_files,
+    },
+    models::{
+        agent_basic::{
+            basic_agent::{AgentState, BasicAgent},
+            basic_traits::BasicTraits,
+        },
+        general::deployment::DeploymentSetup,
+    },
+};
+
+#[derive(Debug)]
+pub struct AgentDevOps {
+    attributes: BasicAgent,
+    setup: Option<DeploymentSetup>,
+    files: Vec<(&'static str, String)>,
+}
+
+impl AgentDevOps {
+    pub fn new() -> Self {
+        let attributes = BasicAgent {
+            objective: "Packages the finished backend as a container with compose and env docs"
+                .to_string(),
+            position: "DevOps Engineer".to_string(),
+            state: AgentState::Discovery,
+            memory: Vec::new(),
+        };
+
+        Self {
+            attributes,
+            setup: None,
+            files: Vec::new(),
+        }
+    }
+}
+
+impl Default for AgentDevOps {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl SpecialFunctions for AgentDevOps {
+    fn get_attributes_from_agent(&self) -> &BasicAgent {
+        &self.attributes
+    }
+
+    async fn execute(&mut self, factsheet: &mut FactSheet) -> anyhow::Result<()> {
+        while self.attributes.get_state() != &AgentState::Finished {
+            match self.attributes.get_state() {
+                AgentState::Discovery => {
+                    // Binary and port come from the backend developer, who built the server
+                    let Some(server) = factsheet.server_setup.as_ref() else {
+                        PrintCommand::Issue.print_agent_message(
+                            self.attributes.get_position(),
+                            "No server setup in the factsheet, skipping the container files",
+                        )?;
+                        self.attributes.update_state(AgentState::Finished);
+                        continue;
+                    };
+
+                    self.setup = Some(DeploymentSetup::new(server, factsheet));
+                    self.attributes.update_state(AgentState::Working);
+                }
+                AgentState::Working => {
+                    let Some(setup) = self.setup.as_ref() else {
+                        self.attributes.update_state(AgentState::Discovery);
+                        continue;
+                    };
+
+                    self.files = deployment_files(setup)?;
+                    save_deployment_files(&self.files)?;
+
+                    let files_msg = format!(
+                        "Packaged {} on port {}: {}",
+                        setup.binary_name,
+                        setup.port,
+                        self.files
+                            .iter()
+                            .map(|(name, _)| *name)
+                            .collect::<Vec<&str>>()
+                            .join(", ")
+                    );
+                    PrintCommand::AICall
+                        .print_agent_message(self.attributes.get_position(), &files_msg)?;
+                    self.attributes.update_state(AgentState::UnitTesting);
+                }
+                AgentState::UnitTesting => {
+                    let Some(mut setup) = self.setup.take() else {
+                        self.attributes.update_state(AgentState::Discovery);
+                        continue;
+                    };
+
+                    // The files come from fixed templates, so findings point at the generator
+                    setup.issues = check_deployment_files(&setup, &self.files);
+                    setup.files = self
+                        .files
+                        .iter()
+                        .map(|(name, _)| name.to_string())
+                        .collect();
+
+                    for issue in setup.issues.iter() {
+                        let issue_msg = format!("Container Lint: {}", issue);
+                        PrintCommand::Issue
+                            .print_agent_message(self.attributes.get_position(), &issue_msg)?;
+                    }
+                    if setup.issues.is_empty() {
+                        PrintCommand::UnitTest.print_agent_message(
+                            self.attributes.get_position(),
+                            "Container Lint: Dockerfile, compose file and docs agree with the backend",
+                        )?;
+                    }
+
+                    factsheet.deployment = Some(setup);
+                    self.attributes.update_state(AgentState::Finished);
+                }
+                AgentState::Finished => {}
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,167 @@
+use super::agent_traits::{FactSheet, SpecialFunctions};
+use crate::{
+    helpers::{
+        command_line::PrintCommand,
+        general::{save_project_readme, PROJECT_README_PATH, WEB_SERVER_PROJECT_PATH},
+        project_readme::{documented_base_url, project_readme, run_curl_examples},
+        test_server::{ServerReadiness, TestServer},
+    },
+    models::{
+        agent_basic::{
+            basic_agent::{AgentState, BasicAgent},
+            basic_traits::BasicTraits,
+        },
+        general::project_docs::{CurlExample, ProjectDocs},
+    },
+};
+use std::{fs, time::Duration};
+
+// How long `cargo run` gets to start the backend the examples are tried against
+const BACKEND_STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug)]
+pub struct AgentDocsWriter {
+    attributes: BasicAgent,
+    examples: Vec<CurlExample>,
+    // Test server the examples ran against, not the address they are documented for
+    tested_url: Option<String>,
+}
+
+impl AgentDocsWriter {
+    pub fn new() -> Self {
+        let attributes = BasicAgent {
+            objective: "Documents the generated project with examples checked against the server"
+                .to_string(),
+            position: "Documentation Writer".to_string(),
+            state: AgentState::Discovery,
+            memory: Vec::new(),
+        };
+
+        Self {
+            attributes,
+            examples: Vec::new(),
+            tested_url: None,
+        }
+    }
+
+    // Try every curl example against a fresh server, each run gets its own cookie jar
+    async fn exercise_examples(&mut self, factsheet: &FactSheet) -> anyhow::Result<()> {
+        let routes = factsheet.api_endpoint_schema.clone().unwrap_or_default();
+
+        let mut backend_server = TestServer::start("cargo", &["run"], WEB_SERVER_PROJECT_PATH)?;
+        let readiness = backend_server
+            .wait_until_ready(BACKEND_STARTUP_TIMEOUT)
+            .await?;
+        if readiness != ServerReadiness::Ready {
+            let failure = backend_server.startup_failure(&readiness);
+            backend_server.stop().await?;
+            let err_msg = format!(
+                "README Examples: server did not start, no examples documented\n{}",
+                failure
+            );
+            PrintCommand::Issue.print_agent_message(self.attributes.get_position(), &err_msg)?;
+            return Ok(());
+        }
+
+        let work_dir =
+            std::env::temp_dir().join(format!("auto_gippity_docs_{}", std::process::id()));
+        fs::create_dir_all(&work_dir)?;
+
+        let base_url = backend_server.base_url();
+        self.examples =
+            run_curl_examples(&routes, factsheet.auth_setup.as_ref(), &base_url, &work_dir).await;
+        self.tested_url = Some(base_url);
+
+        backend_server.stop().await?;
+        let _ = fs::remove_dir_all(&work_dir);
+
+        Ok(())
+    }
+}
+
+impl Default for AgentDocsWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl SpecialFunctions for AgentDocsWriter {
+    fn get_attributes_from_agent(&self) -> &BasicAgent {
+        &self.attributes
+    }
+
+    async fn execute(&mut self, factsheet: &mut FactSheet) -> anyhow::Result<()> {
+        while self.attributes.get_state() != &AgentState::Finished {
+            match self.attributes.get_state() {
+                AgentState::Discovery => {
+                    if factsheet.backend_code.is_none() {
+                        PrintCommand::Issue.print_agent_message(
+                            self.attributes.get_position(),
+                            "No backend code in the factsheet, skipping the README",
+                        )?;
+                        self.attributes.update_state(AgentState::Finished);
+                        continue;
+                    }
+
+                    self.attributes.update_state(AgentState::UnitTesting);
+                }
+                AgentState::UnitTesting => {
+                    PrintCommand::UnitTest.print_agent_message(
+                        self.attributes.get_position(),
+                        "README Examples: running curl examples against the backend...",
+                    )?;
+                    self.exercise_examples(factsheet).await?;
+
+                    for example in self.examples.iter().filter(|example| !example.passed()) {
+                        let err_msg = format!(
+                            "README Examples: left out {} {}, got {}",
+                            example.method,
+                            example.path,
+                            example
+                                .error
+                                .clone()
+                                .or(example.status.map(|status| format!("status {}", status)))
+                                .unwrap_or_default()
+                        );
+                        PrintCommand::Issue
+                            .print_agent_message(self.attributes.get_position(), &err_msg)?;
+                    }
+
+                    self.attributes.update_state(AgentState::Working);
+                }
+                AgentState::Working => {
+                    let title = factsheet
+                        .server_setup
+                        .as_ref()
+                        .map(|server| server.binary_name.clone())
+                        .unwrap_or_else(|| "web_server".to_string());
+
+                    let readme = project_readme(factsheet, &title, &self.examples);
+                    save_project_readme(&readme)?;
+
+                    let docs = ProjectDocs {
+                        readme_path: PROJECT_README_PATH.to_string(),
+                        examples: self.examples.clone(),
+                    };
+                    let written_msg = format!(
+                        "Wrote {} with {} of {} curl examples for {}, checked against {}",
+                        PROJECT_README_PATH,
+                        docs.examples.len() - docs.failed_examples().len(),
+                        docs.examples.len(),
+                        documented_base_url(),
+                        self.tested_url.as_deref().unwrap_or("no server")
+                    );
+                    PrintCommand::AICall
+                        .print_agent_message(self.attributes.get_position(), &written_msg)?;
+
+                    factsheet.project_docs = Some(docs);
+                    self.attributes.update_state(AgentState::Finished);
+                }
+                AgentState::Finished => {}
+            }
+        }
+
+        Ok(())
+    }
+}
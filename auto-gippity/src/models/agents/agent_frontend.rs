@@ -0,0 +1,200 @@
+use super::agent_traits::{FactSheet, SpecialFunctions};
+use crate::{
+    ai_functions::aifunc_frontend::{print_fixed_frontend_code, print_frontend_code},
+    helpers::{
+        command_line::PrintCommand,
+        frontend_check::{run_frontend_call_check, serve_directory},
+        general::{
+            ai_task_request, save_frontend_code, FRONTEND_PROJECT_PATH, WEB_SERVER_PROJECT_PATH,
+        },
+        test_server::{ServerReadiness, TestServer},
+    },
+    models::agent_basic::{
+        basic_agent::{AgentState, BasicAgent},
+        basic_traits::BasicTraits,
+        bug_tracker::BugTracker,
+    },
+};
+use reqwest::Client;
+use std::time::Duration;
+
+// How long `cargo run` gets to start the backend the page talks to
+const BACKEND_STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug)]
+pub struct AgentFrontendDeveloper {
+    attributes: BasicAgent,
+    bugs: BugTracker,
+}
+
+impl AgentFrontendDeveloper {
+    pub fn new() -> Self {
+        let attributes = BasicAgent {
+            objective: "Develops a static frontend that talks to the backend endpoints".to_string(),
+            position: "Frontend Developer".to_string(),
+            state: AgentState::Discovery,
+            memory: Vec::new(),
+        };
+
+        Self {
+            attributes,
+            bugs: BugTracker::default(),
+        }
+    }
+
+    fn api_schema_context(factsheet: &FactSheet) -> anyhow::Result<String> {
+        Ok(serde_json::to_string(&factsheet.api_endpoint_schema)?)
+    }
+
+    async fn call_initial_frontend_code(
+        &mut self,
+        factsheet: &mut FactSheet,
+    ) -> anyhow::Result<()> {
+        let msg_context = format!(
+            "PROJECT_DESCRIPTION: {} \n API_ENDPOINT_SCHEMA: {}{}",
+            factsheet.project_context(),
+            Self::api_schema_context(factsheet)?,
+            factsheet.review_feedback_context()
+        );
+
+        let ai_response = ai_task_request(
+            &msg_context,
+            self.attributes.get_position(),
+            get_function_string!(print_frontend_code),
+            print_frontend_code,
+        )
+        .await?;
+
+        save_frontend_code(&ai_response);
+        factsheet.frontend_code = Some(ai_response);
+
+        Ok(())
+    }
+
+    async fn call_fix_frontend_bugs(&mut self, factsheet: &mut FactSheet) -> anyhow::Result<()> {
+        let msg_context = format!(
+            "BROKEN_PAGE: {:?} \n API_ENDPOINT_SCHEMA: {} \n ERROR_BUGS: {:?} \n
+            THIS FUNCTION ONLY OUTPUTS CODE. JUST OUTPUT THE CODE",
+            factsheet.frontend_code,
+            Self::api_schema_context(factsheet)?,
+            self.bugs.last_error()
+        );
+
+        let ai_response = ai_task_request(
+            &msg_context,
+            self.attributes.get_position(),
+            get_function_string!(print_fixed_frontend_code),
+            print_fixed_frontend_code,
+        )
+        .await?;
+
+        save_frontend_code(&ai_response);
+        factsheet.frontend_code = Some(ai_response);
+
+        Ok(())
+    }
+}
+
+impl Default for AgentFrontendDeveloper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl SpecialFunctions for AgentFrontendDeveloper {
+    fn get_attributes_from_agent(&self) -> &BasicAgent {
+        &self.attributes
+    }
+
+    async fn execute(&mut self, factsheet: &mut FactSheet) -> anyhow::Result<()> {
+        while self.attributes.get_state() != &AgentState::Finished {
+            match self.attributes.get_state() {
+                AgentState::Discovery => {
+                    // Without endpoints there is nothing for the page to talk to
+                    if factsheet.api_endpoint_schema.is_none() {
+                        PrintCommand::Issue.print_agent_message(
+                            self.attributes.get_position(),
+                            "No API endpoint schema in the factsheet, skipping the frontend",
+                        )?;
+                        self.attributes.update_state(AgentState::Finished);
+                        continue;
+                    }
+
+                    self.call_initial_frontend_code(factsheet).await?;
+                    self.attributes.update_state(AgentState::UnitTesting);
+                }
+                AgentState::Working => {
+                    self.call_fix_frontend_bugs(factsheet).await?;
+                    self.attributes.update_state(AgentState::UnitTesting);
+                }
+                AgentState::UnitTesting => {
+                    PrintCommand::UnitTest.print_agent_message(
+                        self.attributes.get_position(),
+                        "Frontend Call Check: starting backend and static file server...",
+                    )?;
+
+                    let mut backend_server =
+                        TestServer::start("cargo", &["run"], WEB_SERVER_PROJECT_PATH)?;
+                    let readiness = backend_server
+                        .wait_until_ready(BACKEND_STARTUP_TIMEOUT)
+                        .await?;
+
+                    // The page can still be checked against the schema without a live backend
+                    let backend_url = match readiness {
+                        ServerReadiness::Ready => Some(backend_server.base_url()),
+                        _ => {
+                            PrintCommand::Issue.print_agent_message(
+                                self.attributes.get_position(),
+                                "Frontend Call Check: backend did not start, only matching calls to routes",
+                            )?;
+                            None
+                        }
+                    };
+
+                    let (frontend_url, frontend_server) =
+                        serve_directory(FRONTEND_PROJECT_PATH).await?;
+                    let client = Client::builder().timeout(Duration::from_secs(5)).build()?;
+                    let routes = factsheet.api_endpoint_schema.clone().unwrap_or_default();
+
+                    let report = run_frontend_call_check(
+                        &client,
+                        &frontend_url,
+                        backend_url.as_deref(),
+                        &routes,
+                    )
+                    .await;
+
+                    frontend_server.abort();
+                    backend_server.stop().await?;
+                    let report = report?;
+
+                    let checked_msg = format!(
+                        "Frontend Call Check: read {} file(s), found {} fetch call(s)",
+                        report.loaded_files.len(),
+                        report.fetch_calls.len()
+                    );
+                    PrintCommand::UnitTest
+                        .print_agent_message(self.attributes.get_position(), &checked_msg)?;
+
+                    if let Some(failure) = report.failure_report() {
+                        PrintCommand::Issue
+                            .print_agent_message(self.attributes.get_position(), &failure)?;
+                        self.bugs
+                            .record(&mut self.attributes, "page check", failure)?;
+                        continue;
+                    }
+
+                    PrintCommand::UnitTest.print_agent_message(
+                        self.attributes.get_position(),
+                        "Frontend Call Check: page files load and every fetch call matches a backend route",
+                    )?;
+                    self.attributes.update_state(AgentState::Finished);
+                }
+                AgentState::Finished => {}
+            }
+        }
+
+        Ok(())
+    }
+}
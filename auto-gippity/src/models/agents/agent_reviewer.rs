@@ -0,0 +1,109 @@
+use super::agent_traits::FactSheet;
+use crate::{
+    ai_functions::aifunc_reviewer::{print_code_review, print_review_verification},
+    helpers::{command_line::PrintCommand, general::ai_task_request_decode},
+    models::{
+        agent_basic::{
+            basic_agent::{AgentState, BasicAgent},
+            basic_traits::BasicTraits,
+        },
+        general::code_review::{CodeReview, ReviewItem, ReviewVerification},
+    },
+};
+
+// Critiques backend drafts against the requirements, called by the backend developer
+// between its first draft and the improvement step
+#[derive(Debug)]
+pub struct AgentCodeReviewer {
+    attributes: BasicAgent,
+}
+
+impl AgentCodeReviewer {
+    pub fn new() -> Self {
+        let attributes = BasicAgent {
+            objective: "Reviews backend code against the project requirements".to_string(),
+            position: "Code Reviewer".to_string(),
+            state: AgentState::Discovery,
+            memory: Vec::new(),
+        };
+
+        Self { attributes }
+    }
+
+    // Gaps and defects of `code` measured against the FactSheet
+    pub async fn review(&self, factsheet: &FactSheet, code: &str) -> anyhow::Result<CodeReview> {
+        // The code under review is sent once, not again inside the FactSheet
+        let requirements = FactSheet {
+            backend_code: None,
+            ..factsheet.clone()
+        };
+
+        let msg_context = format!(
+            "PROJECT_DESCRIPTION: {} \n FACT_SHEET: {} \n CODE_INPUT: {}",
+            factsheet.project_context(),
+            serde_json::to_string(&requirements)?,
+            code
+        );
+
+        let items = ai_task_request_decode::<Vec<ReviewItem>>(
+            &msg_context,
+            self.attributes.get_position(),
+            get_function_string!(print_code_review),
+            print_code_review,
+        )
+        .await?;
+
+        let review = CodeReview::new(items);
+        let review_msg = format!(
+            "Code Review: {} item(s) to address\n{}",
+            review.items.len(),
+            CodeReview::checklist(&review.items.iter().collect::<Vec<&ReviewItem>>())
+        );
+        PrintCommand::AICall.print_agent_message(self.attributes.get_position(), &review_msg)?;
+
+        Ok(review)
+    }
+
+    // Check each item of the review against the improved code
+    pub async fn verify(
+        &self,
+        review: &CodeReview,
+        code: &str,
+    ) -> anyhow::Result<Vec<ReviewVerification>> {
+        let items = review.items.iter().collect::<Vec<&ReviewItem>>();
+        let msg_context = format!(
+            "REVIEW_CHECKLIST: {} \n IMPROVED_CODE: {}",
+            CodeReview::checklist(&items),
+            code
+        );
+
+        let verification = ai_task_request_decode::<Vec<ReviewVerification>>(
+            &msg_context,
+            self.attributes.get_position(),
+            get_function_string!(print_review_verification),
+            print_review_verification,
+        )
+        .await?;
+
+        for check in verification.iter() {
+            let status = match check.addressed {
+                true => "addressed",
+                false => "NOT addressed",
+            };
+            let check_msg = format!("Code Review: {} {}: {}", check.id, status, check.note);
+            let command = match check.addressed {
+                true => PrintCommand::UnitTest,
+                false => PrintCommand::Issue,
+            };
+            command.print_agent_message(self.attributes.get_position(), &check_msg)?;
+        }
+
+        Ok(verification)
+    }
+}
+
+impl Default for AgentCodeReviewer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
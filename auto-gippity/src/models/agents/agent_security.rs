@@ -0,0 +1,108 @@
+use crate::{
+    ai_functions::aifunc_security::print_security_review,
+    helpers::{
+        command_line::PrintCommand, general::ai_task_request_decode,
+        security_scan::scan_generated_code,
+    },
+    models::{
+        agent_basic::{
+            basic_agent::{AgentState, BasicAgent},
+            basic_traits::BasicTraits,
+        },
+        general::security_review::{FindingSource, SecurityFinding, SecurityReport},
+    },
+};
+
+// Reviews generated code right before a human is asked to run it, so it is called
+// by the agent that wants approval rather than scheduled by the manager
+#[derive(Debug)]
+pub struct AgentSecurityReviewer {
+    attributes: BasicAgent,
+}
+
+impl AgentSecurityReviewer {
+    pub fn new() -> Self {
+        let attributes = BasicAgent {
+            objective: "Reviews generated code for security problems before it runs".to_string(),
+            position: "Security Reviewer".to_string(),
+            state: AgentState::Discovery,
+            memory: Vec::new(),
+        };
+
+        Self { attributes }
+    }
+
+    async fn call_security_review(
+        &self,
+        files: &[(&str, String)],
+        external_urls: &[String],
+        static_findings: &[SecurityFinding],
+    ) -> anyhow::Result<Vec<SecurityFinding>> {
+        let files = files
+            .iter()
+            .map(|(name, code)| format!("--- {} ---\n{}", name, code))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let msg_context = format!(
+            "FILES: {} \n EXTERNAL_URLS: {:?} \n STATIC_FINDINGS: {}",
+            files,
+            external_urls,
+            serde_json::to_string(static_findings)?
+        );
+
+        let mut findings = ai_task_request_decode::<Vec<SecurityFinding>>(
+            &msg_context,
+            self.attributes.get_position(),
+            get_function_string!(print_security_review),
+            print_security_review,
+        )
+        .await?;
+
+        for finding in findings.iter_mut() {
+            finding.source = FindingSource::Llm;
+        }
+
+        Ok(findings)
+    }
+
+    // Static checks always run, the LLM review adds to them when it succeeds
+    pub async fn review(
+        &self,
+        files: &[(&str, String)],
+        external_urls: &[String],
+    ) -> anyhow::Result<SecurityReport> {
+        PrintCommand::UnitTest.print_agent_message(
+            self.attributes.get_position(),
+            "Security Review: checking generated code...",
+        )?;
+
+        let mut report = SecurityReport::default();
+        for (file, code) in files.iter() {
+            report
+                .findings
+                .extend(scan_generated_code(file, code, external_urls));
+        }
+
+        match self
+            .call_security_review(files, external_urls, &report.findings)
+            .await
+        {
+            Ok(findings) => report.findings.extend(findings),
+            Err(e) => {
+                let err_msg = format!("Security Review: LLM review failed: {}", e);
+                PrintCommand::Issue
+                    .print_agent_message(self.attributes.get_position(), &err_msg)?;
+                report.llm_review_error = Some(e.to_string());
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+impl Default for AgentSecurityReviewer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
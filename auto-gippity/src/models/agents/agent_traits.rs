@@ -1,35 +1,279 @@
-use crate::models::agent_basic::basic_agent::BasicAgent;
+use crate::{
+    helpers::{cargo_testing::TestCaseResult, route_diff::RouteDiff},
+    models::{
+        agent_basic::basic_agent::BasicAgent,
+        general::{
+            api_schema::{deserialize_lenient_bool, FieldSchema, HttpMethod},
+            auth_schema::AuthSetup,
+            clarification::{describe_requirements, ClarifiedRequirement},
+            code_review::CodeReview,
+            database_schema::DatabaseDesign,
+            deployment::{DeploymentSetup, ServerSetup},
+            project_docs::ProjectDocs,
+            project_review::ProjectReview,
+            requirements::{AcceptanceResult, RequirementsSpec},
+            security_review::SecurityReport,
+        },
+        templates::template_traits::BackendFramework,
+    },
+};
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct RouteObject {
-    pub is_route_dynamic: String,
-    pub method: String,
-    pub request_body: serde_json::Value,
-    pub response: serde_json::Value,
+    #[serde(deserialize_with = "deserialize_lenient_bool")]
+    pub is_route_dynamic: bool,
+    pub method: HttpMethod,
+    #[serde(default)]
+    pub request_body: FieldSchema,
+    #[serde(default)]
+    pub response: FieldSchema,
     pub route: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub handler: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dynamic_segments: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extractors: Vec<String>,
+}
+
+// Routes for the tests, bodies and responses written the way the LLM writes them
+#[cfg(test)]
+impl RouteObject {
+    pub fn test(method: HttpMethod, path: &str) -> Self {
+        Self {
+            is_route_dynamic: path.contains(['{', '<']),
+            method,
+            request_body: FieldSchema::None,
+            response: FieldSchema::NotProvided,
+            route: path.to_string(),
+            handler: None,
+            dynamic_segments: Vec::new(),
+            extractors: Vec::new(),
+        }
+    }
+
+    pub fn with_handler(mut self, handler: &str) -> Self {
+        self.handler = Some(handler.to_string());
+        self
+    }
+
+    pub fn with_body(mut self, body: serde_json::Value) -> Self {
+        self.request_body = FieldSchema::from_value(&body);
+        self
+    }
+
+    pub fn with_response(mut self, response: serde_json::Value) -> Self {
+        self.response = FieldSchema::from_value(&response);
+        self
+    }
+}
+
+// Place where a live response differs from the schema recorded for its route
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ContractViolation {
+    pub path: String,
+    pub expected: String,
+    pub found: String,
+}
+
+// Outcome of calling one endpoint of the generated server
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EndpointTestResult {
+    pub method: HttpMethod,
+    pub route: String,
+    pub url: String,
+    pub status: Option<u16>,
+    pub passed: bool,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub contract_violations: Vec<ContractViolation>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
 pub struct ProjectScope {
+    #[serde(deserialize_with = "deserialize_lenient_bool")]
     pub is_curd_required: bool,
+    #[serde(deserialize_with = "deserialize_lenient_bool")]
     pub is_user_login_and_logout: bool,
+    #[serde(deserialize_with = "deserialize_lenient_bool")]
     pub is_external_urls_required: bool,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
 pub struct FactSheet {
     pub project_description: String,
+    // Answers the user gave to the manager's clarifying questions
+    pub clarified_requirements: Option<Vec<ClarifiedRequirement>>,
     pub project_scope: Option<ProjectScope>,
+    // Entities, user stories, non-functional needs and acceptance criteria from the architect
+    pub requirements: Option<RequirementsSpec>,
+    // Routes the architect planned before any code was written
+    pub route_plan: Option<Vec<RouteObject>>,
     pub external_urls: Option<Vec<String>>,
+    pub backend_framework: Option<BackendFramework>,
     pub backend_code: Option<String>,
+    pub server_setup: Option<ServerSetup>,
     pub api_endpoint_schema: Option<Vec<RouteObject>>,
+    pub route_diff: Option<RouteDiff>,
+    pub endpoint_test_results: Option<Vec<EndpointTestResult>>,
+    pub acceptance_results: Option<Vec<AcceptanceResult>>,
+    pub backend_test_results: Option<Vec<TestCaseResult>>,
+    pub frontend_code: Option<String>,
+    pub database_design: Option<DatabaseDesign>,
+    pub auth_setup: Option<AuthSetup>,
+    pub security_review: Option<SecurityReport>,
+    pub code_review: Option<CodeReview>,
+    pub deployment: Option<DeploymentSetup>,
+    pub project_docs: Option<ProjectDocs>,
+    // Latest review of the whole project by the manager
+    pub project_review: Option<ProjectReview>,
+    // What the manager asked the running agent to fix, set on its own copy only
+    pub review_feedback: Option<String>,
 }
 
+// Names a FactSheet field, so agents can declare what they read and write
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FactSheetField {
+    ProjectDescription,
+    ProjectScope,
+    ExternalUrls,
+    BackendFramework,
+    BackendCode,
+    ServerSetup,
+    ApiEndpointSchema,
+    RouteDiff,
+    EndpointTestResults,
+    AcceptanceResults,
+    BackendTestResults,
+    FrontendCode,
+    DatabaseDesign,
+    AuthSetup,
+    SecurityReview,
+    CodeReview,
+    Deployment,
+    ProjectDocs,
+}
+
+impl FactSheet {
+    // Project description together with the clarified requirements, as given to the agents
+    pub fn project_context(&self) -> String {
+        match self.clarified_requirements.as_deref() {
+            Some(requirements) if !requirements.is_empty() => format!(
+                "{} \n CLARIFIED_REQUIREMENTS: {}",
+                self.project_description,
+                describe_requirements(requirements)
+            ),
+            _ => self.project_description.clone(),
+        }
+    }
+
+    // Prompt section with the manager's feedback, empty on the first run
+    pub fn review_feedback_context(&self) -> String {
+        match self.review_feedback.as_ref() {
+            Some(feedback) => format!(" REVIEW_FEEDBACK: {} \n", feedback),
+            None => String::new(),
+        }
+    }
+
+    // Take one field over from the FactSheet an agent worked on
+    pub fn copy_field(&mut self, from: &FactSheet, field: FactSheetField) {
+        match field {
+            FactSheetField::ProjectDescription => {
+                self.project_description = from.project_description.clone()
+            }
+            FactSheetField::ProjectScope => self.project_scope = from.project_scope,
+            FactSheetField::ExternalUrls => self.external_urls = from.external_urls.clone(),
+            FactSheetField::BackendFramework => self.backend_framework = from.backend_framework,
+            FactSheetField::BackendCode => self.backend_code = from.backend_code.clone(),
+            FactSheetField::ServerSetup => self.server_setup = from.server_setup.clone(),
+            FactSheetField::ApiEndpointSchema => {
+                self.api_endpoint_schema = from.api_endpoint_schema.clone()
+            }
+            FactSheetField::RouteDiff => self.route_diff = from.route_diff.clone(),
+            FactSheetField::EndpointTestResults => {
+                self.endpoint_test_results = from.endpoint_test_results.clone()
+            }
+            FactSheetField::AcceptanceResults => {
+                self.acceptance_results = from.acceptance_results.clone()
+            }
+            FactSheetField::BackendTestResults => {
+                self.backend_test_results = from.backend_test_results.clone()
+            }
+            FactSheetField::FrontendCode => self.frontend_code = from.frontend_code.clone(),
+            FactSheetField::DatabaseDesign => self.database_design = from.database_design.clone(),
+            FactSheetField::AuthSetup => self.auth_setup = from.auth_setup.clone(),
+            FactSheetField::SecurityReview => self.security_review = from.security_review.clone(),
+            FactSheetField::CodeReview => self.code_review = from.code_review.clone(),
+            FactSheetField::Deployment => self.deployment = from.deployment.clone(),
+            FactSheetField::ProjectDocs => self.project_docs = from.project_docs.clone(),
+        }
+    }
+}
+
+// Agents run on their own tokio task, so they have to be Send
 #[async_trait::async_trait]
-pub trait SpecialFunctions: Debug {
+pub trait SpecialFunctions: Debug + Send {
     fn get_attributes_from_agent(&self) -> &BasicAgent;
 
     async fn execute(&mut self, factsheet: &mut FactSheet) -> anyhow::Result<()>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_decodes_llm_route_object() -> anyhow::Result<()> {
+        let route = serde_json::from_value::<RouteObject>(json!({
+            "route": "/item/{id}",
+            "is_route_dynamic": "true",
+            "method": "get",
+            "request_body": "None",
+            "response": { "id": "number", "name": "string" }
+        }))?;
+
+        assert!(route.is_route_dynamic);
+        assert_eq!(route.method, HttpMethod::Get);
+        assert!(route.request_body.is_none());
+        assert!(matches!(route.response, FieldSchema::Object(_)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_route_object_round_trip() -> anyhow::Result<()> {
+        let route = RouteObject {
+            is_route_dynamic: false,
+            method: HttpMethod::Post,
+            request_body: FieldSchema::from_value(&json!({ "name": "string" })),
+            response: FieldSchema::NotProvided,
+            route: "/item".to_string(),
+            handler: Some("create_item".to_string()),
+            dynamic_segments: Vec::new(),
+            extractors: vec!["web::Json<Item>".to_string()],
+        };
+
+        let encoded = serde_json::to_string(&route)?;
+        assert_eq!(serde_json::from_str::<RouteObject>(&encoded)?, route);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decodes_quoted_project_scope() -> anyhow::Result<()> {
+        let scope = serde_json::from_value::<ProjectScope>(json!({
+            "is_curd_required": "true",
+            "is_user_login_and_logout": false,
+            "is_external_urls_required": "False"
+        }))?;
+
+        assert!(scope.is_curd_required);
+        assert!(!scope.is_user_login_and_logout);
+        assert!(!scope.is_external_urls_required);
+
+        Ok(())
+    }
+}
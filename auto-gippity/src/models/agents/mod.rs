@@ -1,3 +1,10 @@
 pub mod agent_architect;
+pub mod agent_auth;
 pub mod agent_backend;
+pub mod agent_database;
+pub mod agent_devops;
+pub mod agent_docs;
+pub mod agent_frontend;
+pub mod agent_reviewer;
+pub mod agent_security;
 pub mod agent_traits;
@@ -0,0 +1,267 @@
+use crate::models::agents::{
+    agent_auth::AgentAuthSpecialist,
+    agent_backend::AgentBackendDeveloper,
+    agent_database::AgentDatabaseDesigner,
+    agent_devops::AgentDevOps,
+    agent_docs::AgentDocsWriter,
+    agent_frontend::AgentFrontendDeveloper,
+    agent_traits::{FactSheetField, ProjectScope, SpecialFunctions},
+};
+
+// Agent the managing agent can put on a project, looked up by its role name
+#[derive(Debug)]
+pub struct AgentRole {
+    pub name: &'static str,
+    pub description: &'static str,
+    // FactSheet fields the agent works from, the roles writing them run first
+    pub reads: &'static [FactSheetField],
+    // FactSheet fields taken over from the agent once it finishes
+    pub writes: &'static [FactSheetField],
+    // Starts the generated server in template/, whose data files every running copy shares
+    pub runs_server: bool,
+    // Works the manager's review feedback into its prompts, the others would only rerun unchanged
+    pub takes_feedback: bool,
+    pub create: fn() -> Box<dyn SpecialFunctions>,
+}
+
+pub const DATABASE_DESIGNER: &str = "database_designer";
+pub const AUTH_SPECIALIST: &str = "auth_specialist";
+pub const BACKEND_DEVELOPER: &str = "backend_developer";
+pub const FRONTEND_DEVELOPER: &str = "frontend_developer";
+pub const DEVOPS_ENGINEER: &str = "devops_engineer";
+pub const DOCUMENTATION_WRITER: &str = "documentation_writer";
+
+use FactSheetField::*;
+
+const ROLES: &[AgentRole] = &[
+    AgentRole {
+        name: DATABASE_DESIGNER,
+        description: "designs SQLite tables, migrations and data access code for CRUD projects",
+        reads: &[ProjectDescription, ProjectScope],
+        writes: &[DatabaseDesign],
+        runs_server: false,
+        takes_feedback: true,
+        create: || Box::new(AgentDatabaseDesigner::new()),
+    },
+    AgentRole {
+        name: AUTH_SPECIALIST,
+        description: "adds vetted session authentication when users log in and out",
+        reads: &[ProjectScope, BackendFramework],
+        writes: &[AuthSetup],
+        runs_server: false,
+        takes_feedback: false,
+        create: || Box::new(AgentAuthSpecialist::new()),
+    },
+    AgentRole {
+        name: BACKEND_DEVELOPER,
+        description: "writes, tests and fixes the Rust web server and its endpoints",
+        reads: &[
+            ProjectDescription,
+            ExternalUrls,
+            BackendFramework,
+            DatabaseDesign,
+            AuthSetup,
+        ],
+        writes: &[
+            BackendCode,
+            ServerSetup,
+            ApiEndpointSchema,
+            RouteDiff,
+            EndpointTestResults,
+            AcceptanceResults,
+            BackendTestResults,
+            SecurityReview,
+            CodeReview,
+        ],
+        runs_server: true,
+        takes_feedback: true,
+        create: || Box::new(AgentBackendDeveloper::new()),
+    },
+    AgentRole {
+        name: FRONTEND_DEVELOPER,
+        description: "writes a static HTML/JS page that talks to the backend endpoints",
+        reads: &[ProjectDescription, ApiEndpointSchema],
+        writes: &[FrontendCode],
+        runs_server: true,
+        takes_feedback: true,
+        create: || Box::new(AgentFrontendDeveloper::new()),
+    },
+    AgentRole {
+        name: DEVOPS_ENGINEER,
+        description: "packages the backend with a Dockerfile, compose file and env docs",
+        // Compose only serves the page the frontend developer actually wrote
+        reads: &[ExternalUrls, ServerSetup, FrontendCode],
+        writes: &[Deployment],
+        runs_server: false,
+        takes_feedback: false,
+        create: || Box::new(AgentDevOps::new()),
+    },
+    AgentRole {
+        name: DOCUMENTATION_WRITER,
+        description: "writes the project README with endpoint table and tested curl examples",
+        reads: &[
+            ProjectDescription,
+            ExternalUrls,
+            BackendCode,
+            ApiEndpointSchema,
+            AuthSetup,
+            ServerSetup,
+            // The README only links DEPLOYMENT.md once devops wrote it
+            Deployment,
+        ],
+        writes: &[ProjectDocs],
+        runs_server: true,
+        takes_feedback: false,
+        create: || Box::new(AgentDocsWriter::new()),
+    },
+];
+
+impl AgentRole {
+    // Whether this role works from a field `other` writes
+    pub fn reads_from(&self, other: &AgentRole) -> bool {
+        self.reads.iter().any(|field| other.writes.contains(field))
+    }
+}
+
+// Role name as written by a user or an LLM, i.e. "Backend Developer" or "\"backend-developer\""
+fn normalize_role_name(name: &str) -> String {
+    name.trim()
+        .trim_matches('"')
+        .trim()
+        .to_lowercase()
+        .replace([' ', '-'], "_")
+}
+
+pub fn find_role(name: &str) -> Option<&'static AgentRole> {
+    let name = normalize_role_name(name);
+    ROLES.iter().find(|role| role.name == name)
+}
+
+// Roles and what they do, as listed in the planning prompt
+pub fn role_catalogue() -> String {
+    ROLES
+        .iter()
+        .map(|role| format!("{}: {}", role.name, role.description))
+        .collect::<Vec<String>>()
+        .join("; ")
+}
+
+// Roles the scope cannot do without, whatever the plan says
+pub fn required_roles(scope: &ProjectScope) -> Vec<&'static str> {
+    let mut roles = Vec::new();
+    if scope.is_curd_required {
+        roles.push(DATABASE_DESIGNER);
+    }
+    if scope.is_user_login_and_logout {
+        roles.push(AUTH_SPECIALIST);
+    }
+    if scope.is_curd_required || scope.is_user_login_and_logout || scope.is_external_urls_required {
+        roles.push(BACKEND_DEVELOPER);
+    }
+    roles
+}
+
+// Team used when planning fails: the specialists the scope needs, then the full build
+pub fn default_roster(scope: &ProjectScope) -> Vec<String> {
+    required_roles(scope)
+        .into_iter()
+        .chain([
+            BACKEND_DEVELOPER,
+            FRONTEND_DEVELOPER,
+            DEVOPS_ENGINEER,
+            DOCUMENTATION_WRITER,
+        ])
+        .map(|name| name.to_string())
+        .collect()
+}
+
+// Known roles of a planned roster plus the required ones, each once, ordered so every role runs
+// after the roles whose output it reads. Unknown names are returned separately.
+pub fn resolve_roster(
+    planned: &[String],
+    scope: &ProjectScope,
+) -> (Vec<&'static AgentRole>, Vec<String>) {
+    let mut team: Vec<&'static AgentRole> = Vec::new();
+    let mut unknown = Vec::new();
+
+    let requested = planned
+        .iter()
+        .map(|name| name.as_str())
+        .chain(required_roles(scope));
+    for name in requested {
+        match find_role(name) {
+            Some(role) if !team.iter().any(|known| known.name == role.name) => team.push(role),
+            Some(_) => {}
+            None => unknown.push(name.to_string()),
+        }
+    }
+
+    // Keep the planned order, except where a role would run before one writing what it reads
+    let mut ordered: Vec<&'static AgentRole> = Vec::new();
+    while ordered.len() < team.len() {
+        let next = team
+            .iter()
+            .find(|role| {
+                !ordered.iter().any(|placed| placed.name == role.name)
+                    && team.iter().all(|member| {
+                        member.name == role.name
+                            || !role.reads_from(member)
+                            || ordered.iter().any(|placed| placed.name == member.name)
+                    })
+            })
+            .copied()
+            .expect("Agent roles have no circular reads");
+        ordered.push(next);
+    }
+
+    (ordered, unknown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(roles: &[&AgentRole]) -> Vec<&'static str> {
+        roles.iter().map(|role| role.name).collect()
+    }
+
+    #[test]
+    fn test_resolves_planned_roster() {
+        let scope = ProjectScope {
+            is_curd_required: true,
+            is_user_login_and_logout: false,
+            is_external_urls_required: false,
+        };
+        let planned = [
+            "Documentation Writer",
+            "frontend-developer",
+            "\"backend_developer\"",
+            "designer",
+            "frontend_developer",
+        ]
+        .map(|name| name.to_string());
+
+        let (team, unknown) = resolve_roster(&planned, &scope);
+        assert_eq!(
+            names(&team),
+            vec![
+                DATABASE_DESIGNER,
+                BACKEND_DEVELOPER,
+                DOCUMENTATION_WRITER,
+                FRONTEND_DEVELOPER
+            ]
+        );
+        assert_eq!(unknown, vec!["designer"]);
+
+        // Every role can be created, and the default team orders itself the same way
+        let (team, unknown) = resolve_roster(&default_roster(&scope), &scope);
+        assert!(unknown.is_empty());
+        assert_eq!(names(&team)[..2], [DATABASE_DESIGNER, BACKEND_DEVELOPER]);
+        for role in team {
+            assert!(!(role.create)()
+                .get_attributes_from_agent()
+                .position
+                .is_empty());
+        }
+    }
+}
@@ -1,21 +1,111 @@
+use super::{
+    agent_registry::{default_roster, resolve_roster, role_catalogue, AgentRole},
+    task_graph::{review_tasks, run_task_graph, task_graph, AgentTask, TaskOutcome},
+};
 use crate::{
-    ai_functions::aifunc_managing::convert_user_input_to_goal,
-    helpers::general::ai_task_request,
+    ai_functions::aifunc_managing::{
+        convert_user_input_to_goal, print_agent_roster, print_clarifying_questions,
+        print_project_review,
+    },
+    helpers::{
+        command_line::{get_user_response, PrintCommand},
+        general::{ai_task_request, ai_task_request_decode},
+    },
     models::{
-        agent_basic::basic_agent::{AgentState, BasicAgent},
+        agent_basic::{
+            basic_agent::{AgentState, BasicAgent},
+            basic_traits::BasicTraits,
+        },
         agents::{
             agent_architect::AgentSolutionArchitect,
-            agent_backend::AgentBackendDeveloper,
             agent_traits::{FactSheet, SpecialFunctions},
         },
+        general::{
+            clarification::{select_questions, ClarifyingQuestion},
+            project_review::ProjectReview,
+            requirements::CriterionOutcome,
+        },
     },
 };
+use dotenvy::dotenv;
+use serde_json::{json, Value};
+use std::env;
+
+// Environment variable limiting how often the manager sends agents back after reviewing
+const MAX_REVIEW_ROUNDS_ENV: &str = "MAX_REVIEW_ROUNDS";
+const DEFAULT_REVIEW_ROUNDS: usize = 2;
+
+// Review rounds set through MAX_REVIEW_ROUNDS, 0 turns the review off
+fn review_rounds_from_env() -> anyhow::Result<usize> {
+    dotenv().ok();
+
+    match env::var(MAX_REVIEW_ROUNDS_ENV) {
+        Ok(rounds) if !rounds.trim().is_empty() => rounds.trim().parse().map_err(|_| {
+            anyhow::anyhow!(
+                "{} must be a number of rounds, found '{}'",
+                MAX_REVIEW_ROUNDS_ENV,
+                rounds
+            )
+        }),
+        _ => Ok(DEFAULT_REVIEW_ROUNDS),
+    }
+}
+
+// What the team delivered, short enough to review against the project description
+fn project_summary(factsheet: &FactSheet, failed_agents: &[&str]) -> Value {
+    json!({
+        "project_scope": factsheet.project_scope,
+        "external_urls": factsheet.external_urls,
+        "backend_framework": factsheet.backend_framework,
+        "database_tables": factsheet.database_design.as_ref().map(|design| design.describe_tables()),
+        "auth_routes": factsheet.auth_setup.as_ref().map(|setup| {
+            setup
+                .routes()
+                .iter()
+                .map(|route| format!("{} {}", route.method, route.route))
+                .collect::<Vec<String>>()
+        }),
+        "api_endpoints": factsheet.api_endpoint_schema.as_ref().map(|routes| {
+            routes
+                .iter()
+                .map(|route| format!("{} {}", route.method, route.route))
+                .collect::<Vec<String>>()
+        }),
+        "route_plan_differences": factsheet.route_diff.as_ref().and_then(|diff| diff.report()),
+        "failing_endpoint_tests": factsheet.endpoint_test_results.as_ref().map(|results| {
+            results
+                .iter()
+                .filter(|result| !result.passed)
+                .map(|result| format!("{} {}: {}", result.method, result.route, result.message))
+                .collect::<Vec<String>>()
+        }),
+        "unmet_acceptance_criteria": factsheet.acceptance_results.as_ref().map(|results| {
+            results
+                .iter()
+                .filter(|result| result.outcome == CriterionOutcome::Unmet)
+                .map(|result| format!("{} {}: {}", result.id, result.description, result.message))
+                .collect::<Vec<String>>()
+        }),
+        "backend_tests": factsheet.backend_test_results.as_ref().map(|tests| {
+            tests
+                .iter()
+                .map(|test| format!("{}: {}", test.name, test.outcome))
+                .collect::<Vec<String>>()
+        }),
+        "has_backend_code": factsheet.backend_code.is_some(),
+        "has_frontend": factsheet.frontend_code.is_some(),
+        "deployment_files": factsheet.deployment.as_ref().map(|deployment| deployment.files.clone()),
+        "readme": factsheet.project_docs.as_ref().map(|docs| docs.readme_path.clone()),
+        "failed_agents": failed_agents,
+    })
+}
 
 #[allow(unused)]
 pub struct ManagingAgent {
     attributes: BasicAgent,
     factsheet: FactSheet,
     agents: Vec<Box<dyn SpecialFunctions>>,
+    max_review_rounds: usize,
 }
 
 impl ManagingAgent {
@@ -42,31 +132,306 @@ impl ManagingAgent {
             ..Default::default()
         };
 
-        Ok(Self {
+        let mut managing_agent = Self {
             attributes,
             factsheet,
             agents: Vec::new(),
-        })
+            max_review_rounds: review_rounds_from_env()?,
+        };
+        managing_agent.interview_user(user_req).await?;
+
+        Ok(managing_agent)
+    }
+
+    // Ask the user about what the request leaves open before anyone plans on it
+    async fn interview_user(&mut self, user_req: &str) -> anyhow::Result<()> {
+        let msg_context = format!(
+            "USER_REQUEST: {} \n PROJECT_GOAL: {}",
+            user_req, self.factsheet.project_description
+        );
+
+        let questions = match ai_task_request_decode::<Vec<ClarifyingQuestion>>(
+            &msg_context,
+            self.attributes.get_position(),
+            get_function_string!(print_clarifying_questions),
+            print_clarifying_questions,
+        )
+        .await
+        {
+            Ok(questions) => select_questions(questions),
+            Err(e) => {
+                let err_msg = format!("Could not find questions to ask ({}), skipping them", e);
+                PrintCommand::Issue
+                    .print_agent_message(self.attributes.get_position(), &err_msg)?;
+                return Ok(());
+            }
+        };
+        if questions.is_empty() {
+            return Ok(());
+        }
+
+        let mut requirements = Vec::new();
+        for (index, question) in questions.iter().enumerate() {
+            let response = get_user_response(&question.prompt(index + 1, questions.len()))?;
+            requirements.extend(question.answer(&response));
+        }
+
+        let assumed = requirements
+            .iter()
+            .filter(|requirement| requirement.assumed)
+            .count();
+        let recorded_msg = format!(
+            "Recorded {} requirement(s), {} of them assumed",
+            requirements.len(),
+            assumed
+        );
+        PrintCommand::AICall.print_agent_message(self.attributes.get_position(), &recorded_msg)?;
+
+        self.factsheet.clarified_requirements = Some(requirements);
+
+        Ok(())
     }
 
     fn add_agent(&mut self, agent: Box<dyn SpecialFunctions>) {
         self.agents.push(agent);
     }
 
-    fn create_agents(&mut self) {
-        self.add_agent(Box::new(AgentSolutionArchitect::new()));
-        self.add_agent(Box::new(AgentBackendDeveloper::new()));
+    // Keep only the latest run of an agent sent back by the review
+    fn replace_agent(&mut self, agent: Box<dyn SpecialFunctions>) {
+        let position = agent.get_attributes_from_agent().get_position().clone();
+        match self
+            .agents
+            .iter()
+            .position(|known| known.get_attributes_from_agent().get_position() == &position)
+        {
+            Some(index) => self.agents[index] = agent,
+            None => self.add_agent(agent),
+        }
+    }
+
+    // Pick the team from the project scope, falling back to the full build when planning fails
+    async fn plan_team(&self) -> anyhow::Result<Vec<&'static AgentRole>> {
+        let scope = self
+            .factsheet
+            .project_scope
+            .ok_or_else(|| anyhow::anyhow!("The architect did not set a project scope"))?;
+
+        let msg_context = format!(
+            "PROJECT_DESCRIPTION: {} \n PROJECT_SCOPE: {} \n AVAILABLE_AGENTS: {}",
+            self.factsheet.project_context(),
+            serde_json::to_string(&scope)?,
+            role_catalogue()
+        );
+
+        let planned = match ai_task_request_decode::<Vec<String>>(
+            &msg_context,
+            self.attributes.get_position(),
+            get_function_string!(print_agent_roster),
+            print_agent_roster,
+        )
+        .await
+        {
+            Ok(planned) => planned,
+            Err(e) => {
+                let err_msg = format!("Could not plan the team ({}), using every agent", e);
+                PrintCommand::Issue
+                    .print_agent_message(self.attributes.get_position(), &err_msg)?;
+                default_roster(&scope)
+            }
+        };
+
+        let (team, unknown) = resolve_roster(&planned, &scope);
+        if !unknown.is_empty() {
+            let err_msg = format!("Ignoring unknown agents: {}", unknown.join(", "));
+            PrintCommand::Issue.print_agent_message(self.attributes.get_position(), &err_msg)?;
+        }
+
+        Ok(team)
     }
 
     pub async fn execute_project(&mut self) -> anyhow::Result<()> {
-        self.create_agents();
+        // The architect always goes first, the rest of the team depends on the scope it finds
+        let mut architect = AgentSolutionArchitect::new();
+        architect.execute(&mut self.factsheet).await?;
+        self.add_agent(Box::new(architect));
+
+        let team = self.plan_team().await?;
+        let tasks = team
+            .iter()
+            .map(|role| AgentTask::from_role(role))
+            .collect::<Vec<AgentTask>>();
+        let mut failed = self.run_tasks(tasks).await?;
+
+        if self.max_review_rounds > 0 {
+            self.review_rounds(&team, &mut failed).await?;
+        }
+
+        if !failed.is_empty() {
+            anyhow::bail!("Agents failed: {}", failed.join(", "));
+        }
+
+        Ok(())
+    }
+
+    // Check the result against the request, sending agents back with feedback while it falls short.
+    // Agents that succeed on a later round no longer count as failed.
+    async fn review_rounds(
+        &mut self,
+        team: &[&'static AgentRole],
+        failed: &mut Vec<&'static str>,
+    ) -> anyhow::Result<()> {
+        for round in 0..=self.max_review_rounds {
+            let Some(review) = self.review_project(team, failed).await? else {
+                break;
+            };
+            let unmet = review.unmet_requirements.join("; ");
+            let feedback = review.feedback_by_agent();
+            self.factsheet.project_review = Some(review.clone());
 
-        for agent in self.agents.iter_mut() {
-            agent.execute(&mut self.factsheet).await?;
+            if review.requirements_met {
+                PrintCommand::AICall.print_agent_message(
+                    self.attributes.get_position(),
+                    "Review: the project meets the requirements",
+                )?;
+                break;
+            }
+            if round == self.max_review_rounds {
+                let err_msg = format!(
+                    "Review: requirements still unmet after {} round(s): {}",
+                    round, unmet
+                );
+                PrintCommand::Issue
+                    .print_agent_message(self.attributes.get_position(), &err_msg)?;
+                break;
+            }
+
+            let (tasks, unknown) = review_tasks(team, &feedback);
+            if !unknown.is_empty() {
+                let err_msg = format!(
+                    "Review: ignoring feedback for agents not on the team or not taking feedback: {}",
+                    unknown.join(", ")
+                );
+                PrintCommand::Issue
+                    .print_agent_message(self.attributes.get_position(), &err_msg)?;
+            }
+            if tasks.is_empty() {
+                let err_msg = format!("Review: no agent to send back for: {}", unmet);
+                PrintCommand::Issue
+                    .print_agent_message(self.attributes.get_position(), &err_msg)?;
+                break;
+            }
+
+            for (agent, agent_feedback) in feedback.iter() {
+                let review_msg = format!(
+                    "Review round {}/{}: {} - {}",
+                    round + 1,
+                    self.max_review_rounds,
+                    agent,
+                    agent_feedback
+                );
+                PrintCommand::Issue
+                    .print_agent_message(self.attributes.get_position(), &review_msg)?;
+            }
+
+            let rerun = tasks.iter().map(|task| task.name).collect::<Vec<&str>>();
+            let rerun_failed = self.run_tasks(tasks).await?;
+            failed.retain(|name| !rerun.contains(name));
+            failed.extend(rerun_failed);
         }
 
         Ok(())
     }
+
+    // Run tasks as a dependency graph, returning the ones that failed
+    async fn run_tasks(&mut self, tasks: Vec<AgentTask>) -> anyhow::Result<Vec<&'static str>> {
+        // Show what runs after what, tasks without one start straight away
+        let graph = task_graph(&tasks);
+        let team_msg = format!(
+            "Team: {}",
+            tasks
+                .iter()
+                .zip(graph.iter())
+                .map(|(task, node)| match node.after.is_empty() {
+                    true => task.name.to_string(),
+                    false => format!(
+                        "{} (after {})",
+                        task.name,
+                        node.after
+                            .iter()
+                            .map(|&before| tasks[before].name)
+                            .collect::<Vec<&str>>()
+                            .join(", ")
+                    ),
+                })
+                .collect::<Vec<String>>()
+                .join(", ")
+        );
+        PrintCommand::AICall.print_agent_message(self.attributes.get_position(), &team_msg)?;
+
+        let reports = run_task_graph(tasks, &mut self.factsheet).await;
+
+        let mut failed = Vec::new();
+        for report in reports {
+            let (command, report_msg) = match &report.outcome {
+                TaskOutcome::Completed => {
+                    (PrintCommand::AICall, format!("{} completed", report.name))
+                }
+                TaskOutcome::Failed(e) => {
+                    failed.push(report.name);
+                    (
+                        PrintCommand::Issue,
+                        format!("{} failed: {}", report.name, e),
+                    )
+                }
+                TaskOutcome::Skipped(reason) => (
+                    PrintCommand::Issue,
+                    format!("{} skipped: {}", report.name, reason),
+                ),
+            };
+            command.print_agent_message(self.attributes.get_position(), &report_msg)?;
+
+            if let Some(agent) = report.agent {
+                self.replace_agent(agent);
+            }
+        }
+
+        Ok(failed)
+    }
+
+    // Judge the FactSheet against the project description, None when the review itself fails
+    async fn review_project(
+        &self,
+        team: &[&'static AgentRole],
+        failed: &[&str],
+    ) -> anyhow::Result<Option<ProjectReview>> {
+        let msg_context = format!(
+            "PROJECT_DESCRIPTION: {} \n PROJECT_SUMMARY: {} \n TEAM: {}",
+            self.factsheet.project_context(),
+            project_summary(&self.factsheet, failed),
+            team.iter()
+                .filter(|role| role.takes_feedback)
+                .map(|role| format!("{}: {}", role.name, role.description))
+                .collect::<Vec<String>>()
+                .join("; ")
+        );
+
+        match ai_task_request_decode::<ProjectReview>(
+            &msg_context,
+            self.attributes.get_position(),
+            get_function_string!(print_project_review),
+            print_project_review,
+        )
+        .await
+        {
+            Ok(review) => Ok(Some(review)),
+            Err(e) => {
+                let err_msg = format!("Could not review the project ({}), keeping the result", e);
+                PrintCommand::Issue
+                    .print_agent_message(self.attributes.get_position(), &err_msg)?;
+                Ok(None)
+            }
+        }
+    }
 }
 
 // #[cfg(test)]
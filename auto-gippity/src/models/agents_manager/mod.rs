@@ -1,2 +1,4 @@
 // agents manager
+pub mod agent_registry;
 pub mod managing_agent;
+pub mod task_graph;
@@ -0,0 +1,390 @@
+use super::agent_registry::{find_role, AgentRole};
+use crate::models::agents::agent_traits::{FactSheet, FactSheetField, SpecialFunctions};
+use tokio::task::JoinSet;
+
+// Agent together with the FactSheet fields it reads and writes
+#[derive(Debug)]
+pub struct AgentTask {
+    pub name: &'static str,
+    pub reads: &'static [FactSheetField],
+    pub writes: &'static [FactSheetField],
+    pub runs_server: bool,
+    pub agent: Box<dyn SpecialFunctions>,
+    // What the manager's review asked this agent to fix
+    pub feedback: Option<String>,
+}
+
+impl AgentTask {
+    pub fn from_role(role: &AgentRole) -> Self {
+        Self {
+            name: role.name,
+            reads: role.reads,
+            writes: role.writes,
+            runs_server: role.runs_server,
+            agent: (role.create)(),
+            feedback: None,
+        }
+    }
+}
+
+// Earlier tasks one task waits for. It needs the output of the ones writing what it reads, the
+// others touch the same fields or start the same server and must not run at the same time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskNode {
+    pub after: Vec<usize>,
+    pub needs: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TaskOutcome {
+    Completed,
+    Failed(String),
+    Skipped(String),
+}
+
+#[derive(Debug)]
+pub struct TaskReport {
+    pub name: &'static str,
+    pub outcome: TaskOutcome,
+    // Gone when the agent panicked
+    pub agent: Option<Box<dyn SpecialFunctions>>,
+}
+
+fn shares_field(a: &[FactSheetField], b: &[FactSheetField]) -> bool {
+    a.iter().any(|field| b.contains(field))
+}
+
+// Tasks keep their order: a task only ever waits for tasks before it
+pub fn task_graph(tasks: &[AgentTask]) -> Vec<TaskNode> {
+    tasks
+        .iter()
+        .enumerate()
+        .map(|(index, task)| {
+            let mut node = TaskNode {
+                after: Vec::new(),
+                needs: Vec::new(),
+            };
+            for (earlier, before) in tasks[..index].iter().enumerate() {
+                let needs = shares_field(task.reads, before.writes);
+                if needs
+                    || shares_field(task.writes, before.writes)
+                    || shares_field(task.writes, before.reads)
+                    || (task.runs_server && before.runs_server)
+                {
+                    node.after.push(earlier);
+                }
+                if needs {
+                    node.needs.push(earlier);
+                }
+            }
+            node
+        })
+        .collect()
+}
+
+// Run every task once the ones before it are done, independent tasks at the same time. Each agent
+// works on its own copy of the FactSheet, only its declared writes are merged back. A failing or
+// panicking agent skips the tasks needing its output, unrelated tasks still run.
+pub async fn run_task_graph(tasks: Vec<AgentTask>, factsheet: &mut FactSheet) -> Vec<TaskReport> {
+    let graph = task_graph(&tasks);
+    let names = tasks.iter().map(|task| task.name).collect::<Vec<&str>>();
+    let writes = tasks.iter().map(|task| task.writes).collect::<Vec<_>>();
+
+    let mut waiting = tasks
+        .into_iter()
+        .map(Some)
+        .collect::<Vec<Option<AgentTask>>>();
+    let mut outcomes: Vec<Option<TaskOutcome>> = vec![None; names.len()];
+    let mut agents = names
+        .iter()
+        .map(|_| None)
+        .collect::<Vec<Option<Box<dyn SpecialFunctions>>>>();
+    let mut running = JoinSet::new();
+
+    loop {
+        for (index, node) in graph.iter().enumerate() {
+            if waiting[index].is_none() {
+                continue;
+            }
+
+            // Dependencies come first, so skips carry over to their dependents in one pass
+            let missing = node
+                .needs
+                .iter()
+                .find(|&&needed| !matches!(outcomes[needed], None | Some(TaskOutcome::Completed)));
+            if let Some(&needed) = missing {
+                let task = waiting[index].take().expect("Task is still waiting");
+                outcomes[index] = Some(TaskOutcome::Skipped(format!(
+                    "{} did not complete",
+                    names[needed]
+                )));
+                agents[index] = Some(task.agent);
+                continue;
+            }
+
+            if node.after.iter().all(|&before| outcomes[before].is_some()) {
+                let task = waiting[index].take().expect("Task is still waiting");
+                let mut task_factsheet = factsheet.clone();
+                task_factsheet.review_feedback = task.feedback;
+                running.spawn(async move {
+                    let mut agent = task.agent;
+                    // Own tokio task, so a panicking agent only fails this task
+                    let handle = tokio::spawn(async move {
+                        let result = agent.execute(&mut task_factsheet).await;
+                        (agent, task_factsheet, result)
+                    });
+                    (index, handle.await)
+                });
+            }
+        }
+
+        let Some(joined) = running.join_next().await else {
+            break;
+        };
+        let (index, finished) = joined.expect("Agent panics are caught inside the task");
+
+        outcomes[index] = Some(match finished {
+            Ok((agent, task_factsheet, Ok(()))) => {
+                for field in writes[index] {
+                    factsheet.copy_field(&task_factsheet, *field);
+                }
+                agents[index] = Some(agent);
+                TaskOutcome::Completed
+            }
+            Ok((agent, _, Err(e))) => {
+                agents[index] = Some(agent);
+                TaskOutcome::Failed(e.to_string())
+            }
+            Err(e) => TaskOutcome::Failed(e.to_string()),
+        });
+    }
+
+    names
+        .into_iter()
+        .zip(outcomes)
+        .zip(agents)
+        .map(|((name, outcome), agent)| TaskReport {
+            name,
+            outcome: outcome.expect("Every task was run or skipped"),
+            agent,
+        })
+        .collect()
+}
+
+// Fresh agents for a review round: the team members given feedback, plus the ones working from
+// what those write, so their output follows the changes. Names not on the team, or of roles that
+// do not read feedback, are returned apart.
+pub fn review_tasks(
+    team: &[&'static AgentRole],
+    feedback: &[(String, String)],
+) -> (Vec<AgentTask>, Vec<String>) {
+    let mut unknown = Vec::new();
+    let mut addressed: Vec<(&str, &str)> = Vec::new();
+    for (agent, agent_feedback) in feedback.iter() {
+        let actionable = find_role(agent).filter(|role| {
+            role.takes_feedback && team.iter().any(|member| member.name == role.name)
+        });
+        match actionable {
+            Some(role) => addressed.push((role.name, agent_feedback)),
+            None => unknown.push(agent.clone()),
+        }
+    }
+
+    // The team is ordered by reads, so dependents come after the roles they read from
+    let mut chosen: Vec<&'static AgentRole> = Vec::new();
+    let mut tasks = Vec::new();
+    for role in team.iter() {
+        let role_feedback = addressed
+            .iter()
+            .filter(|(name, _)| *name == role.name)
+            .map(|(_, feedback)| *feedback)
+            .collect::<Vec<&str>>();
+        if role_feedback.is_empty() && !chosen.iter().any(|before| role.reads_from(before)) {
+            continue;
+        }
+
+        let mut task = AgentTask::from_role(role);
+        if !role_feedback.is_empty() {
+            task.feedback = Some(role_feedback.join("; "));
+        }
+        chosen.push(role);
+        tasks.push(task);
+    }
+
+    (tasks, unknown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        agent_basic::basic_agent::{AgentState, BasicAgent},
+        agents::agent_traits::ProjectScope,
+        agents_manager::agent_registry::{
+            default_roster, resolve_roster, AUTH_SPECIALIST, BACKEND_DEVELOPER, DATABASE_DESIGNER,
+            DOCUMENTATION_WRITER, FRONTEND_DEVELOPER,
+        },
+    };
+
+    #[derive(Debug)]
+    struct FakeAgent {
+        attributes: BasicAgent,
+        work: fn(&mut FactSheet) -> anyhow::Result<()>,
+    }
+
+    #[async_trait::async_trait]
+    impl SpecialFunctions for FakeAgent {
+        fn get_attributes_from_agent(&self) -> &BasicAgent {
+            &self.attributes
+        }
+
+        async fn execute(&mut self, factsheet: &mut FactSheet) -> anyhow::Result<()> {
+            // Long enough for the tasks running alongside to start
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            (self.work)(factsheet)
+        }
+    }
+
+    fn task(
+        name: &'static str,
+        reads: &'static [FactSheetField],
+        writes: &'static [FactSheetField],
+        work: fn(&mut FactSheet) -> anyhow::Result<()>,
+    ) -> AgentTask {
+        AgentTask {
+            name,
+            reads,
+            writes,
+            runs_server: false,
+            agent: Box::new(FakeAgent {
+                attributes: BasicAgent {
+                    objective: String::new(),
+                    position: name.to_string(),
+                    state: AgentState::Discovery,
+                    memory: Vec::new(),
+                },
+                work,
+            }),
+            feedback: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_task_only_skips_its_dependents() {
+        use FactSheetField::*;
+
+        let tasks = vec![
+            task("urls", &[], &[ExternalUrls], |factsheet| {
+                factsheet.external_urls = Some(vec!["https://example.com".to_string()]);
+                // Not declared, so never merged
+                factsheet.project_description = "changed".to_string();
+                Ok(())
+            }),
+            task("backend", &[], &[BackendCode], |_| {
+                panic!("backend agent gave up")
+            }),
+            task("frontend", &[BackendCode], &[FrontendCode], |factsheet| {
+                factsheet.frontend_code = Some("<html></html>".to_string());
+                Ok(())
+            }),
+            task("docs", &[ExternalUrls], &[FrontendCode], |factsheet| {
+                anyhow::ensure!(factsheet.external_urls.is_some(), "urls were not merged");
+                factsheet.frontend_code = Some("docs".to_string());
+                Ok(())
+            }),
+        ];
+
+        // urls and backend start together, docs waits for urls and for frontend's write
+        let graph = task_graph(&tasks);
+        assert!(graph[0].after.is_empty() && graph[1].after.is_empty());
+        assert_eq!(graph[2].needs, vec![1]);
+        assert_eq!(graph[3].after, vec![0, 2]);
+        assert_eq!(graph[3].needs, vec![0]);
+
+        let mut factsheet = FactSheet {
+            project_description: "todo app".to_string(),
+            ..Default::default()
+        };
+        let reports = run_task_graph(tasks, &mut factsheet).await;
+
+        let outcomes = reports
+            .iter()
+            .map(|report| (report.name, report.outcome.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(outcomes[0], ("urls", TaskOutcome::Completed));
+        assert!(matches!(&outcomes[1].1, TaskOutcome::Failed(e) if e.contains("panicked")));
+        assert_eq!(
+            outcomes[2],
+            (
+                "frontend",
+                TaskOutcome::Skipped("backend did not complete".to_string())
+            )
+        );
+        assert_eq!(outcomes[3], ("docs", TaskOutcome::Completed));
+        assert!(reports[1].agent.is_none() && reports[2].agent.is_some());
+
+        assert_eq!(factsheet.project_description, "todo app");
+        assert_eq!(factsheet.frontend_code.as_deref(), Some("docs"));
+        assert!(factsheet.external_urls.is_some());
+    }
+
+    #[test]
+    fn test_default_team_shares_the_server_one_task_at_a_time() {
+        let scope = ProjectScope {
+            is_curd_required: true,
+            is_user_login_and_logout: true,
+            is_external_urls_required: false,
+        };
+        let (team, _) = resolve_roster(&default_roster(&scope), &scope);
+        let tasks = team
+            .iter()
+            .map(|role| AgentTask::from_role(role))
+            .collect::<Vec<AgentTask>>();
+        let graph = task_graph(&tasks);
+        let index = |name: &str| {
+            tasks
+                .iter()
+                .position(|task| task.name == name)
+                .expect("Role is on the default team")
+        };
+
+        // The database and auth designs do not touch each other, so they start together
+        assert!(graph[index(DATABASE_DESIGNER)].after.is_empty());
+        assert!(graph[index(AUTH_SPECIALIST)].after.is_empty());
+
+        // Docs does not need the page, but both start the server on the same data files
+        let docs = &graph[index(DOCUMENTATION_WRITER)];
+        assert!(docs.after.contains(&index(FRONTEND_DEVELOPER)));
+        assert!(!docs.needs.contains(&index(FRONTEND_DEVELOPER)));
+    }
+
+    #[test]
+    fn test_review_sends_back_agent_and_its_dependents() {
+        let scope = ProjectScope {
+            is_curd_required: true,
+            is_user_login_and_logout: false,
+            is_external_urls_required: false,
+        };
+        let (team, _) = resolve_roster(&default_roster(&scope), &scope);
+
+        let feedback = [
+            ("Backend Developer", "Tasks cannot be deleted"),
+            ("designer", "Use a nicer font"),
+            ("Documentation Writer", "Mention the delete endpoint"),
+            ("backend-developer", "Return 404 for unknown ids"),
+        ]
+        .map(|(agent, feedback)| (agent.to_string(), feedback.to_string()));
+        let (tasks, unknown) = review_tasks(&team, &feedback);
+
+        assert_eq!(unknown, vec!["designer", "Documentation Writer"]);
+        assert_eq!(tasks[0].name, BACKEND_DEVELOPER);
+        assert_eq!(
+            tasks[0].feedback.as_deref(),
+            Some("Tasks cannot be deleted; Return 404 for unknown ids")
+        );
+        // Everyone working from the backend runs again, the database design stays
+        assert_eq!(tasks.len(), team.len() - 1);
+        assert!(tasks.iter().all(|task| task.name != DATABASE_DESIGNER));
+        assert!(tasks[1..].iter().all(|task| task.feedback.is_none()));
+    }
+}
@@ -0,0 +1,270 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::{collections::BTreeMap, str::FromStr};
+use strum_macros::{Display, EnumIter, EnumString};
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Display, EnumString, EnumIter,
+)]
+#[strum(serialize_all = "UPPERCASE", ascii_case_insensitive)]
+pub enum HttpMethod {
+    Get,
+    Post,
+    Put,
+    Patch,
+    Delete,
+    Head,
+    Options,
+}
+
+impl HttpMethod {
+    pub fn as_reqwest(&self) -> reqwest::Method {
+        match self {
+            HttpMethod::Get => reqwest::Method::GET,
+            HttpMethod::Post => reqwest::Method::POST,
+            HttpMethod::Put => reqwest::Method::PUT,
+            HttpMethod::Patch => reqwest::Method::PATCH,
+            HttpMethod::Delete => reqwest::Method::DELETE,
+            HttpMethod::Head => reqwest::Method::HEAD,
+            HttpMethod::Options => reqwest::Method::OPTIONS,
+        }
+    }
+}
+
+impl Serialize for HttpMethod {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+// Accepts "get", "GET", "Get" and so on, as LLMs are not consistent about casing
+impl<'de> Deserialize<'de> for HttpMethod {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let method = String::deserialize(deserializer)?;
+        HttpMethod::from_str(method.trim()).map_err(|_| {
+            serde::de::Error::custom(format!("unknown http method '{}'", method.trim()))
+        })
+    }
+}
+
+// Shape of a JSON request or response body
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum FieldSchema {
+    // The route has no body at all
+    None,
+    // The body exists but its shape is unknown
+    #[default]
+    NotProvided,
+    Number,
+    String,
+    Bool,
+    Array(Box<FieldSchema>),
+    Object(BTreeMap<String, FieldSchema>),
+}
+
+impl FieldSchema {
+    // Lenient conversion of the shapes LLMs print, i.e. "bool", "None", {"id": "number"} or [{..}]
+    pub fn from_value(value: &Value) -> Self {
+        match value {
+            Value::Null => FieldSchema::None,
+            Value::Bool(_) => FieldSchema::Bool,
+            Value::Number(_) => FieldSchema::Number,
+            Value::String(name) => Self::from_type_name(name),
+            Value::Array(items) => FieldSchema::Array(Box::new(
+                items
+                    .first()
+                    .map(Self::from_value)
+                    .unwrap_or(FieldSchema::NotProvided),
+            )),
+            Value::Object(fields) => FieldSchema::Object(
+                fields
+                    .iter()
+                    .map(|(name, field)| (name.clone(), Self::from_value(field)))
+                    .collect(),
+            ),
+        }
+    }
+
+    fn from_type_name(name: &str) -> Self {
+        match name.trim().to_lowercase().as_str() {
+            "" | "none" | "null" | "unit" | "()" => FieldSchema::None,
+            "number" | "integer" | "int" | "float" | "double" | "u8" | "u16" | "u32" | "u64"
+            | "u128" | "usize" | "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "f32"
+            | "f64" => FieldSchema::Number,
+            "string" | "str" | "&str" | "char" => FieldSchema::String,
+            "bool" | "boolean" => FieldSchema::Bool,
+            "array" | "list" => FieldSchema::Array(Box::new(FieldSchema::NotProvided)),
+            "object" | "map" => FieldSchema::Object(BTreeMap::new()),
+            _ => FieldSchema::NotProvided,
+        }
+    }
+
+    // Form understood by the prompts, i.e. {"id": "number", "tags": ["string"]}
+    pub fn to_value(&self) -> Value {
+        match self {
+            FieldSchema::None => Value::String("None".to_string()),
+            FieldSchema::NotProvided => Value::String("not_provided".to_string()),
+            FieldSchema::Number => Value::String("number".to_string()),
+            FieldSchema::String => Value::String("string".to_string()),
+            FieldSchema::Bool => Value::String("bool".to_string()),
+            FieldSchema::Array(item) => Value::Array(vec![item.to_value()]),
+            FieldSchema::Object(fields) => Value::Object(
+                fields
+                    .iter()
+                    .map(|(name, field)| (name.clone(), field.to_value()))
+                    .collect(),
+            ),
+        }
+    }
+
+    pub fn is_none(&self) -> bool {
+        matches!(self, FieldSchema::None)
+    }
+}
+
+impl Serialize for FieldSchema {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_value().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for FieldSchema {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        Ok(FieldSchema::from_value(&value))
+    }
+}
+
+// Accepts true, "true", "True" or "yes" as LLMs often quote their booleans
+pub fn deserialize_lenient_bool<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<bool, D::Error> {
+    match Value::deserialize(deserializer)? {
+        Value::Bool(flag) => Ok(flag),
+        Value::String(flag) => match flag.trim().to_lowercase().as_str() {
+            "true" | "yes" | "1" => Ok(true),
+            "false" | "no" | "0" | "" => Ok(false),
+            other => Err(serde::de::Error::custom(format!(
+                "expected a boolean, found '{}'",
+                other
+            ))),
+        },
+        Value::Number(number) => Ok(number.as_i64().unwrap_or_default() != 0),
+        other => Err(serde::de::Error::custom(format!(
+            "expected a boolean, found {}",
+            other
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn test_http_method_round_trip() -> anyhow::Result<()> {
+        for method in HttpMethod::iter() {
+            let encoded = serde_json::to_string(&method)?;
+            assert_eq!(serde_json::from_str::<HttpMethod>(&encoded)?, method);
+        }
+
+        assert_eq!(
+            serde_json::from_str::<HttpMethod>("\"get\"")?,
+            HttpMethod::Get
+        );
+        assert_eq!(
+            serde_json::from_str::<HttpMethod>("\" Delete\"")?,
+            HttpMethod::Delete
+        );
+        assert_eq!(serde_json::to_string(&HttpMethod::Post)?, "\"POST\"");
+        assert!(serde_json::from_str::<HttpMethod>("\"fetch\"").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_field_schema_from_llm_forms() -> anyhow::Result<()> {
+        let schema = serde_json::from_value::<FieldSchema>(json!({
+            "id": "number",
+            "name": "String",
+            "completed": "boolean",
+            "tags": ["string"],
+            "owner": { "id": 1 },
+            "notes": "None",
+            "extra": "whatever"
+        }))?;
+
+        let FieldSchema::Object(fields) = &schema else {
+            panic!("Expected an object schema");
+        };
+
+        assert_eq!(fields["id"], FieldSchema::Number);
+        assert_eq!(fields["name"], FieldSchema::String);
+        assert_eq!(fields["completed"], FieldSchema::Bool);
+        assert_eq!(
+            fields["tags"],
+            FieldSchema::Array(Box::new(FieldSchema::String))
+        );
+        assert_eq!(fields["notes"], FieldSchema::None);
+        assert_eq!(fields["extra"], FieldSchema::NotProvided);
+        assert_eq!(
+            fields["owner"],
+            FieldSchema::Object(BTreeMap::from([("id".to_string(), FieldSchema::Number)]))
+        );
+
+        assert_eq!(
+            serde_json::from_value::<FieldSchema>(json!(null))?,
+            FieldSchema::None
+        );
+        assert_eq!(
+            serde_json::from_value::<FieldSchema>(json!("not_provided"))?,
+            FieldSchema::NotProvided
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_field_schema_round_trip() -> anyhow::Result<()> {
+        let original = json!({
+            "items": [{ "id": "number", "done": "bool" }],
+            "total": "number",
+            "cursor": "None",
+            "meta": "not_provided"
+        });
+
+        let schema = serde_json::from_value::<FieldSchema>(original.clone())?;
+        assert_eq!(serde_json::to_value(&schema)?, original);
+        assert_eq!(
+            serde_json::from_value::<FieldSchema>(schema.to_value())?,
+            schema
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_lenient_bool() -> anyhow::Result<()> {
+        #[derive(Deserialize)]
+        struct Flag {
+            #[serde(deserialize_with = "deserialize_lenient_bool")]
+            value: bool,
+        }
+
+        for (raw, expected) in [
+            (json!(true), true),
+            (json!("true"), true),
+            (json!("False"), false),
+            (json!(0), false),
+        ] {
+            let flag = serde_json::from_value::<Flag>(json!({ "value": raw }))?;
+            assert_eq!(flag.value, expected);
+        }
+
+        assert!(serde_json::from_value::<Flag>(json!({ "value": "maybe" })).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+use super::api_schema::{FieldSchema, HttpMethod};
+use crate::models::agents::agent_traits::RouteObject;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+// Path every route of the vetted auth module lives under
+pub const AUTH_ROUTE_PREFIX: &str = "/auth";
+
+// What the vetted auth module adds to the generated backend
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AuthSetup {
+    // Handler argument type that rejects anonymous requests
+    pub guard_type: String,
+    pub session_cookie: String,
+    pub signup_route: String,
+    pub login_route: String,
+    pub logout_route: String,
+    pub current_user_route: String,
+}
+
+impl Default for AuthSetup {
+    fn default() -> Self {
+        Self {
+            guard_type: "AuthenticatedUser".to_string(),
+            session_cookie: "session_id".to_string(),
+            signup_route: format!("{}/signup", AUTH_ROUTE_PREFIX),
+            login_route: format!("{}/login", AUTH_ROUTE_PREFIX),
+            logout_route: format!("{}/logout", AUTH_ROUTE_PREFIX),
+            current_user_route: format!("{}/me", AUTH_ROUTE_PREFIX),
+        }
+    }
+}
+
+impl AuthSetup {
+    pub fn is_auth_route(route: &str) -> bool {
+        route == AUTH_ROUTE_PREFIX || route.starts_with(&format!("{}/", AUTH_ROUTE_PREFIX))
+    }
+
+    // Routes of the auth module, which endpoint extraction cannot see in main.rs
+    pub fn routes(&self) -> Vec<RouteObject> {
+        let credentials =
+            FieldSchema::from_value(&json!({ "username": "string", "password": "string" }));
+        let user = FieldSchema::from_value(&json!({ "id": "number", "username": "string" }));
+
+        [
+            (
+                HttpMethod::Post,
+                &self.signup_route,
+                credentials.clone(),
+                user.clone(),
+                "signup",
+            ),
+            (
+                HttpMethod::Post,
+                &self.login_route,
+                credentials,
+                user.clone(),
+                "login",
+            ),
+            (
+                HttpMethod::Post,
+                &self.logout_route,
+                FieldSchema::None,
+                FieldSchema::None,
+                "logout",
+            ),
+            (
+                HttpMethod::Get,
+                &self.current_user_route,
+                FieldSchema::None,
+                user,
+                "me",
+            ),
+        ]
+        .into_iter()
+        .map(
+            |(method, route, request_body, response, handler)| RouteObject {
+                is_route_dynamic: false,
+                method,
+                request_body,
+                response,
+                route: route.clone(),
+                handler: Some(handler.to_string()),
+                dynamic_segments: Vec::new(),
+                extractors: Vec::new(),
+            },
+        )
+        .collect()
+    }
+}
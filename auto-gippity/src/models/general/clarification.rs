@@ -0,0 +1,170 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+
+// Most questions the interview asks before any agent starts
+pub const MAX_CLARIFYING_QUESTIONS: usize = 4;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum RequirementTopic {
+    #[serde(alias = "data_source", alias = "data sources")]
+    DataSources,
+    #[serde(alias = "authentication", alias = "login")]
+    Auth,
+    #[serde(alias = "storage", alias = "database")]
+    Persistence,
+    #[serde(alias = "scaling", alias = "performance")]
+    Scale,
+    #[serde(other)]
+    Other,
+}
+
+// Something the request leaves open, with the answer assumed when the user gives none
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ClarifyingQuestion {
+    pub topic: RequirementTopic,
+    pub question: String,
+    #[serde(default)]
+    pub default_answer: String,
+}
+
+// Answer the agents build on, marked when the default was taken
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ClarifiedRequirement {
+    pub topic: RequirementTopic,
+    pub question: String,
+    pub answer: String,
+    pub assumed: bool,
+}
+
+impl ClarifyingQuestion {
+    // Question as shown to the user, i.e. "[1/3] Where do prices come from? (enter: Coinbase API)"
+    pub fn prompt(&self, number: usize, count: usize) -> String {
+        match self.default_answer.trim() {
+            "" => format!("[{}/{}] {}", number, count, self.question.trim()),
+            default => format!(
+                "[{}/{}] {} (enter: {})",
+                number,
+                count,
+                self.question.trim(),
+                default
+            ),
+        }
+    }
+
+    // An empty response takes the default, None when there is none either
+    pub fn answer(&self, response: &str) -> Option<ClarifiedRequirement> {
+        let (answer, assumed) = match response.trim() {
+            "" => (self.default_answer.trim(), true),
+            response => (response, false),
+        };
+        if answer.is_empty() {
+            return None;
+        }
+
+        Some(ClarifiedRequirement {
+            topic: self.topic,
+            question: self.question.trim().to_string(),
+            answer: answer.to_string(),
+            assumed,
+        })
+    }
+}
+
+// Questions worth asking: no blank or repeated ones, at most MAX_CLARIFYING_QUESTIONS
+pub fn select_questions(questions: Vec<ClarifyingQuestion>) -> Vec<ClarifyingQuestion> {
+    let mut selected: Vec<ClarifyingQuestion> = Vec::new();
+    for question in questions {
+        let text = question.question.trim().to_lowercase();
+        if text.is_empty()
+            || selected
+                .iter()
+                .any(|asked| asked.question.trim().to_lowercase() == text)
+        {
+            continue;
+        }
+        selected.push(question);
+    }
+
+    selected.truncate(MAX_CLARIFYING_QUESTIONS);
+    selected
+}
+
+// One requirement per entry, as given to the agents
+pub fn describe_requirements(requirements: &[ClarifiedRequirement]) -> String {
+    requirements
+        .iter()
+        .map(|requirement| {
+            format!(
+                "{}: {} {}{}",
+                requirement.topic,
+                requirement.question,
+                requirement.answer,
+                if requirement.assumed {
+                    " (assumed)"
+                } else {
+                    ""
+                }
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_selects_and_answers_questions() -> anyhow::Result<()> {
+        let questions = serde_json::from_value::<Vec<ClarifyingQuestion>>(json!([
+            { "topic": "data sources", "question": "Where do the prices come from?", "default_answer": "A public forex API" },
+            { "topic": "authentication", "question": "Do users need accounts?" },
+            { "topic": "payments", "question": "where do the prices come from? " },
+            { "topic": "storage", "question": "" },
+            { "topic": "scale", "question": "How many users at once?", "default_answer": "A handful" },
+            { "topic": "persistence", "question": "Keep history after a restart?", "default_answer": "Yes" },
+            { "topic": "payments", "question": "Which currency?", "default_answer": "USD" }
+        ]))?;
+
+        let selected = select_questions(questions);
+        let topics = selected
+            .iter()
+            .map(|question| question.topic)
+            .collect::<Vec<RequirementTopic>>();
+        assert_eq!(
+            topics,
+            vec![
+                RequirementTopic::DataSources,
+                RequirementTopic::Auth,
+                RequirementTopic::Scale,
+                RequirementTopic::Persistence
+            ]
+        );
+        assert_eq!(
+            selected[0].prompt(1, 4),
+            "[1/4] Where do the prices come from? (enter: A public forex API)"
+        );
+
+        let requirements = [
+            selected[0].answer(""),
+            selected[1].answer(" yes, with email "),
+            selected[1].answer(""),
+        ];
+        assert!(requirements[0]
+            .as_ref()
+            .is_some_and(|answer| answer.assumed));
+        assert!(requirements[2].is_none());
+
+        let requirements = requirements.into_iter().flatten().collect::<Vec<_>>();
+        assert_eq!(
+            describe_requirements(&requirements),
+            "data_sources: Where do the prices come from? A public forex API (assumed); \
+            auth: Do users need accounts? yes, with email"
+        );
+
+        Ok(())
+    }
+}
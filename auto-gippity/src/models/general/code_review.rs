@@ -0,0 +1,127 @@
+use super::api_schema::deserialize_lenient_bool;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReviewItemKind {
+    // Something the requirements ask for that the code does not do
+    #[serde(alias = "Gap", alias = "missing")]
+    Gap,
+    // Something the code does wrong
+    #[serde(alias = "Defect", alias = "bug")]
+    Defect,
+}
+
+impl fmt::Display for ReviewItemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReviewItemKind::Gap => write!(f, "gap"),
+            ReviewItemKind::Defect => write!(f, "defect"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReviewItem {
+    // Numbered once decoded, i.e. R1, R2, so the verification can refer back to it
+    #[serde(default)]
+    pub id: String,
+    pub kind: ReviewItemKind,
+    pub description: String,
+    // Handler, struct or function the item is about, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    pub suggestion: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReviewVerification {
+    pub id: String,
+    #[serde(deserialize_with = "deserialize_lenient_bool")]
+    pub addressed: bool,
+    #[serde(default)]
+    pub note: String,
+}
+
+// Critique of the first backend draft and whether the improved code handled it
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CodeReview {
+    pub items: Vec<ReviewItem>,
+    #[serde(default)]
+    pub verification: Vec<ReviewVerification>,
+}
+
+impl CodeReview {
+    pub fn new(mut items: Vec<ReviewItem>) -> Self {
+        for (index, item) in items.iter_mut().enumerate() {
+            item.id = format!("R{}", index + 1);
+        }
+
+        Self {
+            items,
+            verification: Vec::new(),
+        }
+    }
+
+    // Items the verification did not confirm, including ones it skipped
+    pub fn unaddressed(&self) -> Vec<&ReviewItem> {
+        self.items
+            .iter()
+            .filter(|item| {
+                !self
+                    .verification
+                    .iter()
+                    .any(|check| check.id == item.id && check.addressed)
+            })
+            .collect()
+    }
+
+    // Numbered checklist for prompts, one item per line
+    pub fn checklist(items: &[&ReviewItem]) -> String {
+        items
+            .iter()
+            .map(|item| {
+                let location = item
+                    .location
+                    .as_ref()
+                    .map(|location| format!(" ({})", location))
+                    .unwrap_or_default();
+                format!(
+                    "{} [{}]{}: {} -> {}",
+                    item.id, item.kind, location, item.description, item.suggestion
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_tracks_unaddressed_items() -> anyhow::Result<()> {
+        let mut review = CodeReview::new(serde_json::from_value(json!([
+            { "kind": "gap", "description": "No delete route for tasks", "suggestion": "Add DELETE /task/{id}" },
+            { "kind": "Defect", "description": "Ids restart after a restart", "location": "create_task", "suggestion": "Use the max stored id" },
+            { "kind": "bug", "description": "Missing 404", "location": "read_task", "suggestion": "Return 404 for unknown ids" }
+        ]))?);
+
+        review.verification = serde_json::from_value(json!([
+            { "id": "R1", "addressed": "true", "note": "delete_task added" },
+            { "id": "R2", "addressed": false }
+        ]))?;
+
+        let unaddressed = review.unaddressed();
+        assert_eq!(
+            CodeReview::checklist(&unaddressed),
+            "R2 [defect] (create_task): Ids restart after a restart -> Use the max stored id\n\
+            R3 [defect] (read_task): Missing 404 -> Return 404 for unknown ids"
+        );
+
+        Ok(())
+    }
+}
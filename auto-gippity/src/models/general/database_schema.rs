@@ -0,0 +1,101 @@
+use super::api_schema::deserialize_lenient_bool;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ColumnSchema {
+    pub name: String,
+    pub sql_type: String,
+    #[serde(default, deserialize_with = "deserialize_lenient_bool")]
+    pub primary_key: bool,
+    #[serde(default, deserialize_with = "deserialize_lenient_bool")]
+    pub nullable: bool,
+    // Referenced column as "table.column"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub references: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TableSchema {
+    pub name: String,
+    pub columns: Vec<ColumnSchema>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Migration {
+    pub name: String,
+    pub sql: String,
+}
+
+// Relational schema of the generated backend, with the migrations creating it
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DatabaseDesign {
+    pub tables: Vec<TableSchema>,
+    pub migrations: Vec<Migration>,
+    // Rust module wrapping the queries, written after the schema is verified
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub data_access_code: String,
+}
+
+impl DatabaseDesign {
+    // Compact table listing for prompts, i.e. task(id INTEGER PK, name TEXT)
+    pub fn describe_tables(&self) -> String {
+        self.tables
+            .iter()
+            .map(|table| {
+                let columns = table
+                    .columns
+                    .iter()
+                    .map(|column| {
+                        let mut description = format!("{} {}", column.name, column.sql_type);
+                        if column.primary_key {
+                            description.push_str(" PK");
+                        }
+                        if column.nullable {
+                            description.push_str(" NULL");
+                        }
+                        if let Some(reference) = column.references.as_ref() {
+                            description.push_str(&format!(" -> {}", reference));
+                        }
+                        description
+                    })
+                    .collect::<Vec<String>>();
+
+                format!("{}({})", table.name, columns.join(", "))
+            })
+            .collect::<Vec<String>>()
+            .join("; ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_decodes_llm_design() -> anyhow::Result<()> {
+        let design = serde_json::from_value::<DatabaseDesign>(json!({
+            "tables": [
+                {
+                    "name": "tasks",
+                    "columns": [
+                        { "name": "id", "sql_type": "INTEGER", "primary_key": "true" },
+                        { "name": "name", "sql_type": "TEXT" },
+                        { "name": "user_id", "sql_type": "INTEGER", "nullable": true, "references": "users.id" }
+                    ]
+                }
+            ],
+            "migrations": [
+                { "name": "create_tasks", "sql": "CREATE TABLE tasks (id INTEGER PRIMARY KEY);" }
+            ]
+        }))?;
+
+        assert!(design.data_access_code.is_empty());
+        assert_eq!(
+            design.describe_tables(),
+            "tasks(id INTEGER PK, name TEXT, user_id INTEGER NULL -> users.id)"
+        );
+
+        Ok(())
+    }
+}
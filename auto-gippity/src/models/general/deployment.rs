@@ -0,0 +1,99 @@
+use crate::{
+    helpers::{
+        devops::manifest_binary_name,
+        test_server::{DEFAULT_SERVER_PORT, SERVER_HOST_ENV, SERVER_PORT_ENV},
+    },
+    models::agents::agent_traits::FactSheet,
+};
+use serde::{Deserialize, Serialize};
+
+// Binary of the default manifest, used when the generated one cannot be read
+const DEFAULT_BINARY_NAME: &str = "web_server";
+
+// Binary the backend developer built and the port it told the server to default to
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ServerSetup {
+    pub binary_name: String,
+    pub port: u16,
+}
+
+impl ServerSetup {
+    pub fn from_manifest(manifest: &str) -> Self {
+        Self {
+            binary_name: manifest_binary_name(manifest)
+                .unwrap_or_else(|| DEFAULT_BINARY_NAME.to_string()),
+            port: DEFAULT_SERVER_PORT,
+        }
+    }
+}
+
+// Environment variable the generated server reads, documented next to the Dockerfile
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EnvVar {
+    pub name: String,
+    // Value set inside the container
+    pub value: String,
+    pub description: String,
+}
+
+// What the DevOps agent packaged, and what the generated files must agree on
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DeploymentSetup {
+    pub binary_name: String,
+    pub port: u16,
+    pub env_vars: Vec<EnvVar>,
+    // Hosts the container has to reach at runtime
+    pub external_urls: Vec<String>,
+    pub has_frontend: bool,
+    #[serde(default)]
+    pub files: Vec<String>,
+    // Lint and consistency findings of the generated files
+    #[serde(default)]
+    pub issues: Vec<String>,
+}
+
+impl DeploymentSetup {
+    pub fn new(server: &ServerSetup, factsheet: &FactSheet) -> Self {
+        let env_vars = vec![
+            EnvVar {
+                name: SERVER_HOST_ENV.to_string(),
+                value: "0.0.0.0".to_string(),
+                description:
+                    "Address the server binds to, all interfaces so the published port reaches it"
+                        .to_string(),
+            },
+            EnvVar {
+                name: SERVER_PORT_ENV.to_string(),
+                value: server.port.to_string(),
+                description: "Port the server listens on inside the container".to_string(),
+            },
+        ];
+
+        Self {
+            binary_name: server.binary_name.clone(),
+            port: server.port,
+            env_vars,
+            external_urls: factsheet.external_urls.clone().unwrap_or_default(),
+            has_frontend: factsheet.frontend_code.is_some(),
+            files: Vec::new(),
+            issues: Vec::new(),
+        }
+    }
+
+    // Compose service name, which only allows letters, digits, '.', '_' and '-'
+    pub fn service_name(&self) -> String {
+        self.binary_name
+            .to_lowercase()
+            .chars()
+            .map(|c| match c.is_ascii_alphanumeric() || "._-".contains(c) {
+                true => c,
+                false => '-',
+            })
+            .collect()
+    }
+
+    // Host port of the static frontend, next to the backend's
+    pub fn frontend_port(&self) -> u16 {
+        self.port.checked_add(1).unwrap_or(self.port - 1)
+    }
+}
@@ -1 +1,11 @@
+pub mod api_schema;
+pub mod auth_schema;
+pub mod clarification;
+pub mod code_review;
+pub mod database_schema;
+pub mod deployment;
 pub mod llm;
+pub mod project_docs;
+pub mod project_review;
+pub mod requirements;
+pub mod security_review;
@@ -0,0 +1,95 @@
+use super::api_schema::HttpMethod;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Cookie jar the documented curl commands share, so login carries over to later calls
+pub const COOKIE_JAR: &str = "cookies.txt";
+
+// Documented curl call, with the outcome of running it against the test server
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CurlExample {
+    pub method: HttpMethod,
+    // Route from the schema, i.e. /task/{id}
+    pub route: String,
+    // Route with its parameters filled in, i.e. /task/1
+    pub path: String,
+    pub body: Option<Value>,
+    pub save_session: bool,
+    pub send_session: bool,
+    pub status: Option<u16>,
+    pub error: Option<String>,
+}
+
+impl CurlExample {
+    pub fn passed(&self) -> bool {
+        self.error.is_none()
+            && self
+                .status
+                .is_some_and(|status| (200..300).contains(&status))
+    }
+
+    // Arguments after `curl`, exactly as documented
+    pub fn args(&self, base_url: &str) -> Vec<String> {
+        let mut args = Vec::new();
+        // curl sends a GET on its own, unless a body turns it into a POST
+        if self.method != HttpMethod::Get || self.body.is_some() {
+            args.extend(["-X".to_string(), self.method.to_string()]);
+        }
+        args.push(format!("{}{}", base_url.trim_end_matches('/'), self.path));
+
+        if let Some(body) = self.body.as_ref() {
+            args.extend([
+                "-H".to_string(),
+                "Content-Type: application/json".to_string(),
+                "-d".to_string(),
+                body.to_string(),
+            ]);
+        }
+        if self.save_session {
+            args.extend(["-c".to_string(), COOKIE_JAR.to_string()]);
+        }
+        if self.send_session {
+            args.extend(["-b".to_string(), COOKIE_JAR.to_string()]);
+        }
+
+        args
+    }
+
+    // Shell command shown in the README
+    pub fn command(&self, base_url: &str) -> String {
+        let mut command = "curl".to_string();
+        for arg in self.args(base_url) {
+            command.push(' ');
+            command.push_str(&shell_quote(&arg));
+        }
+        command
+    }
+}
+
+fn shell_quote(arg: &str) -> String {
+    let is_plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c));
+
+    match is_plain {
+        true => arg.to_string(),
+        false => format!("'{}'", arg.replace('\'', "'\\''")),
+    }
+}
+
+// What the documentation writer produced for the generated project
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProjectDocs {
+    pub readme_path: String,
+    pub examples: Vec<CurlExample>,
+}
+
+impl ProjectDocs {
+    pub fn failed_examples(&self) -> Vec<&CurlExample> {
+        self.examples
+            .iter()
+            .filter(|example| !example.passed())
+            .collect()
+    }
+}
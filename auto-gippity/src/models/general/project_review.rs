@@ -0,0 +1,37 @@
+use super::api_schema::deserialize_lenient_bool;
+use serde::{Deserialize, Serialize};
+
+// What one agent has to change, addressed by its role name
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AgentFeedback {
+    pub agent: String,
+    pub feedback: String,
+}
+
+// The manager's verdict on the finished FactSheet against the project description
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProjectReview {
+    #[serde(deserialize_with = "deserialize_lenient_bool")]
+    pub requirements_met: bool,
+    #[serde(default)]
+    pub unmet_requirements: Vec<String>,
+    #[serde(default)]
+    pub feedback: Vec<AgentFeedback>,
+}
+
+impl ProjectReview {
+    // Feedback for every agent the review sends back, in the order first named
+    pub fn feedback_by_agent(&self) -> Vec<(String, String)> {
+        let mut grouped: Vec<(String, String)> = Vec::new();
+        for entry in self.feedback.iter() {
+            match grouped.iter_mut().find(|(agent, _)| *agent == entry.agent) {
+                Some((_, feedback)) => {
+                    feedback.push_str("; ");
+                    feedback.push_str(&entry.feedback);
+                }
+                None => grouped.push((entry.agent.clone(), entry.feedback.clone())),
+            }
+        }
+        grouped
+    }
+}
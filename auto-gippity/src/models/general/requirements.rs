@@ -0,0 +1,272 @@
+use super::api_schema::{deserialize_lenient_bool, FieldSchema, HttpMethod};
+use serde::{Deserialize, Serialize};
+
+fn required_by_default() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EntityField {
+    pub name: String,
+    #[serde(rename = "type", default)]
+    pub field_type: FieldSchema,
+    #[serde(
+        default = "required_by_default",
+        deserialize_with = "deserialize_lenient_bool"
+    )]
+    pub required: bool,
+}
+
+// Something the project keeps data about, i.e. a Task with a title and a done flag
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Entity {
+    pub name: String,
+    pub fields: Vec<EntityField>,
+}
+
+fn rust_type(schema: &FieldSchema) -> String {
+    match schema {
+        FieldSchema::Number => "i64".to_string(),
+        FieldSchema::String => "String".to_string(),
+        FieldSchema::Bool => "bool".to_string(),
+        FieldSchema::Array(item) => format!("Vec<{}>", rust_type(item)),
+        _ => "serde_json::Value".to_string(),
+    }
+}
+
+impl Entity {
+    // Struct the backend uses for the entity, optional fields as Option
+    pub fn rust_struct(&self) -> String {
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| {
+                let ty = rust_type(&field.field_type);
+                match field.required {
+                    true => format!("    pub {}: {},", field.name, ty),
+                    false => format!("    pub {}: Option<{}>,", field.name, ty),
+                }
+            })
+            .collect::<Vec<String>>();
+
+        format!(
+            "#[derive(Debug, Clone, Serialize, Deserialize)]\npub struct {} {{\n{}\n}}",
+            self.name,
+            fields.join("\n")
+        )
+    }
+
+    // i.e. Task {id: number, title: string, notes?: string}
+    pub fn describe(&self) -> String {
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| {
+                format!(
+                    "{}{}: {}",
+                    field.name,
+                    if field.required { "" } else { "?" },
+                    field.field_type.to_value()
+                )
+            })
+            .collect::<Vec<String>>();
+        format!("{} {{{}}}", self.name, fields.join(", ")).replace('"', "")
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UserStory {
+    pub actor: String,
+    pub action: String,
+    #[serde(default)]
+    pub benefit: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct NonFunctionalRequirements {
+    // Data has to survive a restart of the server
+    #[serde(default, deserialize_with = "deserialize_lenient_bool")]
+    pub persistence: bool,
+    #[serde(default)]
+    pub rate_limit_per_minute: Option<u32>,
+    // Listings return pages of this many items
+    #[serde(default)]
+    pub page_size: Option<u32>,
+    #[serde(default)]
+    pub notes: Vec<String>,
+}
+
+// Checkable statement about the finished project. Ones naming a route are checked against the
+// endpoint tests.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AcceptanceCriterion {
+    // Numbered once decoded, i.e. AC1, AC2
+    #[serde(default)]
+    pub id: String,
+    pub description: String,
+    #[serde(default)]
+    pub method: Option<HttpMethod>,
+    #[serde(default)]
+    pub route: Option<String>,
+    #[serde(default)]
+    pub expected_status: Option<u16>,
+}
+
+// What the architect works out the project has to do, beyond the scope flags
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RequirementsSpec {
+    #[serde(default)]
+    pub entities: Vec<Entity>,
+    #[serde(default)]
+    pub user_stories: Vec<UserStory>,
+    #[serde(default)]
+    pub non_functional: NonFunctionalRequirements,
+    #[serde(default)]
+    pub acceptance_criteria: Vec<AcceptanceCriterion>,
+}
+
+impl RequirementsSpec {
+    pub fn numbered(mut self) -> Self {
+        for (index, criterion) in self.acceptance_criteria.iter_mut().enumerate() {
+            criterion.id = format!("AC{}", index + 1);
+        }
+        self
+    }
+
+    // Prompt form of the specification
+    pub fn describe(&self) -> String {
+        let mut sections = Vec::new();
+
+        if !self.entities.is_empty() {
+            let entities = self
+                .entities
+                .iter()
+                .map(Entity::describe)
+                .collect::<Vec<String>>();
+            sections.push(format!("ENTITIES: {}", entities.join("; ")));
+        }
+
+        if !self.user_stories.is_empty() {
+            let stories = self
+                .user_stories
+                .iter()
+                .map(|story| match story.benefit.trim() {
+                    "" => format!("As a {} I can {}", story.actor, story.action),
+                    benefit => format!(
+                        "As a {} I can {} so that {}",
+                        story.actor, story.action, benefit
+                    ),
+                })
+                .collect::<Vec<String>>();
+            sections.push(format!("USER_STORIES: {}", stories.join("; ")));
+        }
+
+        let needs = &self.non_functional;
+        let mut non_functional = Vec::new();
+        if needs.persistence {
+            non_functional.push("data survives a server restart".to_string());
+        }
+        if let Some(limit) = needs.rate_limit_per_minute {
+            non_functional.push(format!(
+                "at most {} requests per minute per client, answering 429 above it",
+                limit
+            ));
+        }
+        if let Some(page_size) = needs.page_size {
+            non_functional.push(format!(
+                "listings take ?page= (from 1) and return at most {} items per page",
+                page_size
+            ));
+        }
+        non_functional.extend(needs.notes.iter().cloned());
+        if !non_functional.is_empty() {
+            sections.push(format!("NON_FUNCTIONAL: {}", non_functional.join("; ")));
+        }
+
+        if !self.acceptance_criteria.is_empty() {
+            let criteria = self
+                .acceptance_criteria
+                .iter()
+                .map(
+                    |criterion| match (criterion.method, criterion.route.as_ref()) {
+                        (Some(method), Some(route)) => format!(
+                            "{} {} {}{}: {}",
+                            criterion.id,
+                            method,
+                            route,
+                            criterion
+                                .expected_status
+                                .map(|status| format!(" -> {}", status))
+                                .unwrap_or_default(),
+                            criterion.description
+                        ),
+                        _ => format!("{}: {}", criterion.id, criterion.description),
+                    },
+                )
+                .collect::<Vec<String>>();
+            sections.push(format!("ACCEPTANCE_CRITERIA: {}", criteria.join("; ")));
+        }
+
+        sections.join(" \n ")
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CriterionOutcome {
+    Met,
+    Unmet,
+    // Not tied to an endpoint call the tests make
+    Unchecked,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AcceptanceResult {
+    pub id: String,
+    pub description: String,
+    pub outcome: CriterionOutcome,
+    pub message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_decodes_and_describes_spec() -> anyhow::Result<()> {
+        let spec = serde_json::from_value::<RequirementsSpec>(json!({
+            "entities": [{
+                "name": "Task",
+                "fields": [
+                    { "name": "id", "type": "number" },
+                    { "name": "title", "type": "string" },
+                    { "name": "notes", "type": "string", "required": "false" }
+                ]
+            }],
+            "user_stories": [{ "actor": "user", "action": "add a task", "benefit": "I remember it" }],
+            "non_functional": { "persistence": "true", "page_size": 20 },
+            "acceptance_criteria": [
+                { "description": "Tasks can be created", "method": "post", "route": "/task", "expected_status": 201 },
+                { "description": "The page loads quickly" }
+            ]
+        }))?
+        .numbered();
+
+        assert_eq!(spec.acceptance_criteria[1].id, "AC2");
+        assert_eq!(
+            spec.entities[0].rust_struct(),
+            "#[derive(Debug, Clone, Serialize, Deserialize)]\npub struct Task {\n    pub id: i64,\n    pub title: String,\n    pub notes: Option<String>,\n}"
+        );
+        assert_eq!(spec.non_functional.rate_limit_per_minute, None);
+        assert_eq!(
+            spec.describe(),
+            "ENTITIES: Task {id: number, title: string, notes?: string} \n \
+            USER_STORIES: As a user I can add a task so that I remember it \n \
+            NON_FUNCTIONAL: data survives a server restart; listings take ?page= (from 1) and return at most 20 items per page \n \
+            ACCEPTANCE_CRITERIA: AC1 POST /task -> 201: Tasks can be created; AC2: The page loads quickly"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+use std::{cmp::Reverse, fmt};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    #[serde(alias = "Low", alias = "LOW", alias = "info")]
+    Low,
+    #[serde(alias = "Medium", alias = "MEDIUM")]
+    Medium,
+    #[serde(alias = "High", alias = "HIGH", alias = "critical")]
+    High,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Low => write!(f, "LOW"),
+            Severity::Medium => write!(f, "MEDIUM"),
+            Severity::High => write!(f, "HIGH"),
+        }
+    }
+}
+
+// Where a finding came from, so the reviewer knows how much to trust it
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FindingSource {
+    #[default]
+    Static,
+    Llm,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SecurityFinding {
+    pub severity: Severity,
+    // Short label such as "process", "filesystem", "unsafe", "secret" or "url"
+    pub category: String,
+    pub file: String,
+    #[serde(default)]
+    pub line: Option<usize>,
+    pub message: String,
+    #[serde(default)]
+    pub source: FindingSource,
+}
+
+// Findings on the generated code, shown to the human before it runs
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SecurityReport {
+    pub findings: Vec<SecurityFinding>,
+    // Set when the LLM review could not be completed, the static checks still ran
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub llm_review_error: Option<String>,
+}
+
+impl SecurityReport {
+    pub fn highest_severity(&self) -> Option<Severity> {
+        self.findings.iter().map(|finding| finding.severity).max()
+    }
+
+    // One finding per line, most severe first
+    pub fn describe(&self) -> String {
+        let mut findings = self.findings.iter().collect::<Vec<&SecurityFinding>>();
+        findings.sort_by_key(|finding| Reverse(finding.severity));
+
+        let mut lines = findings
+            .iter()
+            .map(|finding| {
+                let location = match finding.line {
+                    Some(line) => format!("{}:{}", finding.file, line),
+                    None => finding.file.clone(),
+                };
+                let source = match finding.source {
+                    FindingSource::Static => "",
+                    FindingSource::Llm => " (llm)",
+                };
+                format!(
+                    "[{}] {} {}{}: {}",
+                    finding.severity, location, finding.category, source, finding.message
+                )
+            })
+            .collect::<Vec<String>>();
+
+        if lines.is_empty() {
+            lines.push("No security findings".to_string());
+        }
+        if let Some(error) = self.llm_review_error.as_ref() {
+            lines.push(format!("LLM review unavailable: {}", error));
+        }
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_describes_llm_findings() -> anyhow::Result<()> {
+        let mut report = SecurityReport {
+            findings: serde_json::from_value(json!([
+                { "severity": "Low", "category": "cors", "file": "main.rs", "message": "CORS allows any origin" },
+                { "severity": "high", "category": "sql", "file": "db.rs", "line": 12, "message": "Query built with format!" }
+            ]))?,
+            llm_review_error: None,
+        };
+        for finding in report.findings.iter_mut() {
+            finding.source = FindingSource::Llm;
+        }
+
+        assert_eq!(report.highest_severity(), Some(Severity::High));
+        assert_eq!(
+            report.describe(),
+            "[HIGH] db.rs:12 sql (llm): Query built with format!\n\
+            [LOW] main.rs cors (llm): CORS allows any origin"
+        );
+        assert_eq!(SecurityReport::default().describe(), "No security findings");
+
+        Ok(())
+    }
+}
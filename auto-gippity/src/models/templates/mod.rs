@@ -0,0 +1,6 @@
+// backend framework templates
+pub mod template_actix_web;
+pub mod template_axum;
+pub mod template_registry;
+pub mod template_rocket;
+pub mod template_traits;
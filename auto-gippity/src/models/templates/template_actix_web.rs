@@ -0,0 +1,65 @@
+use super::template_traits::{BackendFramework, BackendTemplate};
+use crate::{
+    helpers::{dependencies::CrateSpec, route_parser::extract_actix_routes},
+    models::agents::agent_traits::RouteObject,
+};
+
+#[derive(Debug)]
+pub struct ActixWebTemplate;
+
+impl BackendTemplate for ActixWebTemplate {
+    fn framework(&self) -> BackendFramework {
+        BackendFramework::ActixWeb
+    }
+
+    fn code_template_path(&self) -> &'static str {
+        "template/frameworks/actix_web.rs"
+    }
+
+    fn framework_dependencies(&self) -> &'static [CrateSpec] {
+        &[
+            CrateSpec {
+                name: "actix-web",
+                version: "4.8",
+                features: &[],
+            },
+            CrateSpec {
+                name: "actix-cors",
+                version: "0.7",
+                features: &[],
+            },
+        ]
+    }
+
+    fn prompt_fragment(&self) -> &'static str {
+        "Uses actix-web 4 with #[actix_web::main]. Routes are registered with \
+        App::new().route(path, web::get().to(handler)), shared state goes through web::Data \
+        and the app is wrapped with actix_cors::Cors::permissive()."
+    }
+
+    fn route_declaration_example(&self) -> &'static str {
+        ".route(\"/item/{id}\", web::get().to(read_item))"
+    }
+
+    fn test_harness_notes(&self) -> &'static str {
+        "Integration tests are #[actix_web::test] async functions that build the same App with \
+        actix_web::test::init_service(App::new()...) and send requests through \
+        actix_web::test::TestRequest with test::call_service."
+    }
+
+    fn extract_routes(&self, code: &str) -> Option<Vec<RouteObject>> {
+        extract_actix_routes(code)
+            .ok()
+            .filter(|routes| !routes.is_empty())
+    }
+
+    fn auth_template_path(&self) -> &'static str {
+        "template/auth/actix_web.rs"
+    }
+
+    fn auth_integration_notes(&self) -> &'static str {
+        "Register the auth routes with App::new().configure(auth::configure). \
+        Protected handlers take an auth::AuthenticatedUser argument, which answers 401 \
+        without a valid session cookie."
+    }
+}
@@ -0,0 +1,66 @@
+use super::template_traits::{BackendFramework, BackendTemplate};
+use crate::{
+    helpers::{dependencies::CrateSpec, route_parser::extract_axum_routes},
+    models::agents::agent_traits::RouteObject,
+};
+
+#[derive(Debug)]
+pub struct AxumTemplate;
+
+impl BackendTemplate for AxumTemplate {
+    fn framework(&self) -> BackendFramework {
+        BackendFramework::Axum
+    }
+
+    fn code_template_path(&self) -> &'static str {
+        "template/frameworks/axum.rs"
+    }
+
+    fn framework_dependencies(&self) -> &'static [CrateSpec] {
+        &[
+            CrateSpec {
+                name: "axum",
+                version: "0.8",
+                features: &[],
+            },
+            CrateSpec {
+                name: "tower-http",
+                version: "0.6",
+                features: &["cors"],
+            },
+        ]
+    }
+
+    fn prompt_fragment(&self) -> &'static str {
+        "Uses axum 0.8 with #[tokio::main]. Routes are registered with \
+        Router::new().route(path, get(handler).post(handler)), path parameters use the \
+        {id} syntax, shared state goes through State and the router is layered with \
+        tower_http::cors::CorsLayer::permissive()."
+    }
+
+    fn route_declaration_example(&self) -> &'static str {
+        ".route(\"/item/{id}\", get(read_item).put(update_item))"
+    }
+
+    fn test_harness_notes(&self) -> &'static str {
+        "Integration tests are #[tokio::test] async functions that build the same Router, serve \
+        it with axum::serve on a tokio::net::TcpListener bound to 127.0.0.1:0 inside \
+        tokio::spawn and send requests with reqwest."
+    }
+
+    fn extract_routes(&self, code: &str) -> Option<Vec<RouteObject>> {
+        extract_axum_routes(code)
+            .ok()
+            .filter(|routes| !routes.is_empty())
+    }
+
+    fn auth_template_path(&self) -> &'static str {
+        "template/auth/axum.rs"
+    }
+
+    fn auth_integration_notes(&self) -> &'static str {
+        "Merge the auth routes with .merge(auth::router()) before adding state and \
+        layers. Protected handlers take an auth::AuthenticatedUser argument, which answers 401 \
+        without a valid session cookie."
+    }
+}
@@ -0,0 +1,94 @@
+use super::{
+    template_actix_web::ActixWebTemplate,
+    template_axum::AxumTemplate,
+    template_rocket::RocketTemplate,
+    template_traits::{BackendFramework, BackendTemplate},
+};
+use dotenvy::dotenv;
+use std::{env, str::FromStr};
+use strum::IntoEnumIterator;
+
+// Environment variable used to force a framework instead of letting the architect choose
+const BACKEND_FRAMEWORK_ENV: &str = "BACKEND_FRAMEWORK";
+
+const TEMPLATES: &[&dyn BackendTemplate] = &[&ActixWebTemplate, &AxumTemplate, &RocketTemplate];
+
+// Look up the template registered for a framework
+pub fn find_template(framework: BackendFramework) -> &'static dyn BackendTemplate {
+    TEMPLATES
+        .iter()
+        .find(|template| template.framework() == framework)
+        .copied()
+        .expect("Every backend framework has a registered template")
+}
+
+// Framework names accepted in prompts and configuration
+pub fn framework_names() -> String {
+    BackendFramework::iter()
+        .map(|framework| framework.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+// Parse a framework name as written by a user or an LLM, i.e. "Axum" or "\"actix_web\""
+pub fn parse_framework(name: &str) -> anyhow::Result<BackendFramework> {
+    let name = name.trim().trim_matches('"').trim();
+
+    BackendFramework::from_str(name).map_err(|_| {
+        anyhow::anyhow!(
+            "Unknown backend framework '{}', expected one of: {}",
+            name,
+            framework_names()
+        )
+    })
+}
+
+// Framework forced by the user through the BACKEND_FRAMEWORK variable, if any
+pub fn framework_from_env() -> anyhow::Result<Option<BackendFramework>> {
+    dotenv().ok();
+
+    match env::var(BACKEND_FRAMEWORK_ENV) {
+        Ok(name) if !name.trim().is_empty() => Ok(Some(parse_framework(&name)?)),
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_framework_has_template() {
+        for framework in BackendFramework::iter() {
+            let template = find_template(framework);
+            assert_eq!(template.framework(), framework);
+            assert!(std::path::Path::new(template.code_template_path()).exists());
+        }
+    }
+
+    #[test]
+    fn test_parses_framework_names() -> anyhow::Result<()> {
+        assert_eq!(parse_framework("actix-web")?, BackendFramework::ActixWeb);
+        assert_eq!(
+            parse_framework("\"Actix_Web\"")?,
+            BackendFramework::ActixWeb
+        );
+        assert_eq!(parse_framework(" axum\n")?, BackendFramework::Axum);
+        assert_eq!(parse_framework("ROCKET")?, BackendFramework::Rocket);
+        assert!(parse_framework("warp").is_err());
+
+        let decoded = serde_json::from_str::<BackendFramework>("\"actix-web\"")?;
+        assert_eq!(decoded, BackendFramework::ActixWeb);
+        assert_eq!(serde_json::to_string(&BackendFramework::Axum)?, "\"axum\"");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_templates_declare_framework_crates() {
+        let names = find_template(BackendFramework::Axum).dependency_names();
+        assert!(names.contains("axum"));
+        assert!(names.contains("tower_http"));
+        assert!(!names.contains("actix_web"));
+    }
+}
@@ -0,0 +1,56 @@
+use super::template_traits::{BackendFramework, BackendTemplate};
+use crate::{
+    helpers::{dependencies::CrateSpec, route_parser::extract_rocket_routes},
+    models::agents::agent_traits::RouteObject,
+};
+
+#[derive(Debug)]
+pub struct RocketTemplate;
+
+impl BackendTemplate for RocketTemplate {
+    fn framework(&self) -> BackendFramework {
+        BackendFramework::Rocket
+    }
+
+    fn code_template_path(&self) -> &'static str {
+        "template/frameworks/rocket.rs"
+    }
+
+    fn framework_dependencies(&self) -> &'static [CrateSpec] {
+        &[CrateSpec {
+            name: "rocket",
+            version: "0.5",
+            features: &["json"],
+        }]
+    }
+
+    fn prompt_fragment(&self) -> &'static str {
+        "Uses rocket 0.5 with #[launch]. Handlers are declared with attribute macros such as \
+        #[get(\"/item/<id>\")], mounted with routes![...] and JSON goes through \
+        rocket::serde::json::Json. Path parameters use the <id> syntax."
+    }
+
+    fn route_declaration_example(&self) -> &'static str {
+        "#[get(\"/item/<id>\")]\nfn read_item(id: u64) -> Option<Json<Item>>"
+    }
+
+    fn test_harness_notes(&self) -> &'static str {
+        "Integration tests are #[rocket::async_test] async functions that create a \
+        rocket::local::asynchronous::Client::tracked(rocket()) and dispatch requests through it."
+    }
+
+    fn extract_routes(&self, code: &str) -> Option<Vec<RouteObject>> {
+        extract_rocket_routes(code)
+            .ok()
+            .filter(|routes| !routes.is_empty())
+    }
+
+    fn auth_template_path(&self) -> &'static str {
+        "template/auth/rocket.rs"
+    }
+
+    fn auth_integration_notes(&self) -> &'static str {
+        "Mount the auth routes with .mount(\"/\", auth::routes()). Protected handlers take an \
+        auth::AuthenticatedUser argument, which answers 401 without a valid session cookie."
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{
+    helpers::{
+        dependencies::CrateSpec,
+        test_server::{DEFAULT_SERVER_HOST, DEFAULT_SERVER_PORT, SERVER_HOST_ENV, SERVER_PORT_ENV},
+    },
+    models::agents::agent_traits::RouteObject,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use strum_macros::{Display, EnumIter, EnumString};
+
+#[derive(
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Display,
+    EnumString,
+    EnumIter,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(ascii_case_insensitive)]
+pub enum BackendFramework {
+    #[default]
+    #[serde(alias = "actix_web", alias = "actix")]
+    #[strum(to_string = "actix-web", serialize = "actix_web", serialize = "actix")]
+    ActixWeb,
+    #[strum(to_string = "axum")]
+    Axum,
+    #[strum(to_string = "rocket")]
+    Rocket,
+}
+
+// Crates every generated backend may use regardless of the framework
+pub const COMMON_DEPENDENCIES: &[CrateSpec] = &[
+    CrateSpec {
+        name: "async-trait",
+        version: "0.1",
+        features: &[],
+    },
+    CrateSpec {
+        name: "reqwest",
+        version: "0.12",
+        features: &["json"],
+    },
+    CrateSpec {
+        name: "serde",
+        version: "1.0",
+        features: &["derive"],
+    },
+    CrateSpec {
+        name: "serde_json",
+        version: "1.0",
+        features: &[],
+    },
+    CrateSpec {
+        name: "tokio",
+        version: "1",
+        features: &["full"],
+    },
+];
+
+// Crates added on top when the project stores its data in SQLite
+pub const DATABASE_DEPENDENCIES: &[CrateSpec] = &[CrateSpec {
+    name: "rusqlite",
+    version: "0.40",
+    features: &["bundled"],
+}];
+
+// Crates the vetted auth module needs for password hashing
+pub const AUTH_DEPENDENCIES: &[CrateSpec] = &[CrateSpec {
+    name: "argon2",
+    version: "0.5",
+    features: &["std"],
+}];
+
+pub trait BackendTemplate: Debug + Sync {
+    fn framework(&self) -> BackendFramework;
+
+    // Starter code the backend developer rewrites for the project
+    fn code_template_path(&self) -> &'static str;
+
+    // Crates specific to this framework, on top of COMMON_DEPENDENCIES
+    fn framework_dependencies(&self) -> &'static [CrateSpec];
+
+    // Framework specific guidance appended to code writing prompts
+    fn prompt_fragment(&self) -> &'static str;
+
+    // Snippet showing how routes are declared, used when extracting endpoints
+    fn route_declaration_example(&self) -> &'static str;
+
+    // How generated tests drive the app, used when writing the test module
+    fn test_harness_notes(&self) -> &'static str;
+
+    // Vetted session auth module copied next to main.rs when users log in
+    fn auth_template_path(&self) -> &'static str;
+
+    // How main.rs wires the auth module into the app
+    fn auth_integration_notes(&self) -> &'static str;
+
+    // Deterministic route table of generated code, None when the LLM has to work it out
+    fn extract_routes(&self, _code: &str) -> Option<Vec<RouteObject>> {
+        None
+    }
+
+    fn dependencies(&self) -> Vec<CrateSpec> {
+        COMMON_DEPENDENCIES
+            .iter()
+            .chain(self.framework_dependencies())
+            .copied()
+            .collect()
+    }
+
+    fn dependency_names(&self) -> String {
+        self.dependencies()
+            .iter()
+            .map(|spec| spec.lib_name())
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    // Context shared by every prompt that writes code for this framework
+    fn prompt_context(&self) -> String {
+        format!(
+            "FRAMEWORK: {} \n ALLOWED LIBRARIES: {} \n FRAMEWORK NOTES: {} The server listens on the host \
+            read from the {} environment variable, defaulting to {}, and the port read from the {} \
+            environment variable, defaulting to {}. \n",
+            self.framework(),
+            self.dependency_names(),
+            self.prompt_fragment(),
+            SERVER_HOST_ENV,
+            DEFAULT_SERVER_HOST,
+            SERVER_PORT_ENV,
+            DEFAULT_SERVER_PORT
+        )
+    }
+}
@@ -0,0 +1,178 @@
+// Vetted authentication module, copied into generated projects as auth.rs
+use actix_web::{
+    cookie::{time::Duration, Cookie, SameSite},
+    dev::Payload,
+    error::ErrorUnauthorized,
+    web, FromRequest, HttpRequest, HttpResponse, Responder,
+};
+use argon2::{
+    password_hash::{
+        rand_core::{OsRng, RngCore},
+        PasswordHash, PasswordHasher, PasswordVerifier, SaltString,
+    },
+    Argon2,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    future::{ready, Ready},
+    sync::{Mutex, OnceLock},
+};
+
+pub const SESSION_COOKIE: &str = "session_id";
+const MIN_PASSWORD_LENGTH: usize = 8;
+
+struct UserRecord {
+    id: u64,
+    username: String,
+    password_hash: String,
+}
+
+#[derive(Default)]
+struct AuthStore {
+    users: HashMap<String, UserRecord>,
+    sessions: HashMap<String, String>,
+    next_id: u64,
+}
+
+fn store() -> &'static Mutex<AuthStore> {
+    static STORE: OnceLock<Mutex<AuthStore>> = OnceLock::new();
+    STORE.get_or_init(|| Mutex::new(AuthStore::default()))
+}
+
+fn hash_password(password: &str) -> Option<String> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .ok()
+        .map(|hash| hash.to_string())
+}
+
+fn verify_password(password: &str, password_hash: &str) -> bool {
+    PasswordHash::new(password_hash)
+        .map(|parsed| {
+            Argon2::default()
+                .verify_password(password.as_bytes(), &parsed)
+                .is_ok()
+        })
+        .unwrap_or(false)
+}
+
+fn new_session_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[derive(Deserialize)]
+pub struct Credentials {
+    pub username: String,
+    pub password: String,
+}
+
+// User behind the session cookie, add it as a handler argument to require a login
+#[derive(Serialize, Debug, Clone)]
+pub struct AuthenticatedUser {
+    pub id: u64,
+    pub username: String,
+}
+
+impl FromRequest for AuthenticatedUser {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let user = req.cookie(SESSION_COOKIE).and_then(|cookie| {
+            let store = store().lock().unwrap();
+            let username = store.sessions.get(cookie.value())?;
+            store.users.get(username).map(|user| AuthenticatedUser {
+                id: user.id,
+                username: user.username.clone(),
+            })
+        });
+
+        ready(user.ok_or_else(|| ErrorUnauthorized("login required")))
+    }
+}
+
+async fn signup(credentials: web::Json<Credentials>) -> impl Responder {
+    let credentials = credentials.into_inner();
+    if credentials.username.trim().is_empty() || credentials.password.len() < MIN_PASSWORD_LENGTH
+    {
+        return HttpResponse::BadRequest().body("username and a password of 8+ characters required");
+    }
+
+    let Some(password_hash) = hash_password(&credentials.password) else {
+        return HttpResponse::InternalServerError().finish();
+    };
+
+    let mut store = store().lock().unwrap();
+    if store.users.contains_key(&credentials.username) {
+        return HttpResponse::Conflict().body("username taken");
+    }
+
+    store.next_id += 1;
+    let user = AuthenticatedUser {
+        id: store.next_id,
+        username: credentials.username.clone(),
+    };
+    store.users.insert(
+        credentials.username,
+        UserRecord {
+            id: user.id,
+            username: user.username.clone(),
+            password_hash,
+        },
+    );
+
+    HttpResponse::Created().json(user)
+}
+
+async fn login(credentials: web::Json<Credentials>) -> impl Responder {
+    let mut store = store().lock().unwrap();
+    let user = match store.users.get(&credentials.username) {
+        Some(user) if verify_password(&credentials.password, &user.password_hash) => {
+            AuthenticatedUser {
+                id: user.id,
+                username: user.username.clone(),
+            }
+        }
+        _ => return HttpResponse::Unauthorized().body("invalid username or password"),
+    };
+
+    let token = new_session_token();
+    store.sessions.insert(token.clone(), user.username.clone());
+
+    let cookie = Cookie::build(SESSION_COOKIE, token)
+        .path("/")
+        .http_only(true)
+        .same_site(SameSite::Lax)
+        .finish();
+
+    HttpResponse::Ok().cookie(cookie).json(user)
+}
+
+async fn logout(req: HttpRequest) -> impl Responder {
+    if let Some(cookie) = req.cookie(SESSION_COOKIE) {
+        store().lock().unwrap().sessions.remove(cookie.value());
+    }
+
+    let cookie = Cookie::build(SESSION_COOKIE, "")
+        .path("/")
+        .max_age(Duration::ZERO)
+        .finish();
+
+    HttpResponse::Ok().cookie(cookie).finish()
+}
+
+async fn me(user: AuthenticatedUser) -> impl Responder {
+    HttpResponse::Ok().json(user)
+}
+
+// Register the /auth routes, i.e. App::new().configure(auth::configure)
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.route("/auth/signup", web::post().to(signup))
+        .route("/auth/login", web::post().to(login))
+        .route("/auth/logout", web::post().to(logout))
+        .route("/auth/me", web::get().to(me));
+}
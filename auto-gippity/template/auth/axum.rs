@@ -0,0 +1,188 @@
+// Vetted authentication module, copied into generated projects as auth.rs
+use argon2::{
+    password_hash::{
+        rand_core::{OsRng, RngCore},
+        PasswordHash, PasswordHasher, PasswordVerifier, SaltString,
+    },
+    Argon2,
+};
+use axum::{
+    extract::FromRequestParts,
+    http::{header, request::Parts, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+};
+
+pub const SESSION_COOKIE: &str = "session_id";
+const MIN_PASSWORD_LENGTH: usize = 8;
+
+struct UserRecord {
+    id: u64,
+    username: String,
+    password_hash: String,
+}
+
+#[derive(Default)]
+struct AuthStore {
+    users: HashMap<String, UserRecord>,
+    sessions: HashMap<String, String>,
+    next_id: u64,
+}
+
+fn store() -> &'static Mutex<AuthStore> {
+    static STORE: OnceLock<Mutex<AuthStore>> = OnceLock::new();
+    STORE.get_or_init(|| Mutex::new(AuthStore::default()))
+}
+
+fn hash_password(password: &str) -> Option<String> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .ok()
+        .map(|hash| hash.to_string())
+}
+
+fn verify_password(password: &str, password_hash: &str) -> bool {
+    PasswordHash::new(password_hash)
+        .map(|parsed| {
+            Argon2::default()
+                .verify_password(password.as_bytes(), &parsed)
+                .is_ok()
+        })
+        .unwrap_or(false)
+}
+
+fn new_session_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[derive(Deserialize)]
+pub struct Credentials {
+    pub username: String,
+    pub password: String,
+}
+
+// User behind the session cookie, add it as a handler argument to require a login
+#[derive(Serialize, Debug, Clone)]
+pub struct AuthenticatedUser {
+    pub id: u64,
+    pub username: String,
+}
+
+fn session_token(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .find_map(|pair| {
+            let (name, value) = pair.trim().split_once('=')?;
+            (name == SESSION_COOKIE).then(|| value.to_string())
+        })
+}
+
+fn session_user(headers: &HeaderMap) -> Option<AuthenticatedUser> {
+    let token = session_token(headers)?;
+    let store = store().lock().unwrap();
+    let username = store.sessions.get(&token)?;
+    store.users.get(username).map(|user| AuthenticatedUser {
+        id: user.id,
+        username: user.username.clone(),
+    })
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for AuthenticatedUser {
+    type Rejection = (StatusCode, &'static str);
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        session_user(&parts.headers).ok_or((StatusCode::UNAUTHORIZED, "login required"))
+    }
+}
+
+async fn signup(Json(credentials): Json<Credentials>) -> Response {
+    if credentials.username.trim().is_empty() || credentials.password.len() < MIN_PASSWORD_LENGTH
+    {
+        return (
+            StatusCode::BAD_REQUEST,
+            "username and a password of 8+ characters required",
+        )
+            .into_response();
+    }
+
+    let Some(password_hash) = hash_password(&credentials.password) else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+
+    let mut store = store().lock().unwrap();
+    if store.users.contains_key(&credentials.username) {
+        return (StatusCode::CONFLICT, "username taken").into_response();
+    }
+
+    store.next_id += 1;
+    let user = AuthenticatedUser {
+        id: store.next_id,
+        username: credentials.username.clone(),
+    };
+    store.users.insert(
+        credentials.username,
+        UserRecord {
+            id: user.id,
+            username: user.username.clone(),
+            password_hash,
+        },
+    );
+
+    (StatusCode::CREATED, Json(user)).into_response()
+}
+
+async fn login(Json(credentials): Json<Credentials>) -> Response {
+    let mut store = store().lock().unwrap();
+    let user = match store.users.get(&credentials.username) {
+        Some(user) if verify_password(&credentials.password, &user.password_hash) => {
+            AuthenticatedUser {
+                id: user.id,
+                username: user.username.clone(),
+            }
+        }
+        _ => return (StatusCode::UNAUTHORIZED, "invalid username or password").into_response(),
+    };
+
+    let token = new_session_token();
+    store.sessions.insert(token.clone(), user.username.clone());
+
+    let cookie = format!(
+        "{}={}; Path=/; HttpOnly; SameSite=Lax",
+        SESSION_COOKIE, token
+    );
+    ([(header::SET_COOKIE, cookie)], Json(user)).into_response()
+}
+
+async fn logout(headers: HeaderMap) -> Response {
+    if let Some(token) = session_token(&headers) {
+        store().lock().unwrap().sessions.remove(&token);
+    }
+
+    let cookie = format!("{}=; Path=/; Max-Age=0", SESSION_COOKIE);
+    ([(header::SET_COOKIE, cookie)], StatusCode::OK).into_response()
+}
+
+async fn me(user: AuthenticatedUser) -> Json<AuthenticatedUser> {
+    Json(user)
+}
+
+// The /auth routes, merged into the app with Router::new().merge(auth::router())
+pub fn router<S: Clone + Send + Sync + 'static>() -> Router<S> {
+    Router::new()
+        .route("/auth/signup", post(signup))
+        .route("/auth/login", post(login))
+        .route("/auth/logout", post(logout))
+        .route("/auth/me", get(me))
+}
@@ -0,0 +1,183 @@
+// Vetted authentication module, copied into generated projects as auth.rs
+use argon2::{
+    password_hash::{
+        rand_core::{OsRng, RngCore},
+        PasswordHash, PasswordHasher, PasswordVerifier, SaltString,
+    },
+    Argon2,
+};
+use rocket::{
+    http::{Cookie, CookieJar, SameSite, Status},
+    request::{FromRequest, Outcome, Request},
+    response::status::Custom,
+    serde::json::Json,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+};
+
+pub const SESSION_COOKIE: &str = "session_id";
+const MIN_PASSWORD_LENGTH: usize = 8;
+
+struct UserRecord {
+    id: u64,
+    username: String,
+    password_hash: String,
+}
+
+#[derive(Default)]
+struct AuthStore {
+    users: HashMap<String, UserRecord>,
+    sessions: HashMap<String, String>,
+    next_id: u64,
+}
+
+fn store() -> &'static Mutex<AuthStore> {
+    static STORE: OnceLock<Mutex<AuthStore>> = OnceLock::new();
+    STORE.get_or_init(|| Mutex::new(AuthStore::default()))
+}
+
+fn hash_password(password: &str) -> Option<String> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .ok()
+        .map(|hash| hash.to_string())
+}
+
+fn verify_password(password: &str, password_hash: &str) -> bool {
+    PasswordHash::new(password_hash)
+        .map(|parsed| {
+            Argon2::default()
+                .verify_password(password.as_bytes(), &parsed)
+                .is_ok()
+        })
+        .unwrap_or(false)
+}
+
+fn new_session_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[derive(Deserialize)]
+pub struct Credentials {
+    pub username: String,
+    pub password: String,
+}
+
+// User behind the session cookie, add it as a handler argument to require a login
+#[derive(Serialize, Debug, Clone)]
+pub struct AuthenticatedUser {
+    pub id: u64,
+    pub username: String,
+}
+
+fn session_user(cookies: &CookieJar<'_>) -> Option<AuthenticatedUser> {
+    let cookie = cookies.get(SESSION_COOKIE)?;
+    let store = store().lock().unwrap();
+    let username = store.sessions.get(cookie.value())?;
+    store.users.get(username).map(|user| AuthenticatedUser {
+        id: user.id,
+        username: user.username.clone(),
+    })
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AuthenticatedUser {
+    type Error = &'static str;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match session_user(request.cookies()) {
+            Some(user) => Outcome::Success(user),
+            None => Outcome::Error((Status::Unauthorized, "login required")),
+        }
+    }
+}
+
+type AuthResponse = Result<Custom<Json<AuthenticatedUser>>, Custom<&'static str>>;
+
+#[rocket::post("/auth/signup", data = "<credentials>")]
+fn signup(credentials: Json<Credentials>) -> AuthResponse {
+    let credentials = credentials.into_inner();
+    if credentials.username.trim().is_empty() || credentials.password.len() < MIN_PASSWORD_LENGTH
+    {
+        return Err(Custom(
+            Status::BadRequest,
+            "username and a password of 8+ characters required",
+        ));
+    }
+
+    let password_hash = hash_password(&credentials.password)
+        .ok_or(Custom(Status::InternalServerError, "could not hash password"))?;
+
+    let mut store = store().lock().unwrap();
+    if store.users.contains_key(&credentials.username) {
+        return Err(Custom(Status::Conflict, "username taken"));
+    }
+
+    store.next_id += 1;
+    let user = AuthenticatedUser {
+        id: store.next_id,
+        username: credentials.username.clone(),
+    };
+    store.users.insert(
+        credentials.username,
+        UserRecord {
+            id: user.id,
+            username: user.username.clone(),
+            password_hash,
+        },
+    );
+
+    Ok(Custom(Status::Created, Json(user)))
+}
+
+#[rocket::post("/auth/login", data = "<credentials>")]
+fn login(credentials: Json<Credentials>, cookies: &CookieJar<'_>) -> AuthResponse {
+    let mut store = store().lock().unwrap();
+    let user = match store.users.get(&credentials.username) {
+        Some(user) if verify_password(&credentials.password, &user.password_hash) => {
+            AuthenticatedUser {
+                id: user.id,
+                username: user.username.clone(),
+            }
+        }
+        _ => return Err(Custom(Status::Unauthorized, "invalid username or password")),
+    };
+
+    let token = new_session_token();
+    store.sessions.insert(token.clone(), user.username.clone());
+
+    cookies.add(
+        Cookie::build((SESSION_COOKIE, token))
+            .path("/")
+            .http_only(true)
+            .same_site(SameSite::Lax),
+    );
+
+    Ok(Custom(Status::Ok, Json(user)))
+}
+
+#[rocket::post("/auth/logout")]
+fn logout(cookies: &CookieJar<'_>) -> Status {
+    if let Some(cookie) = cookies.get(SESSION_COOKIE) {
+        store().lock().unwrap().sessions.remove(cookie.value());
+    }
+
+    cookies.remove(Cookie::build(SESSION_COOKIE).path("/"));
+    Status::Ok
+}
+
+#[rocket::get("/auth/me")]
+fn me(user: AuthenticatedUser) -> Json<AuthenticatedUser> {
+    Json(user)
+}
+
+// The /auth routes, mounted with .mount("/", auth::routes())
+pub fn routes() -> Vec<rocket::Route> {
+    rocket::routes![signup, login, logout, me]
+}
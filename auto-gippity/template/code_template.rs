@@ -1 +0,0 @@
-// code template
@@ -0,0 +1,115 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs,
+    sync::{Arc, Mutex},
+};
+use tower_http::cors::CorsLayer;
+
+const DATABASE_PATH: &str = "database.json";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct Task {
+    id: u64,
+    name: String,
+    completed: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct Database {
+    tasks: HashMap<u64, Task>,
+}
+
+impl Database {
+    fn insert(&mut self, task: Task) {
+        self.tasks.insert(task.id, task);
+    }
+
+    fn get(&self, id: &u64) -> Option<&Task> {
+        self.tasks.get(id)
+    }
+
+    fn get_all(&self) -> Vec<Task> {
+        self.tasks.values().cloned().collect()
+    }
+
+    fn delete(&mut self, id: &u64) -> Option<Task> {
+        self.tasks.remove(id)
+    }
+
+    fn save_to_file(&self) -> std::io::Result<()> {
+        let data = serde_json::to_string(&self)?;
+        fs::write(DATABASE_PATH, data)
+    }
+
+    fn load_from_file() -> std::io::Result<Self> {
+        let data = fs::read_to_string(DATABASE_PATH)?;
+        Ok(serde_json::from_str(&data)?)
+    }
+}
+
+type AppState = Arc<Mutex<Database>>;
+
+async fn create_task(State(state): State<AppState>, Json(task): Json<Task>) -> StatusCode {
+    let mut db = state.lock().unwrap();
+    db.insert(task);
+    let _ = db.save_to_file();
+    StatusCode::OK
+}
+
+async fn read_task(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+) -> Result<Json<Task>, StatusCode> {
+    let db = state.lock().unwrap();
+    db.get(&id).cloned().map(Json).ok_or(StatusCode::NOT_FOUND)
+}
+
+async fn read_all_tasks(State(state): State<AppState>) -> Json<Vec<Task>> {
+    let db = state.lock().unwrap();
+    Json(db.get_all())
+}
+
+async fn update_task(State(state): State<AppState>, Json(task): Json<Task>) -> StatusCode {
+    let mut db = state.lock().unwrap();
+    db.insert(task);
+    let _ = db.save_to_file();
+    StatusCode::OK
+}
+
+async fn delete_task(State(state): State<AppState>, Path(id): Path<u64>) -> StatusCode {
+    let mut db = state.lock().unwrap();
+    db.delete(&id);
+    let _ = db.save_to_file();
+    StatusCode::OK
+}
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let db = Database::load_from_file().unwrap_or_default();
+    let state: AppState = Arc::new(Mutex::new(db));
+
+    let app = Router::new()
+        .route("/task", post(create_task).get(read_all_tasks))
+        .route(
+            "/task/{id}",
+            get(read_task).put(update_task).delete(delete_task),
+        )
+        .layer(CorsLayer::permissive())
+        .with_state(state);
+
+    let port = std::env::var("PORT")
+        .ok()
+        .and_then(|port| port.parse::<u16>().ok())
+        .unwrap_or(8080);
+    let host = std::env::var("HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+
+    let listener = tokio::net::TcpListener::bind((host.as_str(), port)).await?;
+    axum::serve(listener, app).await
+}
@@ -0,0 +1,113 @@
+#[macro_use]
+extern crate rocket;
+
+use rocket::{http::Status, serde::json::Json, State};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, sync::Mutex};
+
+const DATABASE_PATH: &str = "database.json";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct Task {
+    id: u64,
+    name: String,
+    completed: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct Database {
+    tasks: HashMap<u64, Task>,
+}
+
+impl Database {
+    fn insert(&mut self, task: Task) {
+        self.tasks.insert(task.id, task);
+    }
+
+    fn get(&self, id: &u64) -> Option<&Task> {
+        self.tasks.get(id)
+    }
+
+    fn get_all(&self) -> Vec<Task> {
+        self.tasks.values().cloned().collect()
+    }
+
+    fn delete(&mut self, id: &u64) -> Option<Task> {
+        self.tasks.remove(id)
+    }
+
+    fn save_to_file(&self) -> std::io::Result<()> {
+        let data = serde_json::to_string(&self)?;
+        fs::write(DATABASE_PATH, data)
+    }
+
+    fn load_from_file() -> std::io::Result<Self> {
+        let data = fs::read_to_string(DATABASE_PATH)?;
+        Ok(serde_json::from_str(&data)?)
+    }
+}
+
+struct AppState {
+    db: Mutex<Database>,
+}
+
+#[post("/task", data = "<task>")]
+fn create_task(state: &State<AppState>, task: Json<Task>) -> Status {
+    let mut db = state.db.lock().unwrap();
+    db.insert(task.into_inner());
+    let _ = db.save_to_file();
+    Status::Ok
+}
+
+#[get("/task/<id>")]
+fn read_task(state: &State<AppState>, id: u64) -> Option<Json<Task>> {
+    let db = state.db.lock().unwrap();
+    db.get(&id).cloned().map(Json)
+}
+
+#[get("/task")]
+fn read_all_tasks(state: &State<AppState>) -> Json<Vec<Task>> {
+    let db = state.db.lock().unwrap();
+    Json(db.get_all())
+}
+
+#[put("/task/<_id>", data = "<task>")]
+fn update_task(state: &State<AppState>, _id: u64, task: Json<Task>) -> Status {
+    let mut db = state.db.lock().unwrap();
+    db.insert(task.into_inner());
+    let _ = db.save_to_file();
+    Status::Ok
+}
+
+#[delete("/task/<id>")]
+fn delete_task(state: &State<AppState>, id: u64) -> Status {
+    let mut db = state.db.lock().unwrap();
+    db.delete(&id);
+    let _ = db.save_to_file();
+    Status::Ok
+}
+
+#[launch]
+fn rocket() -> _ {
+    let db = Database::load_from_file().unwrap_or_default();
+    let port = std::env::var("PORT")
+        .ok()
+        .and_then(|port| port.parse::<u16>().ok())
+        .unwrap_or(8080);
+    let address = std::env::var("HOST")
+        .ok()
+        .and_then(|host| host.parse().ok())
+        .unwrap_or(std::net::Ipv4Addr::LOCALHOST.into());
+    let config = rocket::Config {
+        address,
+        port,
+        ..rocket::Config::default()
+    };
+
+    rocket::custom(config)
+        .manage(AppState { db: Mutex::new(db) })
+        .mount(
+            "/",
+            routes![create_task, read_task, read_all_tasks, update_task, delete_task],
+        )
+}